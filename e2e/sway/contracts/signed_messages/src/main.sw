@@ -0,0 +1,72 @@
+contract;
+
+use core::codec::encode;
+use std::{
+    b512::B512,
+    bytes::Bytes,
+    crypto::{
+        message::Message,
+        secp256k1::Secp256k1,
+    },
+    hash::{
+        Hash,
+        sha256,
+    },
+    primitive_conversions::u64::*,
+    string::String,
+};
+
+struct Order {
+    owner: Address,
+    amount: u64,
+}
+
+abi SignedMessages {
+    fn personal_message_signer(message: Bytes, signature: B512) -> Address;
+    fn order_signer(chain_id: u64, order: Order, signature: B512) -> Address;
+}
+
+fn push_decimal(ref mut bytes: Bytes, value: u64) {
+    let mut divisor = 1;
+    while value / divisor >= 10 {
+        divisor = divisor * 10;
+    }
+
+    while divisor > 0 {
+        let digit = (value / divisor) % 10;
+        bytes.push(48u8 + digit.try_as_u8().unwrap());
+        divisor = divisor / 10;
+    }
+}
+
+fn signer(hash: b256, signature: B512) -> Address {
+    Secp256k1::from(signature).address(Message::from(hash)).unwrap()
+}
+
+// ANCHOR: signed_messages
+impl SignedMessages for Contract {
+    fn personal_message_signer(message: Bytes, signature: B512) -> Address {
+        // "\x19Fuel Signed Message:\n" ++ decimal length ++ message
+        let mut prefixed = Bytes::new();
+        prefixed.push(0x19u8);
+        let mut text = Bytes::from(String::from_ascii_str("Fuel Signed Message:"));
+        prefixed.append(text);
+        prefixed.push(0x0Au8);
+        push_decimal(prefixed, message.len());
+        let mut message = message;
+        prefixed.append(message);
+
+        signer(sha256(prefixed), signature)
+    }
+
+    fn order_signer(chain_id: u64, order: Order, signature: B512) -> Address {
+        let domain_separator = sha256((chain_id, ContractId::this()));
+        let data_hash = sha256(Bytes::from(encode(order)));
+
+        signer(
+            sha256((0x19u8, 0x01u8, domain_separator, data_hash)),
+            signature,
+        )
+    }
+}
+// ANCHOR_END: signed_messages
@@ -2,9 +2,10 @@ use fuel_tx::SubAssetId;
 use fuels::{
     core::codec::DecoderConfig,
     prelude::*,
-    tx::ContractIdExt,
+    tx::{ContractIdExt, TxId},
     types::{AsciiString, Bits256, SizedAsciiString, errors::transaction::Reason},
 };
+use futures::StreamExt;
 
 #[tokio::test]
 async fn parse_logged_variables() -> Result<()> {
@@ -1847,6 +1848,53 @@ async fn script_heap_log() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn dynamic_contract_calls_decode_logs_and_revert_messages() -> Result<()> {
+    use fuels::programs::contract::DynamicContract;
+
+    setup_program_test!(
+        Wallets("wallet"),
+        Abigen(Contract(
+            name = "LogContract",
+            project = "e2e/sway/logs/contract_logs"
+        )),
+        Deploy(
+            name = "contract_instance",
+            contract = "LogContract",
+            wallet = "wallet",
+            random_salt = false,
+        ),
+    );
+    let abi =
+        std::fs::read_to_string("sway/logs/contract_logs/out/release/contract_logs-abi.json")?;
+    let contract = DynamicContract::from_json_abi(contract_instance.id(), wallet.clone(), abi)?;
+
+    let response = contract
+        .method("produce_logs_values", &[])?
+        .call_dynamic()
+        .await?;
+    let expected_response = contract_instance
+        .methods()
+        .produce_logs_values()
+        .call()
+        .await?;
+
+    assert_eq!(
+        response.decode_logs().filter_succeeded(),
+        expected_response.decode_logs().filter_succeeded()
+    );
+    assert_eq!(response.decode_logs_with_type::<u64>()?, vec![64]);
+
+    let error = contract
+        .method("produce_panic_with_error", &[])?
+        .call_dynamic()
+        .await
+        .expect_err("should return a revert error");
+    assert_revert_containing_msg("some complex error B: ", error);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn contract_panic() -> Result<()> {
     setup_program_test!(
@@ -2103,3 +2151,67 @@ async fn script_with_contract_panic() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn contract_logs_are_streamed_as_blocks_are_produced_and_resumed() -> Result<()> {
+    setup_program_test!(
+        Wallets("wallet"),
+        Abigen(Contract(
+            name = "LogContract",
+            project = "e2e/sway/logs/contract_logs"
+        )),
+        Deploy(
+            name = "contract_instance",
+            contract = "LogContract",
+            wallet = "wallet",
+            random_salt = false,
+        ),
+    );
+    let provider = wallet.provider().clone();
+    let from_height = provider.latest_block_height().await? + 1;
+    let logs_of = |tx_id: Option<TxId>| vec![(tx_id.expect("call was submitted"), 64u64)];
+
+    let existing = contract_instance
+        .methods()
+        .produce_logs_values()
+        .call()
+        .await?;
+
+    let mut logs = Box::pin(provider.stream_contract_logs::<u64>(
+        contract_instance.contract_id(),
+        contract_instance.log_decoder(),
+        from_height,
+    ));
+
+    // catches up over the existing blocks
+    let caught_up = logs.next().await.expect("stream is open")?;
+    assert_eq!(caught_up.logs, logs_of(existing.tx_id));
+
+    // then follows the blocks produced while it is consumed
+    let methods = contract_instance.methods();
+    let calling = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        methods.produce_logs_values().call().await
+    });
+    let followed = logs.next().await.expect("stream is open")?;
+    let new = calling.await.expect("calling the contract")?;
+    assert_eq!(followed.logs, logs_of(new.tx_id));
+    assert!(followed.block_height > caught_up.block_height);
+    drop(logs);
+
+    // and resumes from the height following the last processed logs
+    let resumed_call = contract_instance
+        .methods()
+        .produce_logs_values()
+        .call()
+        .await?;
+    let mut resumed = Box::pin(provider.stream_contract_logs::<u64>(
+        contract_instance.contract_id(),
+        contract_instance.log_decoder(),
+        followed.block_height + 1,
+    ));
+    let resumed_logs = resumed.next().await.expect("stream is open")?;
+    assert_eq!(resumed_logs.logs, logs_of(resumed_call.tx_id));
+
+    Ok(())
+}
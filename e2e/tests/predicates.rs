@@ -1437,3 +1437,144 @@ async fn predicate_tx_input_output() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn multisig_account_transfers_with_local_signers() -> Result<()> {
+    use fuels::accounts::multisig::{MultisigAccount, MultisigConfigurables};
+
+    let signers = (0..3)
+        .map(|_| PrivateKeySigner::random(&mut thread_rng()))
+        .collect::<Vec<_>>();
+    let owners = signers.iter().map(|signer| signer.address()).collect();
+    let config = MultisigConfigurables::new(owners, 2)?;
+
+    let multisig = MultisigAccount::new(config.clone());
+    let (provider, multisig_balance, receiver, receiver_balance, asset_id, _) =
+        setup_predicate_test(multisig.address(), 2, 0, 100).await?;
+
+    let multisig = MultisigAccount::new(config.clone())
+        .with_provider(provider.clone())
+        .with_signer(signers[0].clone())?;
+
+    // a single signer isn't enough
+    let err = multisig
+        .transfer(receiver.address(), 10, asset_id, TxPolicies::default())
+        .await
+        .expect_err("should fail");
+    assert!(err.to_string().contains("requires 2 signatures"));
+
+    let multisig = multisig.with_signer(signers[2].clone())?;
+    let tx_status = multisig
+        .transfer(receiver.address(), 10, asset_id, TxPolicies::default())
+        .await?
+        .tx_status;
+
+    assert_address_balance(
+        &multisig.address(),
+        &provider,
+        &asset_id,
+        multisig_balance - 10 - tx_status.total_fee(),
+    )
+    .await;
+    assert_address_balance(
+        &receiver.address(),
+        &provider,
+        &asset_id,
+        receiver_balance + 10,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn multisig_account_collects_remote_signatures() -> Result<()> {
+    use fuels::accounts::multisig::{MultisigAccount, MultisigConfigurables};
+
+    let signers = (0..3)
+        .map(|_| PrivateKeySigner::random(&mut thread_rng()))
+        .collect::<Vec<_>>();
+    let owners = signers.iter().map(|signer| signer.address()).collect();
+    let config = MultisigConfigurables::new(owners, 2)?;
+
+    let (provider, _, receiver, receiver_balance, asset_id, _) =
+        setup_predicate_test(MultisigAccount::new(config.clone()).address(), 2, 0, 100).await?;
+
+    let multisig = MultisigAccount::new(config)
+        .with_provider(provider.clone())
+        .with_signer(signers[1].clone())?;
+
+    let inputs = multisig
+        .get_asset_inputs_for_amount(asset_id, 10, None)
+        .await?;
+    let outputs = multisig.get_asset_outputs_for_amount(receiver.address(), asset_id, 10);
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    multisig.add_partial_witnesses(&mut tb)?;
+    multisig.adjust_for_fee(&mut tb, 10).await?;
+    let tx = tb.build(&provider).await?;
+
+    let partially_signed = multisig.partially_signed(tx).await?;
+    assert_eq!(partially_signed.signed_by(), vec![signers[1].address()]);
+
+    // the remaining signature is added elsewhere
+    let mut remote = multisig.import_partially_signed(&partially_signed.to_json()?)?;
+    remote.sign(&signers[0]).await?;
+    assert!(remote.is_complete());
+
+    let tx = multisig
+        .import_partially_signed(&remote.to_json()?)?
+        .into_transaction::<ScriptTransaction>()?;
+    provider.send_transaction_and_await_commit(tx).await?;
+
+    assert_address_balance(
+        &receiver.address(),
+        &provider,
+        &asset_id,
+        receiver_balance + 10,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn signature_accounts_spend_with_secp256r1_and_ed25519_keys() -> Result<()> {
+    use fuels::accounts::{
+        signature_account::{PredicateSigner, SignatureAccount},
+        signers::{ed25519::Ed25519Signer, secp256r1::Secp256r1Signer},
+    };
+
+    async fn transfer_from(signer: impl PredicateSigner) -> Result<()> {
+        let account = SignatureAccount::new(signer);
+        let (provider, account_balance, receiver, receiver_balance, asset_id, _) =
+            setup_predicate_test(account.address(), 2, 0, 100).await?;
+        let account = account.with_provider(provider.clone());
+
+        let tx_status = account
+            .transfer(receiver.address(), 10, asset_id, TxPolicies::default())
+            .await?
+            .tx_status;
+
+        assert_address_balance(
+            &account.address(),
+            &provider,
+            &asset_id,
+            account_balance - 10 - tx_status.total_fee(),
+        )
+        .await;
+        assert_address_balance(
+            &receiver.address(),
+            &provider,
+            &asset_id,
+            receiver_balance + 10,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    transfer_from(Secp256r1Signer::random(&mut thread_rng())).await?;
+    transfer_from(Ed25519Signer::random(&mut thread_rng())).await?;
+
+    Ok(())
+}
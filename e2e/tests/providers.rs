@@ -7,12 +7,14 @@ use fuels::{
     accounts::{
         Account,
         signers::{fake::FakeSigner, private_key::PrivateKeySigner},
+        tip_bump::{TipBumpConfig, TipStrategy},
     },
     client::{PageDirection, PaginationRequest},
     prelude::*,
     tx::{ContractIdExt, Receipt, Witness},
     types::{
         coin_type::CoinType,
+        gas_price_strategy::{RecentTipsPercentile, Urgent},
         message::Message,
         transaction_builders::{BuildableTransaction, ScriptTransactionBuilder},
         tx_status::{Failure, Success, TxStatus},
@@ -67,6 +69,52 @@ async fn test_provider_launch_and_connect() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_offline_node_transfer_and_contract_call() -> Result<()> {
+    abigen!(Contract(
+        name = "MyContract",
+        abi = "e2e/sway/contracts/contract_test/out/release/contract_test-abi.json"
+    ));
+
+    let signer = PrivateKeySigner::random(&mut thread_rng());
+    let coins = setup_single_asset_coins(
+        signer.address(),
+        AssetId::zeroed(),
+        DEFAULT_NUM_COINS,
+        DEFAULT_COIN_AMOUNT,
+    );
+    let provider = OfflineNode::new(coins, vec![], None).provider().await?;
+    let wallet = Wallet::new(signer, provider.clone());
+
+    let recipient = Wallet::random(&mut thread_rng(), provider.clone());
+    wallet
+        .transfer(
+            recipient.address(),
+            100,
+            AssetId::zeroed(),
+            TxPolicies::default(),
+        )
+        .await?;
+    assert_eq!(recipient.get_asset_balance(&AssetId::zeroed()).await?, 100);
+
+    let contract_id = Contract::load_from(
+        "sway/contracts/contract_test/out/release/contract_test.bin",
+        LoadConfiguration::default(),
+    )?
+    .deploy(&wallet, TxPolicies::default())
+    .await?
+    .contract_id;
+
+    let response = MyContract::new(contract_id, wallet)
+        .methods()
+        .initialize_counter(42)
+        .call()
+        .await?;
+    assert_eq!(42, response.value);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_network_error() -> Result<()> {
     abigen!(Contract(
@@ -1132,6 +1180,95 @@ async fn can_produce_blocks_with_trig_never() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn pending_transactions_are_replaced_with_a_higher_tip() -> Result<()> {
+    let config = NodeConfig {
+        block_production: Trigger::Never,
+        ..NodeConfig::default()
+    };
+    let wallet =
+        launch_custom_provider_and_get_wallets(WalletsConfig::default(), Some(config), None)
+            .await?[0]
+            .clone();
+    let provider = wallet.provider().clone();
+
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+    let inputs = wallet
+        .get_asset_inputs_for_amount(base_asset_id, 100, None)
+        .await?;
+    let outputs = wallet.get_asset_outputs_for_amount(Address::default(), base_asset_id, 100);
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    wallet.add_witnesses(&mut tb)?;
+
+    // ANCHOR: tip_bumping
+    let strategy = TipStrategy::Multiplier {
+        factor: 2.0,
+        min_increase: 10,
+    };
+    let config =
+        TipBumpConfig::new(strategy, std::time::Duration::from_secs(1), 3)?.with_max_tip(1_000);
+
+    let sending = tokio::spawn(async move { wallet.send_with_tip_bumping(tb, config).await });
+    // ANCHOR_END: tip_bumping
+
+    // no block is produced until the transaction was replaced
+    tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+    provider.produce_blocks(1, None).await?;
+
+    let response = sending.await.expect("task should not panic")?;
+    let tx = match provider
+        .get_transaction_by_id(&response.tx_id)
+        .await?
+        .expect("transaction should exist")
+        .transaction
+    {
+        TransactionType::Script(tx) => tx,
+        _ => panic!("expected a script transaction"),
+    };
+    assert!(tx.tip().unwrap_or_default() >= 10);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn gas_price_strategies_tip_like_recent_transactions() -> Result<()> {
+    let wallet = launch_provider_and_get_wallet().await?;
+    let provider = wallet.provider();
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+
+    for tip in [10, 20, 30] {
+        let tx_policies = TxPolicies::default().with_tip(tip);
+        wallet
+            .transfer(Address::default(), 1, base_asset_id, tx_policies)
+            .await?;
+    }
+
+    // ANCHOR: gas_price_strategy
+    let inputs = wallet
+        .get_asset_inputs_for_amount(base_asset_id, 100, None)
+        .await?;
+    let outputs = wallet.get_asset_outputs_for_amount(Address::default(), base_asset_id, 100);
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default())
+        .with_gas_price_strategy(RecentTipsPercentile::new(10, 50)?);
+    wallet.add_witnesses(&mut tb)?;
+    // ANCHOR_END: gas_price_strategy
+
+    let tx = tb.clone().build(provider).await?;
+    assert_eq!(tx.tip(), Some(20));
+
+    let tx = tb
+        .with_gas_price_strategy(Urgent::new(10)?)
+        .build(provider)
+        .await?;
+    assert_eq!(tx.tip(), Some(36));
+
+    provider.send_transaction_and_await_commit(tx).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn can_upload_executor_and_trigger_upgrade() -> Result<()> {
     let signer = PrivateKeySigner::random(&mut thread_rng());
@@ -1385,6 +1522,296 @@ async fn contract_call_with_impersonation() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn middleware_intercepts_provider_requests() -> Result<()> {
+    use std::{
+        io,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    // ANCHOR: provider_middleware
+    #[derive(Debug, Default)]
+    struct CountRequests {
+        count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl ProviderMiddleware for CountRequests {
+        async fn handle(&self, rpc: RpcCall, next: Next<'_>) -> io::Result<()> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+
+            next.run(rpc).await
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct FailEveryOther {
+        attempts: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ProviderMiddleware for FailEveryOther {
+        async fn handle(&self, rpc: RpcCall, next: Next<'_>) -> io::Result<()> {
+            if self.attempts.fetch_add(1, Ordering::Relaxed) % 2 == 0 {
+                return Err(io::Error::other(format!(
+                    "injected `{}` failure",
+                    rpc.method()
+                )));
+            }
+
+            next.run(rpc).await
+        }
+    }
+    // ANCHOR_END: provider_middleware
+
+    let wallet = launch_provider_and_get_wallet().await?;
+    let count = Arc::new(AtomicUsize::new(0));
+
+    // ANCHOR: with_middleware
+    let retry_config = RetryConfig::new(2, Backoff::Fixed(std::time::Duration::ZERO))?;
+    let provider = wallet
+        .provider()
+        .clone()
+        .with_retry_config(retry_config)
+        .with_middleware(CountRequests {
+            count: count.clone(),
+        })
+        .with_middleware(FailEveryOther::default());
+    // ANCHOR_END: with_middleware
+
+    // every failed attempt is retried and counted
+    provider.latest_block_height().await?;
+    provider.get_balances(&wallet.address()).await?;
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+
+    // the consensus parameters are served from the cache
+    provider.consensus_parameters().await?;
+    provider.consensus_parameters().await?;
+    assert!(count.load(Ordering::Relaxed) <= 6);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn pooled_provider_routes_requests_to_healthy_nodes() -> Result<()> {
+    let wallet = launch_provider_and_get_wallet().await?;
+    let node_url = wallet.provider().url().to_string();
+    let unreachable_url = "http://127.0.0.1:1/v1/graphql".to_string();
+
+    // ANCHOR: connect_with_pool
+    let config = EndpointPoolConfig {
+        routing: Routing::LowestLatency,
+        health_check_interval: std::time::Duration::from_secs(1),
+        ..Default::default()
+    };
+    let provider = Provider::connect_with_pool(&[unreachable_url, node_url], config).await?;
+    // ANCHOR_END: connect_with_pool
+
+    let endpoints = provider.endpoints();
+    assert!(!endpoints[0].healthy);
+    assert!(endpoints[1].healthy);
+    assert!(endpoints[1].latency.is_some());
+
+    let wallet = Wallet::new(wallet.signer().clone(), provider.clone());
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+    let tx_response = wallet
+        .transfer(Address::default(), 1, base_asset_id, TxPolicies::default())
+        .await?;
+
+    // the status is requested from the node that received the transaction
+    let status = provider.tx_status(&tx_response.tx_id).await?;
+    assert!(matches!(status, TxStatus::Success { .. }));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn provider_caches_immutable_data_in_a_custom_storage() -> Result<()> {
+    use std::{io, path::PathBuf};
+
+    // ANCHOR: cache_storage
+    #[derive(Debug)]
+    struct DiskStorage {
+        dir: PathBuf,
+    }
+
+    #[async_trait::async_trait]
+    impl CacheStorage for DiskStorage {
+        async fn get(&self, key: &CacheKey) -> Result<Option<Vec<u8>>> {
+            match std::fs::read(self.dir.join(key.to_string())) {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        async fn insert(&self, key: CacheKey, value: Vec<u8>) -> Result<()> {
+            let path = self.dir.join(key.to_string());
+            std::fs::create_dir_all(path.parent().expect("keys are nested paths"))?;
+            std::fs::write(path, value)?;
+
+            Ok(())
+        }
+    }
+    // ANCHOR_END: cache_storage
+
+    #[derive(Debug)]
+    struct Offline;
+
+    #[async_trait::async_trait]
+    impl ProviderMiddleware for Offline {
+        async fn handle(&self, _rpc: RpcCall, _next: Next<'_>) -> io::Result<()> {
+            Err(io::Error::other("offline"))
+        }
+    }
+
+    let wallet = launch_provider_and_get_wallet().await?;
+    let dir = tempfile::tempdir()?;
+
+    // ANCHOR: set_cache_storage
+    let mut provider = wallet.provider().clone();
+    provider.set_cache_config(CacheConfig {
+        chain_info_ttl: std::time::Duration::from_secs(1),
+        immutable_data: CachedData {
+            blobs: false,
+            ..CachedData::ALL
+        },
+    });
+    provider.set_cache_storage(DiskStorage {
+        dir: dir.path().to_path_buf(),
+    });
+    // ANCHOR_END: set_cache_storage
+
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+    let tx_id = wallet
+        .transfer(Address::default(), 1, base_asset_id, TxPolicies::default())
+        .await?
+        .tx_id;
+    let height = provider.latest_block_height().await?;
+    provider.block_by_height(height.into()).await?;
+    provider.tx_status(&tx_id).await?;
+    let chain_id = provider.consensus_parameters().await?.chain_id();
+    assert!(
+        dir.path()
+            .join(
+                CacheKey {
+                    chain_id,
+                    entry: CacheEntry::TransactionStatus(tx_id),
+                }
+                .to_string()
+            )
+            .exists()
+    );
+
+    // the immutable data is served from the storage, without reaching the node
+    let offline = provider.clone().with_middleware(Offline);
+    let block = offline.block_by_height(height.into()).await?;
+    assert_eq!(block.map(|block| block.header.height), Some(height));
+    assert!(matches!(
+        offline.tx_status(&tx_id).await?,
+        TxStatus::Success { .. }
+    ));
+    assert!(offline.get_transaction_by_id(&tx_id).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn provider_runs_over_custom_and_recorded_transports() -> Result<()> {
+    use fuels::accounts::provider::transport::{
+        HttpTransport, RecordedTransport, RecordingTransport,
+    };
+
+    let wallet = launch_provider_and_get_wallet().await?;
+    let url = wallet.provider().url().to_string();
+
+    // ANCHOR: custom_transport
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        reqwest::header::AUTHORIZATION,
+        reqwest::header::HeaderValue::from_static("Bearer some-api-key"),
+    );
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .map_err(|e| Error::Other(e.to_string()))?;
+
+    let transport = HttpTransport::with_client(url, client)?;
+    // ANCHOR_END: custom_transport
+
+    // ANCHOR: recorded_transport
+    let transport = std::sync::Arc::new(RecordingTransport::new(transport));
+    let provider = Provider::connect_with_transport(transport.clone()).await?;
+
+    let height = provider.latest_block_height().await?;
+    let balances = provider.get_balances(&wallet.address()).await?;
+
+    // the recording can be stored, e.g. as JSON, and replayed without a node
+    let recording = transport.recording();
+    let replayed = Provider::connect_with_transport(RecordedTransport::new(recording)).await?;
+
+    assert_eq!(replayed.latest_block_height().await?, height);
+    assert_eq!(replayed.get_balances(&wallet.address()).await?, balances);
+    // ANCHOR_END: recorded_transport
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn balances_of_many_owners_are_queried_in_batches() -> Result<()> {
+    let wallets = launch_custom_provider_and_get_wallets(
+        WalletsConfig::new(Some(3), Some(2), Some(100)),
+        None,
+        None,
+    )
+    .await?;
+    let provider = wallets[0].provider().clone();
+
+    let contract_id = Contract::load_from(
+        "sway/contracts/contract_test/out/release/contract_test.bin",
+        LoadConfiguration::default(),
+    )?
+    .deploy_if_not_exists(&wallets[0], TxPolicies::default())
+    .await?
+    .contract_id;
+    wallets[1]
+        .force_transfer_to_contract(contract_id, 300, AssetId::zeroed(), TxPolicies::default())
+        .await?;
+
+    // ANCHOR: get_balances_many
+    let addresses: Vec<Address> = wallets.iter().map(|wallet| wallet.address()).collect();
+    let balances = provider.get_balances_many(&addresses).await?;
+
+    let contract_balances = provider.get_contract_balances_many(&[contract_id]).await?;
+
+    let coins = provider
+        .get_coins_many(&addresses, AssetId::zeroed())
+        .await?;
+    // ANCHOR_END: get_balances_many
+
+    assert_eq!(balances.len(), addresses.len());
+    for address in &addresses {
+        assert_eq!(balances[address], provider.get_balances(address).await?);
+    }
+    assert_eq!(contract_balances[&contract_id][&AssetId::zeroed()], 300);
+    for address in &addresses {
+        let mut expected = provider.get_coins(address, AssetId::zeroed()).await?;
+        let mut batched = coins[address].clone();
+        expected.sort_by_key(|coin| coin.utxo_id);
+        batched.sort_by_key(|coin| coin.utxo_id);
+        assert_eq!(batched, expected);
+    }
+
+    let empty_owner = Address::from([7; 32]);
+    let balances = provider.get_balances_many(&[empty_owner]).await?;
+    assert!(balances[&empty_owner].is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn is_account_query_test() -> Result<()> {
     {
@@ -1650,3 +2077,42 @@ async fn script_tx_get_owner_returns_owner_when_policy_unset_all_inputs_same_own
 
     Ok(())
 }
+
+#[tokio::test]
+async fn blocks_are_streamed_as_produced_and_resumed_from_a_height() -> Result<()> {
+    let provider = setup_test_provider(vec![], vec![], None, None).await?;
+    provider.produce_blocks(3, None).await?;
+
+    let mut blocks = Box::pin(provider.subscribe_blocks(1));
+
+    // catches up over the existing blocks
+    for height in 1..=3 {
+        let block = blocks.next().await.expect("stream is open")?;
+        assert_eq!(block.header.height, height);
+    }
+
+    // then follows the blocks produced while it is consumed
+    let producer = provider.clone();
+    let producing = tokio::spawn(async move {
+        for _ in 0..2 {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            producer.produce_blocks(1, None).await?;
+        }
+
+        Ok::<_, Error>(())
+    });
+    for height in 4..=5 {
+        let block = blocks.next().await.expect("stream is open")?;
+        assert_eq!(block.header.height, height);
+    }
+    producing.await.expect("producing blocks")?;
+    drop(blocks);
+
+    // and resumes from the height following the last received block
+    provider.produce_blocks(1, None).await?;
+    let mut resumed = Box::pin(provider.subscribe_blocks(5 + 1));
+    let block = resumed.next().await.expect("stream is open")?;
+    assert_eq!(block.header.height, 6);
+
+    Ok(())
+}
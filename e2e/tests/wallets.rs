@@ -1,5 +1,9 @@
 use fuels::{
-    accounts::signers::private_key::PrivateKeySigner,
+    accounts::{
+        coin_selection::{LargestFirst, MinimizeChange, SmallestFirst},
+        signers::private_key::PrivateKeySigner,
+        utxo_pool::UtxoPool,
+    },
     prelude::*,
     types::{coin_type::CoinType, input::Input, output::Output},
 };
@@ -450,6 +454,131 @@ async fn transfer_coins_of_non_base_asset() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn consolidate_coins_merges_small_coins() -> Result<()> {
+    const NUM_COINS: u64 = 10;
+    const AMOUNT: u64 = 1000;
+    let signer = PrivateKeySigner::random(&mut thread_rng());
+
+    let asset_id = AssetId::from([1; 32]);
+    let mut coins = setup_single_asset_coins(signer.address(), asset_id, NUM_COINS, AMOUNT);
+    coins.extend(setup_single_asset_coins(
+        signer.address(),
+        AssetId::zeroed(),
+        1,
+        AMOUNT,
+    ));
+
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    let wallet = Wallet::new(signer, provider);
+
+    // at most 3 coins are merged per tx, leaving one input for the fee
+    let responses = wallet
+        .consolidate_coins(asset_id, 4, TxPolicies::default())
+        .await?;
+
+    assert_eq!(responses.len(), 5);
+    let coins = wallet.get_coins(asset_id).await?;
+    assert_eq!(coins.len(), 1);
+    assert_eq!(coins[0].amount, NUM_COINS * AMOUNT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn split_coins_respects_output_limit() -> Result<()> {
+    use fuels::tx::{ConsensusParameters, TxParameters};
+
+    const PARTS: u16 = 10;
+    const AMOUNT_EACH: u64 = 100;
+    let signer = PrivateKeySigner::random(&mut thread_rng());
+
+    let coins = setup_single_asset_coins(signer.address(), AssetId::zeroed(), 1, 1_000_000);
+
+    let mut consensus_parameters = ConsensusParameters::default();
+    consensus_parameters.set_tx_params(TxParameters::default().with_max_outputs(5));
+    let chain_config = ChainConfig {
+        consensus_parameters,
+        ..ChainConfig::default()
+    };
+
+    let provider = setup_test_provider(coins, vec![], None, Some(chain_config)).await?;
+    let wallet = Wallet::new(signer, provider);
+
+    let responses = wallet
+        .split_coins(AssetId::zeroed(), PARTS, AMOUNT_EACH, TxPolicies::default())
+        .await?;
+
+    // 4 new coins fit next to the change output
+    assert_eq!(responses.len(), 3);
+    let split_coins = wallet
+        .get_coins(AssetId::zeroed())
+        .await?
+        .into_iter()
+        .filter(|coin| coin.amount == AMOUNT_EACH)
+        .count();
+    assert_eq!(split_coins, usize::from(PARTS));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transfer_many_sends_to_all_recipients() -> Result<()> {
+    use fuels::tx::{ConsensusParameters, TxParameters};
+
+    const AMOUNT: u64 = 1_000_000;
+    let signer = PrivateKeySigner::random(&mut thread_rng());
+
+    let asset_id = AssetId::from([1; 32]);
+    let mut coins = setup_single_asset_coins(signer.address(), asset_id, 1, AMOUNT);
+    coins.extend(setup_single_asset_coins(
+        signer.address(),
+        AssetId::zeroed(),
+        1,
+        AMOUNT,
+    ));
+
+    let mut consensus_parameters = ConsensusParameters::default();
+    consensus_parameters.set_tx_params(TxParameters::default().with_max_outputs(6));
+    let chain_config = ChainConfig {
+        consensus_parameters,
+        ..ChainConfig::default()
+    };
+
+    let provider = setup_test_provider(coins, vec![], None, Some(chain_config)).await?;
+    let wallet = Wallet::new(signer, provider.clone());
+
+    let transfers = (0..6u64)
+        .map(|idx| {
+            let asset_id = if idx % 2 == 0 {
+                AssetId::zeroed()
+            } else {
+                asset_id
+            };
+
+            (Address::from([idx as u8 + 1; 32]), asset_id, 100 + idx)
+        })
+        .collect::<Vec<_>>();
+
+    let statuses = wallet
+        .transfer_many(&transfers, TxPolicies::default())
+        .await?;
+
+    assert_eq!(statuses.len(), transfers.len());
+    let tx_ids = statuses
+        .iter()
+        .map(|status| status.result.as_ref().map(|response| response.tx_id))
+        .collect::<Result<std::collections::HashSet<_>>>()?;
+    // 4 coins and 2 change outputs per tx
+    assert_eq!(tx_ids.len(), 2);
+
+    for (to, asset_id, amount) in transfers {
+        assert_address_balance(&to, &provider, &asset_id, amount.into()).await;
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_transfer_with_multiple_signatures() -> Result<()> {
     let wallet_config = base_asset_wallet_config(5);
@@ -559,3 +688,272 @@ async fn wallet_transfer_respects_maturity_and_expiration() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn hd_wallet_discovers_used_accounts() -> Result<()> {
+    use fuels::accounts::{hd_wallet::HdWallet, signers::private_key::generate_mnemonic_phrase};
+
+    let phrase = generate_mnemonic_phrase(&mut thread_rng(), 12)?;
+    let hd_wallet = HdWallet::from_mnemonic_phrase(&phrase)?;
+
+    let mut coins = setup_single_asset_coins(hd_wallet.address(0)?, AssetId::zeroed(), 1, 1000);
+    coins.extend(setup_single_asset_coins(
+        hd_wallet.address(3)?,
+        AssetId::zeroed(),
+        2,
+        100,
+    ));
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+
+    // index 7 is only reachable by scanning past the first window
+    hd_wallet
+        .wallet(0, provider.clone())?
+        .transfer(
+            hd_wallet.address(7)?,
+            50,
+            AssetId::zeroed(),
+            TxPolicies::default(),
+        )
+        .await?;
+
+    let discovery = hd_wallet.discover(&provider, 5).await?;
+
+    let used = discovery
+        .accounts
+        .iter()
+        .map(|account| account.index)
+        .collect::<Vec<_>>();
+    assert_eq!(used, vec![0, 3, 7]);
+    assert_eq!(discovery.next_index, 8);
+
+    let base_asset = AssetId::zeroed().to_string();
+    let expected_total = provider.get_balances(&hd_wallet.address(0)?).await?[&base_asset] + 250;
+    assert_eq!(discovery.total_balances()[&base_asset], expected_total);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn coin_selectors_pick_the_spent_coins() -> Result<()> {
+    let signer = PrivateKeySigner::random(&mut thread_rng());
+    let base_asset_id = AssetId::zeroed();
+
+    let mut coins = setup_single_asset_coins(signer.address(), base_asset_id, 3, 10);
+    coins.extend(setup_single_asset_coins(
+        signer.address(),
+        base_asset_id,
+        1,
+        500,
+    ));
+    coins.extend(setup_single_asset_coins(
+        signer.address(),
+        base_asset_id,
+        1,
+        1_000_000,
+    ));
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+
+    let picked_amounts = |resources: Vec<CoinType>| {
+        let mut amounts: Vec<_> = resources.iter().map(CoinType::amount).collect();
+        amounts.sort();
+        amounts
+    };
+
+    // ANCHOR: coin_selector
+    let wallet = Wallet::new(signer, provider.clone()).with_coin_selector(SmallestFirst);
+    // ANCHOR_END: coin_selector
+    let resources = wallet
+        .get_spendable_resources(base_asset_id, 25, None)
+        .await?;
+    assert_eq!(picked_amounts(resources), [10, 10, 10]);
+
+    let wallet = wallet.with_coin_selector(MinimizeChange);
+    let resources = wallet
+        .get_spendable_resources(base_asset_id, 500, None)
+        .await?;
+    assert_eq!(picked_amounts(resources), [500]);
+
+    // the fee is covered by the selected coins as well
+    let wallet = wallet.with_coin_selector(LargestFirst);
+    let response = wallet
+        .transfer(
+            Address::default(),
+            100,
+            base_asset_id,
+            TxPolicies::default(),
+        )
+        .await?;
+
+    let tx = provider
+        .get_transaction_by_id(&response.tx_id)
+        .await?
+        .expect("transaction should exist");
+    let TransactionType::Script(tx) = tx.transaction else {
+        panic!("expected a script transaction");
+    };
+    let spent_amounts: Vec<_> = tx
+        .inputs()
+        .iter()
+        .filter_map(|input| input.amount())
+        .collect();
+    assert_eq!(spent_amounts, [1_000_000]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn utxo_pool_sends_concurrent_and_chained_transfers() -> Result<()> {
+    let signer = PrivateKeySigner::random(&mut thread_rng());
+    let (coins, asset_ids) = setup_multiple_assets_coins(signer.address(), 2, 5, 1_000_000);
+    let base_asset_id = AssetId::zeroed();
+    let asset_id = asset_ids[0];
+    let provider = setup_test_provider(coins, vec![], None, None).await?;
+    let wallet = Wallet::new(signer, provider.clone());
+    let recipient = Address::from([3; 32]);
+
+    // ANCHOR: utxo_pool_concurrent_transfers
+    let pool = UtxoPool::new(wallet.address());
+    pool.sync(&provider, base_asset_id).await?;
+    pool.sync(&provider, asset_id).await?;
+
+    let transfers = (0..5).map(|_| {
+        pool.transfer(
+            &wallet,
+            recipient,
+            100,
+            base_asset_id,
+            TxPolicies::default(),
+        )
+    });
+    futures::future::try_join_all(transfers).await?;
+    // ANCHOR_END: utxo_pool_concurrent_transfers
+
+    assert_address_balance(&recipient, &provider, &base_asset_id, 500).await;
+
+    // ANCHOR: utxo_pool_chained_transfers
+    let mut tx_ids = vec![];
+    for _ in 0..3 {
+        let tx_id = pool
+            .submit_transfer(
+                &wallet,
+                recipient,
+                1_500_000,
+                asset_id,
+                TxPolicies::default(),
+            )
+            .await?;
+        tx_ids.push(tx_id);
+    }
+
+    for tx_id in tx_ids {
+        pool.await_commit(&provider, tx_id).await?.check(None)?;
+    }
+    // ANCHOR_END: utxo_pool_chained_transfers
+
+    assert_address_balance(&recipient, &provider, &asset_id, 4_500_000).await;
+    assert!(pool.state().in_flight.is_empty());
+    assert_eq!(
+        pool.available_amount(&base_asset_id),
+        wallet.get_asset_balance(&base_asset_id).await?
+    );
+    assert_eq!(pool.available_amount(&asset_id), 500_000);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn personal_and_typed_data_signatures_are_recovered_in_sway() -> Result<()> {
+    use fuels::{
+        accounts::signers::message::{TypedDataDomain, sign_personal_message, sign_typed_data},
+        crypto::Signature,
+        types::{B512, Bits256},
+    };
+
+    setup_program_test!(
+        Wallets("wallet"),
+        Abigen(Contract(
+            name = "SignedMessages",
+            project = "e2e/sway/contracts/signed_messages"
+        )),
+        Deploy(
+            name = "contract_instance",
+            contract = "SignedMessages",
+            wallet = "wallet",
+            random_salt = false,
+        ),
+    );
+    let to_b512 = |signature: Signature| {
+        B512::from((
+            Bits256(signature[..32].try_into().expect("32 bytes")),
+            Bits256(signature[32..].try_into().expect("32 bytes")),
+        ))
+    };
+    let methods = contract_instance.methods();
+
+    // a length with more than one digit is encoded in decimal
+    let message = "sign in to fuel.network, nonce 42";
+    let signature = sign_personal_message(wallet.signer(), message).await?;
+    let signer = methods
+        .personal_message_signer(Bytes(message.as_bytes().to_vec()), to_b512(signature))
+        .call()
+        .await?
+        .value;
+    assert_eq!(signer, wallet.address());
+
+    let chain_id = wallet.provider().consensus_parameters().await?.chain_id();
+    let domain = TypedDataDomain::new(chain_id, contract_instance.contract_id());
+    let order = Order {
+        owner: wallet.address(),
+        amount: 100,
+    };
+    let signature = sign_typed_data(wallet.signer(), &domain, order.clone()).await?;
+    let signer = methods
+        .order_signer(u64::from(chain_id), order, to_b512(signature))
+        .call()
+        .await?
+        .value;
+    assert_eq!(signer, wallet.address());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transactions_built_online_are_signed_offline_and_submitted() -> Result<()> {
+    use fuels::{
+        accounts::signers::fake::FakeSigner,
+        types::{portable_transaction::PortableTransaction, tx_status::TxStatus},
+    };
+
+    let wallet = launch_provider_and_get_wallet().await?;
+    let provider = wallet.provider().clone();
+    let consensus_parameters = provider.consensus_parameters().await?;
+    let base_asset_id = *consensus_parameters.base_asset_id();
+    let recipient = Address::from([1; 32]);
+    let amount = 100;
+
+    // built online, knowing only the address of the signer
+    let online = Wallet::new(FakeSigner::new(wallet.address()), provider.clone());
+    let inputs = online
+        .get_asset_inputs_for_amount(base_asset_id, amount.into(), None)
+        .await?;
+    let outputs = online.get_asset_outputs_for_amount(recipient, base_asset_id, amount);
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    online.add_witnesses(&mut tb)?;
+    online.adjust_for_fee(&mut tb, amount.into()).await?;
+    let tx = tb.build(&provider).await?;
+    let exported = PortableTransaction::new(tx, consensus_parameters.chain_id())?.to_json()?;
+
+    // signed offline
+    let mut portable = PortableTransaction::from_json(&exported)?;
+    portable.sign(wallet.signer()).await?;
+    let signed = portable.to_json()?;
+
+    // imported and submitted online
+    let tx = PortableTransaction::from_json(&signed)?.into_transaction::<ScriptTransaction>()?;
+    let status = provider.send_transaction_and_await_commit(tx).await?;
+
+    assert!(matches!(status, TxStatus::Success { .. }));
+    assert_address_balance(&recipient, &provider, &base_asset_id, amount.into()).await;
+
+    Ok(())
+}
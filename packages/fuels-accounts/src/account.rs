@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
 use fuel_core_client::client::pagination::{PaginatedResult, PaginationRequest};
@@ -8,7 +8,7 @@ use fuels_core::types::{
     coin::Coin,
     coin_type::CoinType,
     coin_type_id::CoinTypeId,
-    errors::{Context, Result},
+    errors::{Context, Result, error},
     input::Input,
     message::Message,
     transaction::{Transaction, TxPolicies},
@@ -23,7 +23,9 @@ use crate::{
         add_base_change_if_needed, available_base_assets_and_amount, calculate_missing_base_amount,
         extract_message_nonce, split_into_utxo_ids_and_nonces,
     },
+    coin_selection::CoinSelector,
     provider::{Provider, ResourceFilter},
+    tip_bump::TipBumpConfig,
 };
 
 #[derive(Clone, Debug)]
@@ -33,6 +35,17 @@ pub struct WithdrawToBaseResponse {
     pub nonce: Nonce,
 }
 
+/// The outcome of a single transfer sent through [`Account::transfer_many`].
+#[derive(Clone, Debug)]
+pub struct TransferStatus {
+    pub to: Address,
+    pub asset_id: AssetId,
+    pub amount: u64,
+    /// The response of the transaction containing the transfer. Transfers sent in the same
+    /// transaction share it.
+    pub result: Result<TxResponse>,
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait ViewOnlyAccount: Send + Sync {
     fn address(&self) -> Address;
@@ -78,9 +91,15 @@ pub trait ViewOnlyAccount: Send + Sync {
         self.try_provider()?.get_balances(&self.address()).await
     }
 
+    /// The selector picking the resources spent by the account, if not left to the node.
+    fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+        None
+    }
+
     /// Get some spendable resources (coins and messages) of asset `asset_id` owned by the account
     /// that add up at least to amount `amount`. The returned coins (UTXOs) are actual coins that
-    /// can be spent. The number of UXTOs is optimized to prevent dust accumulation.
+    /// can be spent. Unless the account has a [`CoinSelector`], the resources are picked by the
+    /// node, which optimizes the number of UTXOs to prevent dust accumulation.
     async fn get_spendable_resources(
         &self,
         asset_id: AssetId,
@@ -98,7 +117,11 @@ pub trait ViewOnlyAccount: Send + Sync {
             excluded_message_nonces,
         };
 
-        self.try_provider()?.get_spendable_resources(filter).await
+        let provider = self.try_provider()?;
+        match self.coin_selector() {
+            Some(selector) => provider.select_spendable_resources(filter, selector).await,
+            None => provider.get_spendable_resources(filter).await,
+        }
     }
 
     /// Returns a vector containing the output coin and change output given an asset and amount
@@ -133,15 +156,30 @@ pub trait ViewOnlyAccount: Send + Sync {
         &self,
         tb: &mut Tb,
         used_base_amount: u128,
+    ) -> Result<()> {
+        self.adjust_for_fee_excluding(tb, used_base_amount, vec![])
+            .await
+    }
+
+    /// Like [`ViewOnlyAccount::adjust_for_fee`] but never picks `excluded_coins` to cover the fee.
+    /// Accounts customizing how the fee is covered override this method, which `adjust_for_fee`
+    /// delegates to.
+    async fn adjust_for_fee_excluding<Tb: TransactionBuilder + Sync>(
+        &self,
+        tb: &mut Tb,
+        used_base_amount: u128,
+        excluded_coins: Vec<CoinTypeId>,
     ) -> Result<()> {
         let provider = self.try_provider()?;
         let consensus_parameters = provider.consensus_parameters().await?;
         let base_asset_id = consensus_parameters.base_asset_id();
-        let (base_assets, base_amount) = available_base_assets_and_amount(tb, base_asset_id);
+        let (mut base_assets, base_amount) = available_base_assets_and_amount(tb, base_asset_id);
         let missing_base_amount =
             calculate_missing_base_amount(tb, base_amount, used_base_amount, provider).await?;
 
         if missing_base_amount > 0 {
+            base_assets.extend(excluded_coins);
+
             let new_base_inputs = self
                 .get_asset_inputs_for_amount(
                     *consensus_parameters.base_asset_id(),
@@ -212,6 +250,74 @@ pub trait Account: ViewOnlyAccount {
         })
     }
 
+    /// Sends each `(recipient, asset_id, amount)` transfer in `transfers`, batching them into
+    /// transactions that stay within the chain's output limit.
+    ///
+    /// Transfers of the same asset are batched together so that fewer change outputs, and thus
+    /// fewer transactions, are needed.
+    ///
+    /// A failing transaction doesn't stop the remaining ones from being sent. Returns the status
+    /// of every transfer, in the order they were given.
+    async fn transfer_many(
+        &self,
+        transfers: &[(Address, AssetId, u64)],
+        tx_policies: TxPolicies,
+    ) -> Result<Vec<TransferStatus>> {
+        let consensus_parameters = self.try_provider()?.consensus_parameters().await?;
+        let base_asset_id = *consensus_parameters.base_asset_id();
+        let batches = batch_transfers(
+            transfers,
+            consensus_parameters.tx_params().max_outputs().into(),
+            base_asset_id,
+        )?;
+
+        let mut statuses = vec![None; transfers.len()];
+        for batch in batches {
+            let transfers = batch.iter().map(|idx| transfers[*idx]).collect::<Vec<_>>();
+
+            let mut amounts: HashMap<AssetId, u128> = HashMap::new();
+            for (_, asset_id, amount) in &transfers {
+                *amounts.entry(*asset_id).or_default() += u128::from(*amount);
+            }
+
+            let result = async {
+                let mut inputs = vec![];
+                for (asset_id, amount) in &amounts {
+                    inputs.extend(
+                        self.get_asset_inputs_for_amount(*asset_id, *amount, None)
+                            .await?,
+                    );
+                }
+
+                let mut outputs = transfers
+                    .iter()
+                    .map(|(to, asset_id, amount)| Output::coin(*to, *amount, *asset_id))
+                    .collect::<Vec<_>>();
+                outputs.extend(
+                    amounts
+                        .keys()
+                        .map(|asset_id| Output::change(self.address(), 0, *asset_id)),
+                );
+
+                let used_base_amount = amounts.get(&base_asset_id).copied().unwrap_or_default();
+
+                send_transfer(self, inputs, outputs, used_base_amount, vec![], tx_policies).await
+            }
+            .await;
+
+            for (idx, (to, asset_id, amount)) in batch.into_iter().zip(transfers) {
+                statuses[idx] = Some(TransferStatus {
+                    to,
+                    asset_id,
+                    amount,
+                    result: result.clone(),
+                });
+            }
+        }
+
+        Ok(statuses.into_iter().flatten().collect())
+    }
+
     /// Unconditionally transfers `balance` of type `asset_id` to
     /// the contract at `to`.
     /// Fails if balance for `asset_id` is larger than this account's spendable balance.
@@ -329,6 +435,249 @@ pub trait Account: ViewOnlyAccount {
             nonce,
         })
     }
+
+    /// Merges the coins of asset `asset_id` owned by the account into a single coin.
+    ///
+    /// The smallest coins are merged first, sending as many transactions as needed. Each
+    /// transaction spends at most `max_inputs` inputs, capped by the chain's input limit,
+    /// including the base asset coins covering the fee.
+    async fn consolidate_coins(
+        &self,
+        asset_id: AssetId,
+        max_inputs: u16,
+        tx_policies: TxPolicies,
+    ) -> Result<Vec<TxResponse>> {
+        let consensus_parameters = self.try_provider()?.consensus_parameters().await?;
+        let max_inputs = usize::from(max_inputs.min(consensus_parameters.tx_params().max_inputs()));
+
+        if max_inputs < 3 {
+            return Err(error!(
+                Other,
+                "`max_inputs` must be at least 3 to merge two coins and cover the fee"
+            ));
+        }
+
+        let mut responses = vec![];
+        loop {
+            let mut coins = self.get_coins(asset_id).await?;
+            if coins.len() < 2 {
+                break;
+            }
+            coins.sort_by_key(|coin| coin.amount);
+
+            // Leave one input for the fee, then fewer coins if covering it takes more inputs
+            let mut coins_in_tx = coins.len().min(max_inputs - 1);
+            let tb = loop {
+                let inputs = coins[..coins_in_tx]
+                    .iter()
+                    .map(|coin| Input::resource_signed(CoinType::Coin(coin.clone())))
+                    .collect();
+                let outputs = vec![Output::change(self.address(), 0, asset_id)];
+
+                let mut tb =
+                    ScriptTransactionBuilder::prepare_transfer(inputs, outputs, tx_policies);
+                self.add_witnesses(&mut tb)?;
+                // Base asset inputs are merged into the change, nothing is spent besides the fee
+                self.adjust_for_fee(&mut tb, 0)
+                    .await
+                    .context("failed to adjust inputs to cover for missing base asset")?;
+
+                let excess_inputs = tb.inputs().len().saturating_sub(max_inputs);
+                if excess_inputs == 0 {
+                    break tb;
+                }
+
+                coins_in_tx = coins_in_tx.saturating_sub(excess_inputs);
+                if coins_in_tx < 2 {
+                    return Err(error!(
+                        Other,
+                        "covering the fee takes too many base asset inputs to merge coins within `{max_inputs}` inputs"
+                    ));
+                }
+            };
+
+            responses.push(send_script(self, tb).await?);
+        }
+
+        Ok(responses)
+    }
+
+    /// Splits coins of asset `asset_id` owned by the account into `parts` new coins of
+    /// `amount_each`, sending as many transactions as needed to stay within the chain's output
+    /// limit.
+    async fn split_coins(
+        &self,
+        asset_id: AssetId,
+        parts: u16,
+        amount_each: u64,
+        tx_policies: TxPolicies,
+    ) -> Result<Vec<TxResponse>> {
+        if parts == 0 || amount_each == 0 {
+            return Err(error!(
+                Other,
+                "`parts` and `amount_each` must be greater than `0`"
+            ));
+        }
+
+        let consensus_parameters = self.try_provider()?.consensus_parameters().await?;
+        let base_asset_id = *consensus_parameters.base_asset_id();
+
+        // Leave room for the change of `asset_id` and of the base asset
+        let change_outputs = if asset_id == base_asset_id { 1 } else { 2 };
+        let parts_per_tx = consensus_parameters
+            .tx_params()
+            .max_outputs()
+            .saturating_sub(change_outputs);
+
+        if parts_per_tx == 0 {
+            return Err(error!(
+                Other,
+                "the chain's output limit leaves no room for new coins"
+            ));
+        }
+
+        let mut responses = vec![];
+        // Coins created by earlier transactions must not be spent by later ones, not even to
+        // cover their fee
+        let mut created_coins = vec![];
+        let mut remaining = parts;
+        while remaining > 0 {
+            let parts_in_tx = remaining.min(parts_per_tx);
+            let amount = u128::from(amount_each) * u128::from(parts_in_tx);
+
+            let inputs = self
+                .get_asset_inputs_for_amount(asset_id, amount, Some(created_coins.clone()))
+                .await?;
+            let mut outputs =
+                vec![Output::coin(self.address(), amount_each, asset_id); parts_in_tx.into()];
+            outputs.push(Output::change(self.address(), 0, asset_id));
+
+            let used_base_amount = if asset_id == base_asset_id { amount } else { 0 };
+            let response = send_transfer(
+                self,
+                inputs,
+                outputs,
+                used_base_amount,
+                created_coins.clone(),
+                tx_policies,
+            )
+            .await?;
+
+            created_coins.extend(
+                (0..parts_in_tx).map(|idx| CoinTypeId::UtxoId(UtxoId::new(response.tx_id, idx))),
+            );
+            responses.push(response);
+            remaining -= parts_in_tx;
+        }
+
+        Ok(responses)
+    }
+
+    /// Builds and sends the transaction of `tb`, replacing it with one paying a higher tip if it
+    /// is squeezed out or still pending after a while, until one of the sent transactions is
+    /// included.
+    ///
+    /// Replacements spend the same inputs, so at most one of them can be included, plus base asset
+    /// inputs of the account covering the raised fee. They are signed by the signers of `tb`.
+    async fn send_with_tip_bumping(
+        &self,
+        tb: ScriptTransactionBuilder,
+        config: TipBumpConfig,
+    ) -> Result<TxResponse> {
+        crate::tip_bump::send_with_tip_bumping(self, tb, config).await
+    }
+}
+
+/// Splits the indices of `transfers` into batches whose coin outputs, together with a change
+/// output for each of their assets and the base asset, fit into `max_outputs`.
+///
+/// Transfers are ordered by asset, base asset first, before being greedily batched, so that the
+/// transfers of an asset share as few batches, and change outputs, as possible.
+fn batch_transfers(
+    transfers: &[(Address, AssetId, u64)],
+    max_outputs: usize,
+    base_asset_id: AssetId,
+) -> Result<Vec<Vec<usize>>> {
+    let outputs_needed = |batch: &[usize]| {
+        let mut assets = batch
+            .iter()
+            .map(|idx| transfers[*idx].1)
+            .collect::<HashSet<_>>();
+        assets.insert(base_asset_id);
+
+        batch.len() + assets.len()
+    };
+
+    let mut order = (0..transfers.len()).collect::<Vec<_>>();
+    order.sort_by_key(|idx| {
+        let asset_id = transfers[*idx].1;
+        (asset_id != base_asset_id, asset_id)
+    });
+
+    let mut batches = vec![];
+    let mut batch: Vec<usize> = vec![];
+    for idx in order {
+        batch.push(idx);
+        if outputs_needed(&batch) <= max_outputs {
+            continue;
+        }
+
+        batch.pop();
+        if outputs_needed(&[idx]) > max_outputs {
+            return Err(error!(
+                Other,
+                "a transfer needs more than the allowed `{max_outputs}` outputs"
+            ));
+        }
+
+        batches.push(std::mem::replace(&mut batch, vec![idx]));
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    Ok(batches)
+}
+
+/// Builds and sends a script transaction moving coins between `inputs` and `outputs`, covering
+/// the fee with base asset coins other than `excluded_coins`.
+async fn send_transfer<A: Account + ?Sized>(
+    account: &A,
+    inputs: Vec<Input>,
+    outputs: Vec<Output>,
+    used_base_amount: u128,
+    excluded_coins: Vec<CoinTypeId>,
+    tx_policies: TxPolicies,
+) -> Result<TxResponse> {
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, tx_policies);
+
+    account.add_witnesses(&mut tb)?;
+    account
+        .adjust_for_fee_excluding(&mut tb, used_base_amount, excluded_coins)
+        .await
+        .context("failed to adjust inputs to cover for missing base asset")?;
+
+    send_script(account, tb).await
+}
+
+/// Builds the transaction of `tb` and waits for it to be committed.
+async fn send_script<A: Account + ?Sized>(
+    account: &A,
+    tb: ScriptTransactionBuilder,
+) -> Result<TxResponse> {
+    let provider = account.try_provider()?;
+
+    let consensus_parameters = provider.consensus_parameters().await?;
+    let tx = tb.build(provider).await?;
+    let tx_id = tx.id(consensus_parameters.chain_id());
+
+    let tx_status = provider.send_transaction_and_await_commit(tx).await?;
+
+    Ok(TxResponse {
+        tx_status: tx_status.take_success_checked(None)?,
+        tx_id,
+    })
 }
 
 #[cfg(test)]
@@ -446,4 +795,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn transfers_are_batched_within_output_limit() -> Result<()> {
+        let base_asset_id = AssetId::zeroed();
+        let other_asset_id = AssetId::from([1; 32]);
+        let to = Address::zeroed();
+
+        let transfers = [
+            (to, base_asset_id, 1),
+            (to, base_asset_id, 2),
+            (to, other_asset_id, 3),
+            (to, other_asset_id, 4),
+            (to, base_asset_id, 5),
+        ];
+
+        // transfers of the same asset are grouped so each batch needs a single change output
+        let batches = batch_transfers(&transfers, 4, base_asset_id)?;
+        assert_eq!(batches, vec![vec![0, 1, 4], vec![2, 3]]);
+
+        // the base asset change is needed even if the batch doesn't transfer the base asset
+        let batches = batch_transfers(&transfers, 3, base_asset_id)?;
+        assert_eq!(batches, vec![vec![0, 1], vec![4], vec![2], vec![3]]);
+
+        let batches = batch_transfers(&transfers, 255, base_asset_id)?;
+        assert_eq!(batches, vec![vec![0, 1, 4, 2, 3]]);
+
+        assert!(batch_transfers(&[], 4, base_asset_id)?.is_empty());
+
+        let err = batch_transfers(&transfers[2..], 2, base_asset_id).expect_err("should fail");
+        assert!(
+            err.to_string()
+                .contains("more than the allowed `2` outputs")
+        );
+
+        Ok(())
+    }
 }
@@ -1,11 +1,16 @@
 use std::{
     collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
+    time::Duration,
 };
 
 use fuel_types::AssetId;
 use fuels_core::types::{Address, coin_type_id::CoinTypeId};
-use tokio::time::{Duration, Instant};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
 type CoinCacheKey = (Address, AssetId);
 
@@ -0,0 +1,283 @@
+//! Client-side selection of the coins and messages spent by an account.
+//!
+//! By default the node picks the resources through its `coins_to_spend` query. An account with a
+//! [`CoinSelector`] instead fetches all of its spendable resources of the requested asset and lets
+//! the selector pick among them.
+
+use std::fmt::Debug;
+
+use fuels_core::types::{
+    coin_type::CoinType,
+    errors::{Error, Result, error},
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+pub trait CoinSelector: Debug + Send + Sync {
+    /// Picks resources out of `candidates`, all spendable and of the requested asset, adding up
+    /// to at least `amount`.
+    fn select(&self, candidates: Vec<CoinType>, amount: u128) -> Result<Vec<CoinType>>;
+}
+
+/// Spends the largest resources first, using as few inputs as possible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LargestFirst;
+
+impl CoinSelector for LargestFirst {
+    fn select(&self, mut candidates: Vec<CoinType>, amount: u128) -> Result<Vec<CoinType>> {
+        candidates.sort_by_key(|resource| std::cmp::Reverse(resource.amount()));
+
+        take_until_covered(candidates, amount)
+    }
+}
+
+/// Spends the smallest resources first, consolidating dust as a side effect.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmallestFirst;
+
+impl CoinSelector for SmallestFirst {
+    fn select(&self, mut candidates: Vec<CoinType>, amount: u128) -> Result<Vec<CoinType>> {
+        candidates.sort_by_key(CoinType::amount);
+
+        take_until_covered(candidates, amount)
+    }
+}
+
+/// Picks the resources leaving the least change, so that fewer change outputs turn into dust.
+///
+/// An exact match is used if there is one. Otherwise the largest resources fitting into the
+/// remaining amount are picked and topped up with the smallest resource covering the rest,
+/// dropping the ones no longer needed. The result is compared against the smallest single
+/// resource covering the whole amount.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinimizeChange;
+
+impl CoinSelector for MinimizeChange {
+    fn select(&self, mut candidates: Vec<CoinType>, amount: u128) -> Result<Vec<CoinType>> {
+        if amount == 0 {
+            return Ok(vec![]);
+        }
+        candidates.sort_by_key(|resource| std::cmp::Reverse(resource.amount()));
+
+        let smallest_covering = |candidates: &[CoinType], target: u128| {
+            candidates
+                .iter()
+                .rposition(|resource| u128::from(resource.amount()) >= target)
+        };
+
+        let single = smallest_covering(&candidates, amount);
+        if let Some(idx) = single
+            && u128::from(candidates[idx].amount()) == amount
+        {
+            return Ok(vec![candidates.swap_remove(idx)]);
+        }
+
+        let mut picked = vec![];
+        let mut unused = vec![];
+        let mut remaining = amount;
+        for resource in &candidates {
+            let resource_amount = u128::from(resource.amount());
+            if remaining > 0 && resource_amount <= remaining {
+                remaining -= resource_amount;
+                picked.push(resource.clone());
+            } else {
+                unused.push(resource.clone());
+            }
+        }
+
+        let combination = if remaining == 0 {
+            Some((picked, 0))
+        } else {
+            smallest_covering(&unused, remaining).map(|idx| {
+                picked.push(unused.swap_remove(idx));
+                without_unneeded(picked, amount)
+            })
+        };
+        let single = single.map(|idx| {
+            let change = u128::from(candidates[idx].amount()) - amount;
+            (vec![candidates[idx].clone()], change)
+        });
+
+        let best = match (combination, single) {
+            (Some(combination), Some(single)) if combination.1 < single.1 => combination,
+            (_, Some(single)) => single,
+            (Some(combination), None) => combination,
+            (None, None) => return Err(not_enough_resources(&candidates, amount)),
+        };
+
+        Ok(best.0)
+    }
+}
+
+/// Drops the smallest resources of `picked` not needed to cover `amount`, returning the rest
+/// together with the change they leave.
+fn without_unneeded(mut picked: Vec<CoinType>, amount: u128) -> (Vec<CoinType>, u128) {
+    let mut total: u128 = picked
+        .iter()
+        .map(|resource| u128::from(resource.amount()))
+        .sum();
+
+    let mut by_amount: Vec<_> = (0..picked.len()).collect();
+    by_amount.sort_by_key(|&idx| picked[idx].amount());
+
+    let mut unneeded = vec![false; picked.len()];
+    for idx in by_amount {
+        let resource_amount = u128::from(picked[idx].amount());
+        if total - resource_amount >= amount {
+            total -= resource_amount;
+            unneeded[idx] = true;
+        }
+    }
+
+    let mut unneeded = unneeded.into_iter();
+    picked.retain(|_| !unneeded.next().expect("one flag per resource"));
+
+    (picked, total - amount)
+}
+
+/// Avoids linking the resources of the account in a single transaction where possible.
+///
+/// A single resource covering the amount is picked at random among those that do, otherwise
+/// resources are added in random order until the amount is covered. Unlike the other selectors
+/// the choice is not predictable from the account's balances.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrivacyPreserving;
+
+impl CoinSelector for PrivacyPreserving {
+    fn select(&self, candidates: Vec<CoinType>, amount: u128) -> Result<Vec<CoinType>> {
+        select_randomly(candidates, amount, &mut StdRng::from_entropy())
+    }
+}
+
+fn select_randomly(
+    mut candidates: Vec<CoinType>,
+    amount: u128,
+    rng: &mut impl Rng,
+) -> Result<Vec<CoinType>> {
+    if amount == 0 {
+        return Ok(vec![]);
+    }
+
+    let covering: Vec<_> = (0..candidates.len())
+        .filter(|&idx| u128::from(candidates[idx].amount()) >= amount)
+        .collect();
+    if !covering.is_empty() {
+        let idx = covering[rng.gen_range(0..covering.len())];
+
+        return Ok(vec![candidates.swap_remove(idx)]);
+    }
+
+    for i in (1..candidates.len()).rev() {
+        candidates.swap(i, rng.gen_range(0..=i));
+    }
+
+    take_until_covered(candidates, amount)
+}
+
+fn take_until_covered(candidates: Vec<CoinType>, amount: u128) -> Result<Vec<CoinType>> {
+    let mut selected = vec![];
+    let mut total = 0;
+    for resource in &candidates {
+        if total >= amount {
+            return Ok(selected);
+        }
+        total += u128::from(resource.amount());
+        selected.push(resource.clone());
+    }
+
+    if total < amount {
+        return Err(not_enough_resources(&candidates, amount));
+    }
+
+    Ok(selected)
+}
+
+fn not_enough_resources(candidates: &[CoinType], amount: u128) -> Error {
+    let available: u128 = candidates
+        .iter()
+        .map(|resource| u128::from(resource.amount()))
+        .sum();
+
+    error!(
+        Other,
+        "not enough resources to cover `{amount}`, only `{available}` available"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels_core::types::coin::Coin;
+
+    use super::*;
+
+    fn coins(amounts: &[u64]) -> Vec<CoinType> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(idx, &amount)| {
+                CoinType::Coin(Coin {
+                    amount,
+                    utxo_id: fuel_tx::UtxoId::new([idx as u8; 32].into(), 0),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn amounts(selected: Result<Vec<CoinType>>) -> Vec<u64> {
+        selected.unwrap().iter().map(CoinType::amount).collect()
+    }
+
+    #[test]
+    fn largest_and_smallest_first() {
+        let candidates = coins(&[5, 50, 1, 20]);
+
+        assert_eq!(
+            amounts(LargestFirst.select(candidates.clone(), 60)),
+            [50, 20]
+        );
+        assert_eq!(
+            amounts(SmallestFirst.select(candidates.clone(), 20)),
+            [1, 5, 20]
+        );
+        assert!(SmallestFirst.select(candidates, 77).is_err());
+    }
+
+    #[test]
+    fn minimize_change() {
+        let candidates = coins(&[100, 30, 25, 8, 5]);
+
+        // exact single match
+        assert_eq!(amounts(MinimizeChange.select(candidates.clone(), 25)), [25]);
+        // exact combination
+        assert_eq!(
+            amounts(MinimizeChange.select(candidates.clone(), 38)),
+            [30, 8]
+        );
+        // combination leaving less change than any single coin
+        assert_eq!(
+            amounts(MinimizeChange.select(candidates.clone(), 62)),
+            [30, 25, 8]
+        );
+        // single coin leaving less change than the combination
+        assert_eq!(
+            amounts(MinimizeChange.select(candidates.clone(), 99)),
+            [100]
+        );
+        assert!(MinimizeChange.select(candidates, 200).is_err());
+    }
+
+    #[test]
+    fn privacy_preserving_prefers_a_single_resource() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let candidates = coins(&[10, 40, 50, 5]);
+
+        for _ in 0..10 {
+            let selected = amounts(select_randomly(candidates.clone(), 30, &mut rng));
+            assert!(selected == [40] || selected == [50]);
+
+            let selected = amounts(select_randomly(candidates.clone(), 60, &mut rng));
+            assert!(selected.iter().sum::<u64>() >= 60);
+        }
+        assert!(select_randomly(candidates, 106, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use fuel_core_client::client::pagination::{PageDirection, PaginationRequest};
+use fuel_crypto::{
+    SecretKey,
+    coins_bip32::{
+        prelude::SigningKey,
+        xkeys::{Parent, XPriv},
+    },
+    coins_bip39::{English, Mnemonic},
+};
+use fuels_core::types::{
+    Address,
+    errors::{Result, error},
+};
+use futures::future::try_join_all;
+
+use crate::{
+    provider::Provider,
+    signers::{
+        derivation::{BIP44_PURPOSE, COIN_TYPE},
+        private_key::PrivateKeySigner,
+    },
+    wallet::Wallet,
+};
+
+/// Derives any number of accounts from a single mnemonic phrase.
+///
+/// Accounts are derived following BIP-44 at `m/44'/1179993420'/{account}'/0/{index}`, where
+/// `account` defaults to `0` and can be changed with [`HdWallet::with_account`]. Keys at
+/// arbitrary paths are available through [`HdWallet::signer_at_path`].
+#[derive(Clone)]
+pub struct HdWallet {
+    root_key: XPriv,
+    account: u32,
+    chain_key: XPriv,
+}
+
+impl std::fmt::Debug for HdWallet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HdWallet")
+            .field("root_key", &"REDACTED")
+            .field("account", &self.account)
+            .finish()
+    }
+}
+
+/// An account found to be in use by [`HdWallet::discover`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedAccount {
+    pub index: u32,
+    pub address: Address,
+    /// Balances of the account by asset id, empty if it was used but holds no funds.
+    pub balances: HashMap<String, u128>,
+}
+
+/// The result of [`HdWallet::discover`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Discovery {
+    /// The accounts in use, ordered by index.
+    pub accounts: Vec<DerivedAccount>,
+    /// The first index after the last used account.
+    pub next_index: u32,
+}
+
+impl Discovery {
+    /// Sum of the balances of all discovered accounts.
+    pub fn total_balances(&self) -> HashMap<String, u128> {
+        let mut total = HashMap::new();
+        for (asset_id, amount) in self.accounts.iter().flat_map(|account| &account.balances) {
+            *total.entry(asset_id.clone()).or_default() += amount;
+        }
+
+        total
+    }
+}
+
+impl HdWallet {
+    pub fn from_mnemonic_phrase(phrase: &str) -> Result<Self> {
+        let seed = Mnemonic::<English>::new_from_phrase(phrase)
+            .and_then(|mnemonic| mnemonic.to_seed(None))
+            .map_err(|e| error!(Other, "invalid mnemonic phrase: {e}"))?;
+        let root_key = XPriv::root_from_seed(&seed, None)
+            .map_err(|e| error!(Other, "could not derive root key: {e}"))?;
+
+        Self::new(root_key, 0)
+    }
+
+    /// Derives the accounts of the BIP-44 `account` instead of the default `0`.
+    pub fn with_account(self, account: u32) -> Result<Self> {
+        Self::new(self.root_key, account)
+    }
+
+    fn new(root_key: XPriv, account: u32) -> Result<Self> {
+        let chain_key = derive(
+            &root_key,
+            &format!("m/{BIP44_PURPOSE}/{COIN_TYPE}/{account}'/0"),
+        )?;
+
+        Ok(Self {
+            root_key,
+            account,
+            chain_key,
+        })
+    }
+
+    pub fn account(&self) -> u32 {
+        self.account
+    }
+
+    pub fn derivation_path(&self, index: u32) -> String {
+        format!("m/{BIP44_PURPOSE}/{COIN_TYPE}/{}'/0/{index}", self.account)
+    }
+
+    pub fn signer(&self, index: u32) -> Result<PrivateKeySigner> {
+        let key = self
+            .chain_key
+            .derive_child(index)
+            .map_err(|e| error!(Other, "could not derive key at index {index}: {e}"))?;
+
+        to_signer(&key)
+    }
+
+    pub fn signer_at_path(&self, path: &str) -> Result<PrivateKeySigner> {
+        to_signer(&derive(&self.root_key, path)?)
+    }
+
+    pub fn address(&self, index: u32) -> Result<Address> {
+        Ok(self.signer(index)?.address())
+    }
+
+    pub fn wallet(&self, index: u32, provider: Provider) -> Result<Wallet> {
+        Ok(Wallet::new(self.signer(index)?, provider))
+    }
+
+    pub fn wallet_at_path(&self, path: &str, provider: Provider) -> Result<Wallet> {
+        Ok(Wallet::new(self.signer_at_path(path)?, provider))
+    }
+
+    /// Finds the derived accounts that were used.
+    ///
+    /// An account is used if it holds any funds or owns any transaction. Accounts are scanned in
+    /// windows of `gap_limit` indexes, queried concurrently, until `gap_limit` consecutive unused
+    /// accounts are found.
+    pub async fn discover(&self, provider: &Provider, gap_limit: u32) -> Result<Discovery> {
+        if gap_limit == 0 {
+            return Err(error!(Other, "gap limit must be greater than 0"));
+        }
+
+        let mut discovery = Discovery::default();
+        let mut window_start = 0;
+
+        while window_start - discovery.next_index < gap_limit {
+            let window_end = discovery.next_index.saturating_add(gap_limit);
+            let accounts = try_join_all(
+                (window_start..window_end).map(|index| self.used_account(provider, index)),
+            )
+            .await?;
+
+            for account in accounts.into_iter().flatten() {
+                discovery.next_index = account.index + 1;
+                discovery.accounts.push(account);
+            }
+
+            window_start = window_end;
+        }
+
+        Ok(discovery)
+    }
+
+    async fn used_account(
+        &self,
+        provider: &Provider,
+        index: u32,
+    ) -> Result<Option<DerivedAccount>> {
+        let address = self.address(index)?;
+        let balances = provider.get_balances(&address).await?;
+
+        let is_used = !balances.is_empty() || {
+            let request = PaginationRequest {
+                cursor: None,
+                results: 1,
+                direction: PageDirection::Forward,
+            };
+
+            !provider
+                .get_transactions_by_owner(&address, request)
+                .await?
+                .results
+                .is_empty()
+        };
+
+        Ok(is_used.then_some(DerivedAccount {
+            index,
+            address,
+            balances,
+        }))
+    }
+}
+
+fn derive(root_key: &XPriv, path: &str) -> Result<XPriv> {
+    root_key
+        .derive_path(path)
+        .map_err(|e| error!(Other, "could not derive key at path `{path}`: {e}"))
+}
+
+fn to_signer(key: &XPriv) -> Result<PrivateKeySigner> {
+    let signing_key: &SigningKey = key.as_ref();
+    let secret_key = SecretKey::try_from(signing_key.to_bytes().as_slice())?;
+
+    Ok(PrivateKeySigner::new(secret_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str =
+        "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+    #[test]
+    fn derives_the_same_keys_as_the_derivation_path() -> Result<()> {
+        let wallet = HdWallet::from_mnemonic_phrase(PHRASE)?;
+
+        for (account, index) in [(0, 0), (0, 7), (3, 2)] {
+            let wallet = wallet.clone().with_account(account)?;
+            let path = wallet.derivation_path(index);
+            let expected = PrivateKeySigner::new(SecretKey::new_from_mnemonic_phrase_with_path(
+                PHRASE, &path,
+            )?);
+
+            assert_eq!(wallet.address(index)?, expected.address());
+            assert_eq!(wallet.signer_at_path(&path)?.address(), expected.address());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn balances_of_discovered_accounts_are_summed() {
+        let account = |index, balances: &[(&str, u128)]| DerivedAccount {
+            index,
+            address: Address::default(),
+            balances: balances
+                .iter()
+                .map(|(asset_id, amount)| (asset_id.to_string(), *amount))
+                .collect(),
+        };
+
+        let discovery = Discovery {
+            accounts: vec![
+                account(0, &[("a", 1), ("b", 2)]),
+                account(4, &[]),
+                account(5, &[("a", 10)]),
+            ],
+            next_index: 6,
+        };
+
+        let expected = HashMap::from([("a".to_string(), 11), ("b".to_string(), 2)]);
+        assert_eq!(discovery.total_balances(), expected);
+    }
+}
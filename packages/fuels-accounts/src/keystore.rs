@@ -1,9 +1,31 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
-use fuel_crypto::SecretKey;
-use fuels_core::{error, types::errors::Result};
+use fuel_crypto::{
+    SecretKey,
+    coins_bip39::{English, Mnemonic},
+};
+use fuels_core::{
+    error,
+    types::{Address, errors::Result},
+};
 use rand::{CryptoRng, Rng};
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    hd_wallet::HdWallet,
+    signers::{derivation::DEFAULT_DERIVATION_PATH, private_key::PrivateKeySigner},
+};
+
+// Name of the field holding our `EntryInfo` in the keystore files. Other tools ignore it.
+const INFO_FIELD: &str = "fuels";
 
 #[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
 pub struct KeySaved {
@@ -22,8 +44,65 @@ impl KeySaved {
     }
 }
 
+/// What a keystore entry holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyKind {
+    SecretKey,
+    Mnemonic,
+}
+
+/// Unencrypted information about a keystore entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyInfo {
+    pub uuid: String,
+    pub kind: KeyKind,
+    /// The address of the key, or of the first account derived at [`DEFAULT_DERIVATION_PATH`]
+    /// for mnemonics. Missing for keys saved by older versions until their password is changed.
+    pub address: Option<Address>,
+    pub label: Option<String>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntryInfo {
+    kind: KeyKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+}
+
+impl EntryInfo {
+    fn new(kind: KeyKind, address: Address) -> Self {
+        Self {
+            kind,
+            address: Some(address),
+            label: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    // Entries written before this information was stored could only hold keys
+    fn legacy() -> Self {
+        Self {
+            kind: KeyKind::SecretKey,
+            address: None,
+            label: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+}
+
 /// A Keystore encapsulates operations for key management such as creation, loading,
 /// and saving of keys into a specified directory.
+///
+/// Every entry is a single encrypted JSON file named after its UUID. Besides the encrypted
+/// secret, which is either a [`SecretKey`] or a mnemonic phrase, the file holds unencrypted
+/// information about it: its kind, address, label and metadata, see [`KeyInfo`].
 pub struct Keystore {
     dir: PathBuf,
 }
@@ -41,9 +120,9 @@ impl Keystore {
     where
         S: AsRef<[u8]>,
     {
-        let key_path = self.dir.join(uuid);
-        let secret =
-            eth_keystore::decrypt_key(key_path, password).map_err(|e| error!(Other, "{e}"))?;
+        self.expect_kind(uuid, KeyKind::SecretKey)?;
+
+        let secret = self.decrypt(uuid, password)?;
         let secret_key = SecretKey::try_from(secret.as_slice())
             .expect("Decrypted key should have a correct size");
         Ok(secret_key)
@@ -57,11 +136,281 @@ impl Keystore {
         S: AsRef<[u8]>,
     {
         // Note: `*key` is used if SecretKey implements Deref to an inner type.
-        eth_keystore::encrypt_key(&self.dir, &mut rng, *key, password, None)
-            .map_err(|e| error!(Other, "{e}"))
+        let uuid = eth_keystore::encrypt_key(&self.dir, &mut rng, *key, password, None)
+            .map_err(|e| error!(Other, "{e}"))?;
+
+        let address = PrivateKeySigner::new(key).address();
+        self.write_info(&uuid, &EntryInfo::new(KeyKind::SecretKey, address))?;
+
+        Ok(uuid)
+    }
+
+    /// Encrypts the provided mnemonic phrase with the given password and saves it to the
+    /// keystore. Returns the generated UUID for the stored mnemonic.
+    pub fn save_mnemonic<R, S>(&self, phrase: &str, password: S, mut rng: R) -> Result<String>
+    where
+        R: Rng + CryptoRng,
+        S: AsRef<[u8]>,
+    {
+        Mnemonic::<English>::new_from_phrase(phrase)
+            .map_err(|e| error!(Other, "invalid mnemonic phrase: {e}"))?;
+        let key = SecretKey::new_from_mnemonic_phrase_with_path(phrase, DEFAULT_DERIVATION_PATH)?;
+
+        let uuid = eth_keystore::encrypt_key(&self.dir, &mut rng, phrase, password, None)
+            .map_err(|e| error!(Other, "{e}"))?;
+
+        let address = PrivateKeySigner::new(key).address();
+        self.write_info(&uuid, &EntryInfo::new(KeyKind::Mnemonic, address))?;
+
+        Ok(uuid)
+    }
+
+    /// Loads and decrypts a mnemonic phrase from the keystore using the given UUID and password.
+    pub fn load_mnemonic<S>(&self, uuid: &str, password: S) -> Result<Zeroizing<String>>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.expect_kind(uuid, KeyKind::Mnemonic)?;
+
+        let secret = self.decrypt(uuid, password)?;
+        let phrase = String::from_utf8(secret.to_vec())
+            .map_err(|_| error!(Other, "decrypted mnemonic is not valid UTF-8"))?;
+
+        Ok(Zeroizing::new(phrase))
+    }
+
+    /// Restores the [`HdWallet`] of a stored mnemonic.
+    pub fn load_hd_wallet<S>(&self, uuid: &str, password: S) -> Result<HdWallet>
+    where
+        S: AsRef<[u8]>,
+    {
+        HdWallet::from_mnemonic_phrase(&self.load_mnemonic(uuid, password)?)
+    }
+
+    /// Returns the information of every entry in the keystore, sorted by UUID.
+    pub fn list(&self) -> Result<Vec<KeyInfo>> {
+        let mut keys = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+
+            // Hidden files are left behind by interrupted writes
+            let Some(uuid) = entry
+                .file_name()
+                .to_str()
+                .filter(|name| !name.starts_with('.'))
+                .map(str::to_owned)
+            else {
+                continue;
+            };
+
+            // Skip files that aren't keystore entries or whose information is malformed, they
+            // can still be accessed by their UUID
+            if let Ok(info) = self
+                .read_listed_entry(&uuid)
+                .and_then(|file| key_info(uuid, &file))
+            {
+                keys.push(info);
+            }
+        }
+
+        keys.sort_by(|a, b| a.uuid.cmp(&b.uuid));
+
+        Ok(keys)
+    }
+
+    pub fn info(&self, uuid: &str) -> Result<KeyInfo> {
+        key_info(uuid.to_owned(), &self.read_entry(uuid)?)
+    }
+
+    /// Removes the entry from the keystore.
+    pub fn delete(&self, uuid: &str) -> Result<()> {
+        // Makes sure that only keystore entries are deleted
+        self.info(uuid)?;
+
+        fs::remove_file(self.dir.join(uuid))
+            .map_err(|e| error!(IO, "could not delete key `{uuid}`: {e}"))
+    }
+
+    pub fn set_label(&self, uuid: &str, label: Option<String>) -> Result<()> {
+        self.update_info(uuid, |info| info.label = label)
+    }
+
+    pub fn set_metadata(
+        &self,
+        uuid: &str,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<()> {
+        self.update_info(uuid, |info| {
+            info.metadata.insert(key.into(), value.into());
+        })
+    }
+
+    pub fn remove_metadata(&self, uuid: &str, key: &str) -> Result<()> {
+        self.update_info(uuid, |info| {
+            info.metadata.remove(key);
+        })
+    }
+
+    /// Re-encrypts the entry under `new_password`, keeping its UUID, label and metadata.
+    pub fn change_password<R, S, N>(
+        &self,
+        uuid: &str,
+        old_password: S,
+        new_password: N,
+        mut rng: R,
+    ) -> Result<()>
+    where
+        R: Rng + CryptoRng,
+        S: AsRef<[u8]>,
+        N: AsRef<[u8]>,
+    {
+        let mut info = self.entry_info(uuid)?;
+        let secret = self.decrypt(uuid, old_password)?;
+
+        if info.address.is_none() {
+            let key = SecretKey::try_from(secret.as_slice())
+                .map_err(|_| error!(Other, "decrypted key has an invalid size"))?;
+            info.address = Some(PrivateKeySigner::new(key).address());
+        }
+
+        // Written in place of the temporary file of `write_file`, which then replaces the entry
+        let tmp_name = tmp_name(uuid);
+        eth_keystore::encrypt_key(&self.dir, &mut rng, &*secret, new_password, Some(&tmp_name))
+            .map_err(|e| error!(Other, "{e}"))?;
+
+        let mut file = self.read_file(&tmp_name)?;
+        file["id"] = Value::String(uuid.to_owned());
+        file[INFO_FIELD] = serde_json::to_value(&info).map_err(|e| error!(Other, "{e}"))?;
+
+        self.write_file(uuid, &file)
+    }
+
+    fn decrypt<S>(&self, uuid: &str, password: S) -> Result<Zeroizing<Vec<u8>>>
+    where
+        S: AsRef<[u8]>,
+    {
+        let secret = eth_keystore::decrypt_key(self.dir.join(uuid), password)
+            .map_err(|e| error!(Other, "{e}"))?;
+
+        Ok(Zeroizing::new(secret))
+    }
+
+    fn expect_kind(&self, uuid: &str, kind: KeyKind) -> Result<()> {
+        let actual = self.entry_info(uuid)?.kind;
+        if actual != kind {
+            return Err(error!(
+                Other,
+                "keystore entry `{uuid}` holds a {actual:?}, not a {kind:?}"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn entry_info(&self, uuid: &str) -> Result<EntryInfo> {
+        entry_info(&self.read_entry(uuid)?)
+    }
+
+    fn update_info(&self, uuid: &str, update: impl FnOnce(&mut EntryInfo)) -> Result<()> {
+        let mut info = self.entry_info(uuid)?;
+        update(&mut info);
+
+        self.write_info(uuid, &info)
+    }
+
+    fn write_info(&self, uuid: &str, info: &EntryInfo) -> Result<()> {
+        let mut file = self.read_file(uuid)?;
+        file[INFO_FIELD] = serde_json::to_value(info).map_err(|e| error!(Other, "{e}"))?;
+
+        self.write_file(uuid, &file)
+    }
+
+    fn read_entry(&self, uuid: &str) -> Result<Value> {
+        self.ensure_listed(uuid)?;
+
+        self.read_listed_entry(uuid)
+    }
+
+    /// Makes sure `uuid` names a file that [`Self::list`] looks at, so that it can't point
+    /// outside of the keystore directory.
+    fn ensure_listed(&self, uuid: &str) -> Result<()> {
+        let listed = Path::new(uuid).file_name() == Some(OsStr::new(uuid))
+            && !uuid.starts_with('.')
+            && fs::metadata(self.dir.join(uuid)).is_ok_and(|metadata| metadata.is_file());
+
+        if !listed {
+            return Err(error!(Other, "no keystore entry `{uuid}`"));
+        }
+
+        Ok(())
+    }
+
+    fn read_listed_entry(&self, uuid: &str) -> Result<Value> {
+        let file = self.read_file(uuid)?;
+        if file.get("crypto").is_none() {
+            return Err(error!(Other, "`{uuid}` is not a keystore entry"));
+        }
+
+        Ok(file)
+    }
+
+    fn read_file(&self, name: &str) -> Result<Value> {
+        let contents = fs::read_to_string(self.dir.join(name))
+            .map_err(|e| error!(IO, "could not read key `{name}`: {e}"))?;
+
+        serde_json::from_str(&contents).map_err(|e| error!(Other, "invalid keystore entry: {e}"))
+    }
+
+    /// Replaces the file atomically, so that the entry is never lost if writing is interrupted.
+    fn write_file(&self, name: &str, file: &Value) -> Result<()> {
+        let contents = serde_json::to_string(file).map_err(|e| error!(Other, "{e}"))?;
+        let tmp_path = self.dir.join(tmp_name(name));
+
+        let write_tmp = || -> std::io::Result<()> {
+            let mut tmp_file = fs::File::create(&tmp_path)?;
+            tmp_file.write_all(contents.as_bytes())?;
+            tmp_file.sync_all()
+        };
+        write_tmp().map_err(|e| error!(IO, "could not write key `{name}`: {e}"))?;
+
+        fs::rename(&tmp_path, self.dir.join(name))
+            .map_err(|e| error!(IO, "could not replace key `{name}`: {e}"))
+    }
+}
+
+fn tmp_name(name: &str) -> String {
+    format!(".{name}.tmp")
+}
+
+fn entry_info(file: &Value) -> Result<EntryInfo> {
+    match file.get(INFO_FIELD) {
+        Some(info) => serde_json::from_value(info.clone())
+            .map_err(|e| error!(Other, "invalid keystore entry information: {e}")),
+        None => Ok(EntryInfo::legacy()),
     }
 }
 
+fn key_info(uuid: String, file: &Value) -> Result<KeyInfo> {
+    let EntryInfo {
+        kind,
+        address,
+        label,
+        metadata,
+    } = entry_info(file)?;
+
+    Ok(KeyInfo {
+        uuid,
+        kind,
+        address,
+        label,
+        metadata,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
@@ -114,4 +463,117 @@ mod tests {
         assert!(std::fs::remove_file(key_path).is_ok());
         Ok(())
     }
+
+    #[test]
+    fn keys_can_be_listed_labeled_and_deleted() -> Result<()> {
+        let dir = tempdir()?;
+        let keystore = Keystore::new(dir.path());
+        let signer = PrivateKeySigner::random(&mut thread_rng());
+        let phrase =
+            "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+        let key_uuid = keystore.save_key(signer.secret_key(), "password", thread_rng())?;
+        let mnemonic_uuid = keystore.save_mnemonic(phrase, "password", thread_rng())?;
+        std::fs::write(dir.path().join("notes.txt"), "not a key")?;
+
+        keystore.set_label(&key_uuid, Some("treasury".to_string()))?;
+        keystore.set_metadata(&key_uuid, "customer", "42")?;
+
+        let info = keystore.info(&key_uuid)?;
+        assert_eq!(info.kind, KeyKind::SecretKey);
+        assert_eq!(info.address, Some(signer.address()));
+        assert_eq!(info.label.as_deref(), Some("treasury"));
+        assert_eq!(info.metadata["customer"], "42");
+
+        let listed = keystore
+            .list()?
+            .into_iter()
+            .map(|info| (info.uuid, info.kind))
+            .collect::<Vec<_>>();
+        let mut expected = vec![
+            (key_uuid.clone(), KeyKind::SecretKey),
+            (mnemonic_uuid.clone(), KeyKind::Mnemonic),
+        ];
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(listed, expected);
+
+        // updates replace the entry without leaving temporary files behind
+        assert_eq!(
+            keystore.load_key(&key_uuid, "password")?,
+            signer.secret_key()
+        );
+        assert_eq!(std::fs::read_dir(dir.path())?.count(), 3);
+
+        // an entry with malformed information doesn't break the listing of the others
+        let mut malformed: Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join(&mnemonic_uuid))?)
+                .map_err(|e| error!(Other, "{e}"))?;
+        malformed[INFO_FIELD] = Value::from("not the entry information");
+        std::fs::write(dir.path().join(&mnemonic_uuid), malformed.to_string())?;
+        assert_eq!(keystore.list()?.len(), 1);
+        assert!(keystore.info(&mnemonic_uuid).is_err());
+
+        keystore.delete(&key_uuid)?;
+        assert!(keystore.info(&key_uuid).is_err());
+        assert!(keystore.delete("notes.txt").is_err());
+        assert!(keystore.list()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn only_entries_of_the_keystore_directory_are_accessed() -> Result<()> {
+        let dir = tempdir()?;
+        let keystore_dir = dir.path().join("keystore");
+        std::fs::create_dir(&keystore_dir)?;
+        let keystore = Keystore::new(&keystore_dir);
+
+        // given
+        let outside = Keystore::new(dir.path());
+        let uuid = outside.save_key(
+            SecretKey::random(&mut thread_rng()),
+            "password",
+            thread_rng(),
+        )?;
+        let escaping_uuid = format!("../{uuid}");
+
+        // when
+        let info = keystore.info(&escaping_uuid);
+        let deleted = keystore.delete(&escaping_uuid);
+
+        // then
+        assert!(info.is_err());
+        assert!(deleted.is_err());
+        assert!(outside.info(&uuid).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn mnemonics_are_restored_after_changing_the_password() -> Result<()> {
+        let dir = tempdir()?;
+        let keystore = Keystore::new(dir.path());
+        let phrase =
+            "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+        let uuid = keystore.save_mnemonic(phrase, "old", thread_rng())?;
+        keystore.set_label(&uuid, Some("deposits".to_string()))?;
+        assert!(keystore.load_key(&uuid, "old").is_err());
+
+        keystore.change_password(&uuid, "old", "new", thread_rng())?;
+
+        assert!(keystore.load_mnemonic(&uuid, "old").is_err());
+        assert_eq!(*keystore.load_mnemonic(&uuid, "new")?, phrase);
+
+        let info = keystore.info(&uuid)?;
+        assert_eq!(info.label.as_deref(), Some("deposits"));
+        assert_eq!(
+            info.address,
+            Some(keystore.load_hd_wallet(&uuid, "new")?.address(0)?)
+        );
+        assert_eq!(keystore.list()?.len(), 1);
+        assert_eq!(std::fs::read_dir(dir.path())?.count(), 1);
+
+        Ok(())
+    }
 }
@@ -2,11 +2,25 @@
 mod account;
 #[cfg(feature = "std")]
 mod accounts_utils;
+#[cfg(feature = "std")]
+pub mod coin_selection;
+#[cfg(feature = "std")]
+pub mod hd_wallet;
 #[cfg(all(feature = "std", feature = "keystore"))]
 pub mod keystore;
 #[cfg(feature = "std")]
+pub mod multisig;
+#[cfg(feature = "std")]
 pub mod provider;
 #[cfg(feature = "std")]
+mod runtime;
+#[cfg(feature = "std")]
+pub mod signature_account;
+#[cfg(feature = "std")]
+pub mod tip_bump;
+#[cfg(feature = "std")]
+pub mod utxo_pool;
+#[cfg(feature = "std")]
 pub mod wallet;
 
 #[cfg(feature = "std")]
@@ -16,6 +30,8 @@ pub use account::*;
 mod coin_cache;
 
 pub mod predicate;
+#[cfg(all(test, feature = "std"))]
+mod predicate_test_utils;
 pub mod signers;
 #[cfg(test)]
 mod test {
@@ -0,0 +1,592 @@
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use fuel_asm::{GTFArgs, Instruction, RegId, op};
+use fuel_crypto::Signature;
+use fuel_tx::{Transaction as FuelTransaction, Witness};
+use fuels_core::{
+    Configurable, Configurables,
+    traits::Signer,
+    types::{
+        Address, AssetId, Bytes32,
+        coin_type_id::CoinTypeId,
+        errors::{Error, Result, error},
+        input::Input,
+        portable_transaction::PortableTransaction,
+        transaction_builders::TransactionBuilder,
+    },
+};
+use itertools::Itertools;
+
+use crate::{
+    Account, ViewOnlyAccount, coin_selection::CoinSelector, predicate::Predicate,
+    provider::Provider, signers::fake::FakeSigner,
+};
+
+/// Maximum number of owners a [`MultisigAccount`] can have.
+pub const MAX_MULTISIG_OWNERS: usize = 10;
+
+// Layout of the predicate's data section, which follows the instructions
+const THRESHOLD_OFFSET: usize = PREDICATE_INSTRUCTIONS * Instruction::SIZE;
+const OWNERS_OFFSET: usize = THRESHOLD_OFFSET + 8;
+const PREDICATE_INSTRUCTIONS: usize = 40;
+
+/// The owners and the number of signatures required by the multisig predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigConfigurables {
+    owners: Vec<Address>,
+    threshold: u8,
+}
+
+impl MultisigConfigurables {
+    pub fn new(owners: Vec<Address>, threshold: u8) -> Result<Self> {
+        if owners.is_empty() || owners.len() > MAX_MULTISIG_OWNERS {
+            return Err(error!(
+                Other,
+                "a multisig must have between 1 and {MAX_MULTISIG_OWNERS} owners, got {}",
+                owners.len()
+            ));
+        }
+
+        if !owners.iter().all_unique() {
+            return Err(error!(Other, "multisig owners must be unique"));
+        }
+
+        if threshold == 0 || usize::from(threshold) > owners.len() {
+            return Err(error!(
+                Other,
+                "multisig threshold must be between 1 and the number of owners ({}), got {threshold}",
+                owners.len()
+            ));
+        }
+
+        Ok(Self { owners, threshold })
+    }
+
+    pub fn owners(&self) -> &[Address] {
+        &self.owners
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+}
+
+impl From<MultisigConfigurables> for Configurables {
+    fn from(config: MultisigConfigurables) -> Self {
+        let owners = config
+            .owners
+            .iter()
+            .flat_map(|owner| **owner)
+            .collect::<Vec<_>>();
+
+        Configurables::new(vec![
+            Configurable {
+                offset: THRESHOLD_OFFSET as u64,
+                data: u64::from(config.threshold).to_be_bytes().to_vec(),
+            },
+            Configurable {
+                offset: OWNERS_OFFSET as u64,
+                data: owners,
+            },
+        ])
+    }
+}
+
+/// Returns the bytecode of the M-of-N multisig predicate, to be configured with
+/// [`MultisigConfigurables`].
+///
+/// The predicate recovers the signer of every witness of the transaction and succeeds if at least
+/// `threshold` distinct owners signed the transaction id. It executes the same instructions
+/// regardless of which witnesses hold valid signatures, so the gas estimated with placeholder
+/// signatures stays valid once the transaction is signed.
+pub fn multisig_predicate_code() -> Vec<u8> {
+    // registers
+    let buf = 0x10;
+    let hash = 0x11;
+    let data = 0x12;
+    let num_witnesses = 0x13;
+    let num_owners = 0x14;
+    let len_pub_key = 0x15;
+    let len_address = 0x16;
+    let i = 0x17;
+    let mask = 0x18;
+    let tmp = 0x19;
+    let witness = 0x1a;
+    let j = 0x1b;
+    let owner = 0x1c;
+    let matched = 0x1d;
+    let count = 0x1e;
+    let threshold = 0x1f;
+
+    // absolute instruction indexes used as jump targets
+    let witness_loop = 10;
+    let owner_loop = 17;
+    let next_witness = 25;
+    let count_owners = 27;
+    let count_loop = 29;
+    let check_threshold = 36;
+
+    let instructions = [
+        // reserve memory for the recovered public key and its hash
+        op::move_(buf, RegId::SP),
+        op::cfei(96),
+        op::addi(hash, buf, 64),
+        op::addi(data, RegId::IS, THRESHOLD_OFFSET as u16),
+        op::gtf_args(num_witnesses, RegId::ZERO, GTFArgs::TxWitnessesCount),
+        op::movi(num_owners, MAX_MULTISIG_OWNERS as u32),
+        op::movi(len_pub_key, 64),
+        op::movi(len_address, 32),
+        op::move_(i, RegId::ZERO),
+        op::move_(mask, RegId::ZERO),
+        // witness_loop: recover the signer of witness `i`, the tx id is at the start of memory
+        op::eq(tmp, i, num_witnesses),
+        op::jnzi(tmp, count_owners),
+        op::gtf_args(witness, i, GTFArgs::WitnessData),
+        op::eck1(buf, witness, RegId::ZERO),
+        op::s256(hash, buf, len_pub_key),
+        op::move_(j, RegId::ZERO),
+        op::addi(owner, data, 8),
+        // owner_loop: set bit `j` of the mask if owner `j` is the signer
+        op::eq(tmp, j, num_owners),
+        op::jnzi(tmp, next_witness),
+        op::meq(matched, hash, owner, len_address),
+        op::sll(matched, matched, j),
+        op::or(mask, mask, matched),
+        op::addi(owner, owner, 32),
+        op::addi(j, j, 1),
+        op::ji(owner_loop),
+        // next_witness
+        op::addi(i, i, 1),
+        op::ji(witness_loop),
+        // count_owners: count the bits set in the mask
+        op::move_(j, RegId::ZERO),
+        op::move_(count, RegId::ZERO),
+        // count_loop
+        op::eq(tmp, j, num_owners),
+        op::jnzi(tmp, check_threshold),
+        op::srl(tmp, mask, j),
+        op::and(tmp, tmp, RegId::ONE),
+        op::add(count, count, tmp),
+        op::addi(j, j, 1),
+        op::ji(count_loop),
+        // check_threshold
+        op::lw(threshold, data, 0),
+        op::lt(tmp, count, threshold),
+        op::eq(tmp, tmp, RegId::ZERO),
+        op::ret(tmp),
+    ];
+    debug_assert_eq!(instructions.len(), PREDICATE_INSTRUCTIONS);
+
+    let data_section_len = OWNERS_OFFSET - THRESHOLD_OFFSET + MAX_MULTISIG_OWNERS * Address::LEN;
+
+    instructions
+        .into_iter()
+        .flat_map(Instruction::to_bytes)
+        .chain(std::iter::repeat_n(0, data_section_len))
+        .collect()
+}
+
+/// An M-of-N multisig account backed by the predicate returned by [`multisig_predicate_code`].
+///
+/// Signers added with [`MultisigAccount::with_signer`] sign every transaction built through the
+/// [`Account`] methods, which requires at least `threshold` of them. When the owners are spread
+/// across machines, or some keys are held by a KMS, a transaction can be built with
+/// [`MultisigAccount::add_partial_witnesses`] and passed around as a
+/// [`PartiallySignedTransaction`] until enough owners signed it.
+#[derive(Clone)]
+pub struct MultisigAccount {
+    config: MultisigConfigurables,
+    predicate: Predicate,
+    signers: Vec<Arc<dyn Signer + Send + Sync>>,
+}
+
+impl Debug for MultisigAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultisigAccount")
+            .field("config", &self.config)
+            .field("predicate", &self.predicate)
+            .field(
+                "signers",
+                &self.signers.iter().map(|s| s.address()).collect_vec(),
+            )
+            .finish()
+    }
+}
+
+impl MultisigAccount {
+    pub fn new(config: MultisigConfigurables) -> Self {
+        let predicate =
+            Predicate::from_code(multisig_predicate_code()).with_configurables(config.clone());
+
+        Self {
+            config,
+            predicate,
+            signers: vec![],
+        }
+    }
+
+    pub fn with_provider(mut self, provider: Provider) -> Self {
+        self.predicate.set_provider(provider);
+        self
+    }
+
+    pub fn set_provider(&mut self, provider: Provider) {
+        self.predicate.set_provider(provider);
+    }
+
+    pub fn with_coin_selector(mut self, coin_selector: impl CoinSelector + 'static) -> Self {
+        self.predicate.set_coin_selector(coin_selector);
+        self
+    }
+
+    /// Adds a signer available locally. It must be one of the owners.
+    pub fn with_signer(mut self, signer: impl Signer + Send + Sync + 'static) -> Result<Self> {
+        let address = signer.address();
+        if !self.config.owners.contains(&address) {
+            return Err(error!(
+                Other,
+                "signer `{address}` is not an owner of the multisig"
+            ));
+        }
+
+        if self.signers.iter().any(|s| s.address() == address) {
+            return Err(error!(Other, "signer `{address}` was already added"));
+        }
+
+        self.signers.push(Arc::new(signer));
+
+        Ok(self)
+    }
+
+    pub fn owners(&self) -> &[Address] {
+        self.config.owners()
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.config.threshold()
+    }
+
+    pub fn predicate(&self) -> &Predicate {
+        &self.predicate
+    }
+
+    /// Reserves a witness for each of the `threshold` required signatures.
+    ///
+    /// Local signers sign their witness once the transaction is built, the rest are left as
+    /// placeholders to be filled through a [`PartiallySignedTransaction`].
+    pub fn add_partial_witnesses<Tb: TransactionBuilder>(&self, tb: &mut Tb) -> Result<()> {
+        let threshold = usize::from(self.threshold());
+
+        let placeholders = self
+            .owners()
+            .iter()
+            .filter(|owner| !self.signers.iter().any(|s| s.address() == **owner))
+            .map(|owner| Arc::new(FakeSigner::new(*owner)) as Arc<dyn Signer + Send + Sync>);
+
+        let signers = self
+            .signers
+            .iter()
+            .cloned()
+            .chain(placeholders)
+            .take(threshold)
+            .collect_vec();
+
+        tb.add_signers(&signers)?;
+
+        Ok(())
+    }
+
+    /// Wraps a transaction spending from this multisig so that the missing signatures can be
+    /// collected.
+    pub async fn partially_signed(
+        &self,
+        tx: impl Into<FuelTransaction>,
+    ) -> Result<PartiallySignedTransaction> {
+        let chain_id = self
+            .try_provider()?
+            .consensus_parameters()
+            .await?
+            .chain_id();
+
+        PartiallySignedTransaction::new(PortableTransaction::new(tx, chain_id)?, self.clone())
+    }
+
+    /// Imports a transaction exported with [`PartiallySignedTransaction::to_json`].
+    pub fn import_partially_signed(&self, json: &str) -> Result<PartiallySignedTransaction> {
+        PartiallySignedTransaction::new(PortableTransaction::from_json(json)?, self.clone())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ViewOnlyAccount for MultisigAccount {
+    fn address(&self) -> Address {
+        self.predicate.address()
+    }
+
+    fn try_provider(&self) -> Result<&Provider> {
+        self.predicate.try_provider()
+    }
+
+    fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+        self.predicate.coin_selector()
+    }
+
+    async fn get_asset_inputs_for_amount(
+        &self,
+        asset_id: AssetId,
+        amount: u128,
+        excluded_coins: Option<Vec<CoinTypeId>>,
+    ) -> Result<Vec<Input>> {
+        self.predicate
+            .get_asset_inputs_for_amount(asset_id, amount, excluded_coins)
+            .await
+    }
+}
+
+impl Account for MultisigAccount {
+    fn add_witnesses<Tb: TransactionBuilder>(&self, tb: &mut Tb) -> Result<()> {
+        if self.signers.len() < usize::from(self.threshold()) {
+            return Err(error!(
+                Other,
+                "multisig requires {} signatures but only {} of its signers are available, use \
+                `add_partial_witnesses` to collect the rest",
+                self.threshold(),
+                self.signers.len()
+            ));
+        }
+
+        self.add_partial_witnesses(tb)
+    }
+}
+
+/// A transaction spending from a [`MultisigAccount`] that still lacks some of the owners'
+/// signatures.
+///
+/// It can be exported with [`PartiallySignedTransaction::to_json`] and imported on another
+/// machine with [`MultisigAccount::import_partially_signed`].
+#[derive(Debug, Clone)]
+pub struct PartiallySignedTransaction {
+    tx: PortableTransaction,
+    multisig: MultisigAccount,
+}
+
+impl PartiallySignedTransaction {
+    fn new(tx: PortableTransaction, multisig: MultisigAccount) -> Result<Self> {
+        let address = multisig.address();
+        if !tx
+            .inputs()
+            .iter()
+            .any(|input| input.input_owner() == Some(&address))
+        {
+            return Err(error!(
+                Other,
+                "transaction does not spend from the multisig `{address}`"
+            ));
+        }
+
+        Ok(Self { tx, multisig })
+    }
+
+    pub fn id(&self) -> Bytes32 {
+        self.tx.id()
+    }
+
+    /// Owners whose signature is already part of the transaction.
+    pub fn signed_by(&self) -> Vec<Address> {
+        let message = self.tx.signing_message();
+
+        self.tx
+            .witnesses()
+            .iter()
+            .filter_map(|witness| {
+                let bytes = witness.as_vec().as_slice().try_into().ok()?;
+                let public_key = Signature::from_bytes(bytes).recover(&message).ok()?;
+
+                Some(Address::from(*public_key.hash()))
+            })
+            .filter(|address| self.multisig.owners().contains(address))
+            .unique()
+            .collect()
+    }
+
+    /// Number of signatures still needed to reach the threshold.
+    pub fn missing_signatures(&self) -> usize {
+        usize::from(self.multisig.threshold()).saturating_sub(self.signed_by().len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.missing_signatures() == 0
+    }
+
+    /// Signs the transaction with one of the owners, filling the next placeholder witness.
+    pub async fn sign(&mut self, signer: &(impl Signer + Send + Sync)) -> Result<Signature> {
+        let address = signer.address();
+        if !self.multisig.owners().contains(&address) {
+            return Err(error!(
+                Other,
+                "signer `{address}` is not an owner of the multisig"
+            ));
+        }
+
+        if self.signed_by().contains(&address) {
+            return Err(error!(Other, "`{address}` already signed the transaction"));
+        }
+
+        let witness_index = self.next_placeholder().ok_or_else(|| {
+            error!(
+                Other,
+                "transaction has no placeholder witness left for another signature"
+            )
+        })?;
+
+        self.tx.sign_witness(witness_index, signer).await
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        self.tx.to_json()
+    }
+
+    /// Returns the transaction, failing if fewer than `threshold` owners signed it.
+    pub fn into_transaction<T>(self) -> Result<T>
+    where
+        T: TryFrom<FuelTransaction, Error = Error>,
+    {
+        let missing = self.missing_signatures();
+        if missing > 0 {
+            return Err(error!(
+                Other,
+                "multisig transaction is missing {missing} signature(s)"
+            ));
+        }
+
+        self.tx.into_transaction()
+    }
+
+    fn next_placeholder(&self) -> Option<u16> {
+        let placeholder: Witness = Signature::default().as_ref().into();
+        let input_slots = self
+            .tx
+            .signer_slots()
+            .into_iter()
+            .map(|(_, witness_index)| witness_index)
+            .collect_vec();
+
+        self.tx
+            .witnesses()
+            .iter()
+            .enumerate()
+            .map(|(index, witness)| (index as u16, witness))
+            .find(|(index, witness)| **witness == placeholder && !input_slots.contains(index))
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_crypto::SecretKey;
+    use fuel_tx::ConsensusParameters;
+    use fuels_core::types::transaction::ScriptTransaction;
+
+    use super::*;
+    use crate::{
+        predicate_test_utils::{
+            assert_code_is_pinned, predicates_pass, set_signature, tx_message, unsigned_tx,
+        },
+        signers::private_key::PrivateKeySigner,
+    };
+
+    fn signer(key: u8) -> PrivateKeySigner {
+        PrivateKeySigner::new(SecretKey::try_from([key; 32].as_slice()).unwrap())
+    }
+
+    fn two_of_three() -> MultisigAccount {
+        let owners = (1..=3).map(|key| signer(key).address()).collect();
+
+        MultisigAccount::new(MultisigConfigurables::new(owners, 2).unwrap())
+    }
+
+    fn unsigned_multisig_tx(multisig: &MultisigAccount) -> fuel_tx::Script {
+        unsigned_tx(multisig.predicate(), usize::from(multisig.threshold()))
+    }
+
+    async fn sign_witnesses(tx: &mut fuel_tx::Script, signers: &[PrivateKeySigner]) {
+        let message = tx_message(tx);
+
+        for (index, signer) in signers.iter().enumerate() {
+            set_signature(tx, index, signer.sign(message).await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn predicate_requires_threshold_distinct_owners() {
+        let multisig = two_of_three();
+
+        assert!(!predicates_pass(unsigned_multisig_tx(&multisig)));
+
+        let mut tx = unsigned_multisig_tx(&multisig);
+        sign_witnesses(&mut tx, &[signer(1), signer(3)]).await;
+        assert!(predicates_pass(tx));
+
+        let mut tx = unsigned_multisig_tx(&multisig);
+        sign_witnesses(&mut tx, &[signer(2), signer(2)]).await;
+        assert!(!predicates_pass(tx));
+
+        let mut tx = unsigned_multisig_tx(&multisig);
+        sign_witnesses(&mut tx, &[signer(1), signer(4)]).await;
+        assert!(!predicates_pass(tx));
+    }
+
+    #[tokio::test]
+    async fn signatures_are_collected_across_exports() -> Result<()> {
+        let multisig = two_of_three();
+        let chain_id = ConsensusParameters::standard().chain_id();
+        let json =
+            PortableTransaction::new(unsigned_multisig_tx(&multisig), chain_id)?.to_json()?;
+
+        let mut tx = multisig.import_partially_signed(&json)?;
+        assert_eq!(tx.missing_signatures(), 2);
+
+        let err = tx.sign(&signer(4)).await.expect_err("should fail");
+        assert!(err.to_string().contains("is not an owner"));
+
+        tx.sign(&signer(2)).await?;
+        let err = tx.sign(&signer(2)).await.expect_err("should fail");
+        assert!(err.to_string().contains("already signed"));
+        assert!(tx.clone().into_transaction::<ScriptTransaction>().is_err());
+
+        let mut tx = multisig.import_partially_signed(&tx.to_json()?)?;
+        assert_eq!(tx.signed_by(), vec![signer(2).address()]);
+
+        tx.sign(&signer(1)).await?;
+        assert!(tx.is_complete());
+
+        let tx: FuelTransaction = tx.into_transaction::<ScriptTransaction>()?.into();
+        let FuelTransaction::Script(tx) = tx else {
+            unreachable!()
+        };
+        assert!(predicates_pass(tx));
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_code_is_pinned() {
+        assert_code_is_pinned(
+            multisig_predicate_code(),
+            "16fcfae959031e19f826f6ed42ffb7b259ea7c54264c938887820e2b87996165",
+        );
+    }
+
+    #[test]
+    fn invalid_configurations_are_rejected() {
+        let owners = vec![signer(1).address(), signer(2).address()];
+
+        assert!(MultisigConfigurables::new(owners.clone(), 0).is_err());
+        assert!(MultisigConfigurables::new(owners.clone(), 3).is_err());
+        assert!(MultisigConfigurables::new(vec![owners[0], owners[0]], 1).is_err());
+
+        let multisig = MultisigAccount::new(MultisigConfigurables::new(owners, 2).unwrap());
+        assert!(multisig.with_signer(signer(3)).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use std::sync::Arc;
 use std::{fmt::Debug, fs};
 
 #[cfg(feature = "std")]
@@ -10,7 +12,7 @@ use fuels_core::{
 #[cfg(feature = "std")]
 use crate::accounts_utils::try_provider_error;
 #[cfg(feature = "std")]
-use crate::{Account, ViewOnlyAccount, provider::Provider};
+use crate::{Account, ViewOnlyAccount, coin_selection::CoinSelector, provider::Provider};
 
 #[derive(Debug, Clone)]
 pub struct Predicate {
@@ -19,6 +21,8 @@ pub struct Predicate {
     data: Vec<u8>,
     #[cfg(feature = "std")]
     provider: Option<Provider>,
+    #[cfg(feature = "std")]
+    coin_selector: Option<Arc<dyn CoinSelector>>,
 }
 
 impl Predicate {
@@ -55,6 +59,8 @@ impl Predicate {
             data: Default::default(),
             #[cfg(feature = "std")]
             provider: None,
+            #[cfg(feature = "std")]
+            coin_selector: None,
         }
     }
 
@@ -97,6 +103,17 @@ impl Predicate {
             ..self
         }
     }
+
+    /// Picks the resources spent by the predicate with `coin_selector` instead of leaving it to
+    /// the node.
+    pub fn set_coin_selector(&mut self, coin_selector: impl CoinSelector + 'static) {
+        self.coin_selector = Some(Arc::new(coin_selector));
+    }
+
+    pub fn with_coin_selector(mut self, coin_selector: impl CoinSelector + 'static) -> Self {
+        self.set_coin_selector(coin_selector);
+        self
+    }
 }
 
 #[cfg(feature = "std")]
@@ -110,6 +127,10 @@ impl ViewOnlyAccount for Predicate {
         self.provider.as_ref().ok_or_else(try_provider_error)
     }
 
+    fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+        self.coin_selector.as_deref()
+    }
+
     async fn get_asset_inputs_for_amount(
         &self,
         asset_id: AssetId,
@@ -0,0 +1,82 @@
+//! Runs the predicates of the predicate-backed accounts in the VM.
+
+use fuel_crypto::{Hasher, Message, Signature};
+use fuel_tx::{
+    ConsensusParameters, Output, TxPointer, UniqueIdentifier, UtxoId, Witness, field::Witnesses,
+    policies::Policies,
+};
+use fuel_vm::{
+    checked_transaction::{CheckPredicateParams, CheckPredicates, EstimatePredicates, IntoChecked},
+    interpreter::NotSupportedEcal,
+    prelude::MemoryInstance,
+    storage::predicate::EmptyStorage,
+};
+use fuels_core::types::{AssetId, Bytes32};
+
+use crate::predicate::Predicate;
+
+/// A transaction spending a coin of `predicate`, with `placeholders` witnesses holding default
+/// signatures.
+///
+/// The predicate gas is estimated with the placeholders, so it must still match once they are
+/// replaced by signatures.
+pub(crate) fn unsigned_tx(predicate: &Predicate, placeholders: usize) -> fuel_tx::Script {
+    let input = fuel_tx::Input::coin_predicate(
+        UtxoId::new(Bytes32::from([1; 32]), 0),
+        predicate.address(),
+        100,
+        AssetId::zeroed(),
+        TxPointer::default(),
+        0,
+        predicate.code().to_vec(),
+        vec![],
+    );
+    let placeholder: Witness = Signature::default().as_ref().into();
+
+    let mut tx = fuel_tx::Transaction::script(
+        0,
+        vec![],
+        vec![],
+        Policies::new().with_max_fee(0),
+        vec![input],
+        vec![Output::change(predicate.address(), 0, AssetId::zeroed())],
+        vec![placeholder; placeholders],
+    );
+
+    let params = CheckPredicateParams::from(&ConsensusParameters::standard());
+    tx.estimate_predicates(&params, MemoryInstance::new(), &EmptyStorage)
+        .unwrap();
+
+    tx
+}
+
+/// The message signed by the owners of the predicate.
+pub(crate) fn tx_message(tx: &fuel_tx::Script) -> Message {
+    let chain_id = ConsensusParameters::standard().chain_id();
+
+    Message::from_bytes(*tx.id(&chain_id))
+}
+
+pub(crate) fn set_signature(tx: &mut fuel_tx::Script, index: usize, signature: Signature) {
+    tx.witnesses_mut()[index] = signature.as_ref().into();
+}
+
+pub(crate) fn predicates_pass(tx: fuel_tx::Script) -> bool {
+    let consensus_parameters = ConsensusParameters::standard();
+    let params = CheckPredicateParams::from(&consensus_parameters);
+
+    tx.into_checked_basic(0u32.into(), &consensus_parameters)
+        .unwrap()
+        .check_predicates(
+            &params,
+            MemoryInstance::new(),
+            &EmptyStorage,
+            NotSupportedEcal,
+        )
+        .is_ok()
+}
+
+/// Changing the bytecode of a predicate changes the address of every account using it.
+pub(crate) fn assert_code_is_pinned(code: Vec<u8>, hash: &str) {
+    assert_eq!(Hasher::hash(code).to_string(), hash);
+}
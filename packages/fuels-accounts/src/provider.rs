@@ -1,16 +1,33 @@
-#[cfg(feature = "coin-cache")]
-use std::sync::Arc;
-use std::{collections::HashMap, fmt::Debug, net::SocketAddr};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
 
+mod batched_queries;
+mod block_subscription;
 mod cache;
+mod cache_storage;
+mod contract_logs;
+mod endpoint_pool;
+mod middleware;
+mod node_api;
 mod retry_util;
 mod retryable_client;
 mod supported_fuel_core_version;
 mod supported_versions;
+pub mod transport;
 
-pub use cache::TtlConfig;
+#[cfg(feature = "coin-cache")]
+use async_lock::Mutex;
+pub use cache::{CacheConfig, CachedData, TtlConfig};
 use cache::{CachedClient, SystemClock};
+pub use cache_storage::{CacheEntry, CacheKey, CacheStorage, InMemoryCacheStorage};
 use chrono::{DateTime, Utc};
+pub use contract_logs::ContractLogs;
+pub use endpoint_pool::{EndpointPoolConfig, EndpointStatus, Routing};
 use fuel_core_client::client::{
     FuelClient,
     pagination::{PageDirection, PaginatedResult, PaginationRequest},
@@ -35,8 +52,8 @@ use fuels_core::{
         chain_info::ChainInfo,
         coin::Coin,
         coin_type::CoinType,
-        errors::Result,
-        message::Message,
+        errors::{Result, error},
+        message::{Message, MessageStatus},
         message_proof::MessageProof,
         node_info::NodeInfo,
         transaction::{Transaction, Transactions},
@@ -46,17 +63,26 @@ use fuels_core::{
     },
 };
 use futures::StreamExt;
+pub use middleware::{Next, ProviderMiddleware, RpcCall};
+pub use node_api::NodeApi;
 pub use retry_util::{Backoff, RetryConfig};
 pub use supported_fuel_core_version::SUPPORTED_FUEL_CORE_VERSION;
 use tai64::Tai64;
-#[cfg(feature = "coin-cache")]
-use tokio::sync::Mutex;
+use transport::GraphQlTransport;
 
 #[cfg(feature = "coin-cache")]
 use crate::coin_cache::CoinsCache;
-use crate::provider::{cache::CacheableRpcs, retryable_client::RetryableClient};
+use crate::{
+    coin_selection::CoinSelector,
+    provider::{cache::CacheableRpcs, retryable_client::RetryableClient},
+};
 
 const NUM_RESULTS_PER_REQUEST: i32 = 100;
+/// How many owners are batched into a single request, keeping it within the default query
+/// complexity limit of the node.
+const BALANCES_BATCH_SIZE: usize = 50;
+const COINS_BATCH_SIZE: usize = 25;
+const CONTRACT_BALANCES_BATCH_SIZE: usize = 25;
 
 #[derive(Debug, Clone, PartialEq)]
 // ANCHOR: transaction_cost
@@ -141,6 +167,19 @@ impl Provider {
         self.cached_client.set_ttl(ttl);
     }
 
+    /// Opts into caching the chain info and the immutable data selected by `config`.
+    pub fn set_cache_config(&mut self, config: CacheConfig) {
+        self.cached_client.set_cache_config(config);
+    }
+
+    /// Keeps the immutable data cached by the provider, e.g. blocks or finalized transactions, in
+    /// `storage` instead of in memory.
+    pub fn set_cache_storage(&mut self, storage: impl CacheStorage + 'static) {
+        self.cached_client.set_storage(Arc::new(storage));
+    }
+
+    /// Clears the cached consensus parameters and chain info. The immutable data in the cache
+    /// storage never goes stale and is kept.
     pub async fn clear_cache(&self) {
         self.cached_client.clear().await;
     }
@@ -168,13 +207,97 @@ impl Provider {
         })
     }
 
+    /// Connects to a pool of nodes, balancing the requests across the healthy ones.
+    ///
+    /// Unlike [`Provider::connect_with_fallbacks`], the health of every node is checked in the
+    /// background for as long as the provider lives. A node failing a request is avoided until
+    /// it is found healthy again, so a retried request is sent to another node. Requests about a
+    /// submitted transaction, e.g. its status, go to the node that received it while that node
+    /// is healthy.
+    ///
+    /// A request is attempted up to once per node, without waiting in between. A
+    /// [`RetryConfig`] set through [`Provider::with_retry_config`] replaces this.
+    pub async fn connect_with_pool(
+        urls: &[impl AsRef<str>],
+        config: EndpointPoolConfig,
+    ) -> Result<Provider> {
+        let retry_config = RetryConfig::new(
+            u32::try_from(urls.len()).unwrap_or(u32::MAX).max(1),
+            Backoff::Fixed(Duration::ZERO),
+        )?;
+
+        let client = CachedClient::new(
+            RetryableClient::connect_with_pool(urls, config, retry_config).await?,
+            TtlConfig::default(),
+            SystemClock,
+        );
+
+        Ok(Self {
+            cached_client: client,
+            #[cfg(feature = "coin-cache")]
+            coins_cache: Default::default(),
+        })
+    }
+
+    /// Connects to a node through a custom `transport`, e.g. one with its own HTTP stack or one
+    /// serving recorded responses.
+    pub async fn connect_with_transport(
+        transport: impl GraphQlTransport + 'static,
+    ) -> Result<Provider> {
+        let client = CachedClient::new(
+            RetryableClient::connect_with_transport(Arc::new(transport), Default::default())
+                .await?,
+            TtlConfig::default(),
+            SystemClock,
+        );
+
+        Ok(Self {
+            cached_client: client,
+            #[cfg(feature = "coin-cache")]
+            coins_cache: Default::default(),
+        })
+    }
+
+    /// Serves the requests of the provider from `backend` instead of a node, e.g. from a node
+    /// running in-process.
+    ///
+    /// `url` is reported by [`Provider::url`] and is where [`Provider::client`] connects to, so
+    /// requests made through the client bypass the backend.
+    pub async fn connect_with_backend(
+        url: impl AsRef<str>,
+        backend: impl NodeApi + 'static,
+    ) -> Result<Provider> {
+        let client = CachedClient::new(
+            RetryableClient::connect_with_backend(
+                url.as_ref(),
+                Arc::new(backend),
+                Default::default(),
+            )
+            .await?,
+            TtlConfig::default(),
+            SystemClock,
+        );
+
+        Ok(Self {
+            cached_client: client,
+            #[cfg(feature = "coin-cache")]
+            coins_cache: Default::default(),
+        })
+    }
+
     pub fn url(&self) -> &str {
         self.uncached_client().url()
     }
 
+    /// The nodes of a provider connected through [`Provider::connect_with_pool`], empty
+    /// otherwise.
+    pub fn endpoints(&self) -> Vec<EndpointStatus> {
+        self.uncached_client().endpoints()
+    }
+
     pub async fn blob(&self, blob_id: BlobId) -> Result<Option<Blob>> {
         Ok(self
-            .uncached_client()
+            .cached_client
             .blob(blob_id.into())
             .await?
             .map(|blob| Blob::new(blob.bytecode)))
@@ -293,6 +416,24 @@ impl Provider {
         self.submit(tx).await
     }
 
+    /// Sends a transaction replacing a pending one that spends the same inputs, e.g. with a
+    /// higher tip.
+    ///
+    /// Unlike `send_transaction`, inputs used by recently submitted transactions are not rejected.
+    pub async fn send_replacement_transaction<T: Transaction>(&self, tx: T) -> Result<TxId> {
+        let tx = self.prepare_transaction_for_sending(tx).await?;
+
+        #[cfg(feature = "coin-cache")]
+        let used_utxos = tx.used_coins(self.consensus_parameters().await?.base_asset_id());
+
+        let tx_id = self.uncached_client().submit(&tx.into()).await?;
+
+        #[cfg(feature = "coin-cache")]
+        self.coins_cache.lock().await.insert_multiple(used_utxos);
+
+        Ok(tx_id)
+    }
+
     pub async fn await_transaction_commit<T: Transaction>(&self, id: TxId) -> Result<TxStatus> {
         Ok(self
             .uncached_client()
@@ -367,11 +508,7 @@ impl Provider {
     }
 
     pub async fn tx_status(&self, tx_id: &TxId) -> Result<TxStatus> {
-        Ok(self
-            .uncached_client()
-            .transaction_status(tx_id)
-            .await?
-            .into())
+        Ok(self.cached_client.transaction_status(tx_id).await?.into())
     }
 
     pub async fn subscribe_transaction_status<'a>(
@@ -388,7 +525,7 @@ impl Provider {
     }
 
     pub async fn chain_info(&self) -> Result<ChainInfo> {
-        Ok(self.uncached_client().chain_info().await?.into())
+        Ok(self.cached_client.chain_info().await?.into())
     }
 
     pub async fn consensus_parameters(&self) -> Result<ConsensusParameters> {
@@ -513,6 +650,45 @@ impl Provider {
         Ok(coins)
     }
 
+    /// Gets all unspent coins of asset `asset_id` owned by every address in `addresses`, batching
+    /// the queries of several addresses into a single request.
+    pub async fn get_coins_many(
+        &self,
+        addresses: &[Address],
+        asset_id: AssetId,
+    ) -> Result<HashMap<Address, Vec<Coin>>> {
+        let mut coins: HashMap<Address, Vec<Coin>> = HashMap::new();
+
+        for owners in addresses.chunks(COINS_BATCH_SIZE) {
+            let first_pages = self
+                .uncached_client()
+                .coins_many(owners, &asset_id, NUM_RESULTS_PER_REQUEST)
+                .await?;
+
+            for (owner, mut page) in owners.iter().zip(first_pages) {
+                let owner_coins = coins.entry(*owner).or_default();
+                loop {
+                    owner_coins.extend(page.results.into_iter().map(Into::into));
+                    if !page.has_next_page {
+                        break;
+                    }
+
+                    let pagination = PaginationRequest {
+                        cursor: page.cursor,
+                        results: NUM_RESULTS_PER_REQUEST,
+                        direction: PageDirection::Forward,
+                    };
+                    page = self
+                        .uncached_client()
+                        .coins(owner, Some(&asset_id), pagination)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(coins)
+    }
+
     async fn request_coins_to_spend(&self, filter: ResourceFilter) -> Result<Vec<CoinType>> {
         let queries = filter.resource_queries();
 
@@ -592,6 +768,59 @@ impl Provider {
         Ok(())
     }
 
+    /// Like `get_spendable_resources`, but the resources are picked by `selector` out of all the
+    /// spendable coins and messages of the owner instead of by the node.
+    pub async fn select_spendable_resources(
+        &self,
+        #[allow(unused_mut)] mut filter: ResourceFilter,
+        selector: &dyn CoinSelector,
+    ) -> Result<Vec<CoinType>> {
+        #[cfg(feature = "coin-cache")]
+        self.extend_filter_with_cached(&mut filter).await?;
+
+        let consensus_parameters = self.consensus_parameters().await?;
+        let base_asset_id = *consensus_parameters.base_asset_id();
+        let asset_id = filter.asset_id.unwrap_or(base_asset_id);
+
+        let excluded_utxos: HashSet<_> = filter.excluded_utxos.iter().collect();
+        let mut candidates: Vec<_> = self
+            .get_coins(&filter.from, asset_id)
+            .await?
+            .into_iter()
+            .filter(|coin| !excluded_utxos.contains(&coin.utxo_id))
+            .map(CoinType::Coin)
+            .collect();
+
+        if asset_id == base_asset_id {
+            let excluded_nonces: HashSet<_> = filter.excluded_message_nonces.iter().collect();
+            // messages with data can only be spent by predicates, not as base asset
+            let messages = self
+                .get_messages(&filter.from)
+                .await?
+                .into_iter()
+                .filter(|message| {
+                    message.data.is_empty()
+                        && message.status == MessageStatus::Unspent
+                        && !excluded_nonces.contains(&message.nonce)
+                })
+                .map(CoinType::Message);
+            candidates.extend(messages);
+        }
+
+        let selected = selector.select(candidates, filter.amount)?;
+
+        let max_inputs = consensus_parameters.tx_params().max_inputs();
+        if selected.len() > usize::from(max_inputs) {
+            return Err(error!(
+                Other,
+                "{selector:?} picked {} resources, more than the `{max_inputs}` inputs allowed",
+                selected.len()
+            ));
+        }
+
+        Ok(selected)
+    }
+
     /// Get the balance of all spendable coins `asset_id` for address `address`. This is different
     /// from getting coins because we are just returning a number (the sum of UTXOs amount) instead
     /// of the UTXOs.
@@ -701,12 +930,104 @@ impl Provider {
         Ok(contract_balances)
     }
 
+    /// Get all balances of all assets for every address in `addresses`, batching the queries of
+    /// several addresses into a single request.
+    ///
+    /// Falls back to a request per address if the node does not index balances.
+    pub async fn get_balances_many(
+        &self,
+        addresses: &[Address],
+    ) -> Result<HashMap<Address, HashMap<String, u128>>> {
+        let mut balances: HashMap<Address, HashMap<String, u128>> = HashMap::new();
+
+        let indexation_flags = self.cached_client.node_info().await?.indexation;
+        if !indexation_flags.balances {
+            for address in addresses {
+                balances.insert(*address, self.get_balances(address).await?);
+            }
+
+            return Ok(balances);
+        }
+
+        for owners in addresses.chunks(BALANCES_BATCH_SIZE) {
+            let first_pages = self
+                .uncached_client()
+                .balances_many(owners, NUM_RESULTS_PER_REQUEST)
+                .await?;
+
+            for (owner, mut page) in owners.iter().zip(first_pages) {
+                let owner_balances = balances.entry(*owner).or_default();
+                loop {
+                    owner_balances.extend(page.results.into_iter().map(
+                        |Balance {
+                             owner: _,
+                             amount,
+                             asset_id,
+                         }| (asset_id.to_string(), amount),
+                    ));
+                    if !page.has_next_page {
+                        break;
+                    }
+
+                    let pagination = PaginationRequest {
+                        cursor: page.cursor,
+                        results: NUM_RESULTS_PER_REQUEST,
+                        direction: PageDirection::Forward,
+                    };
+                    page = self.uncached_client().balances(owner, pagination).await?;
+                }
+            }
+        }
+
+        Ok(balances)
+    }
+
+    /// Get all balances of all assets for every contract in `contract_ids`, batching the queries
+    /// of several contracts into a single request.
+    pub async fn get_contract_balances_many(
+        &self,
+        contract_ids: &[ContractId],
+    ) -> Result<HashMap<ContractId, HashMap<AssetId, u64>>> {
+        let mut contract_balances: HashMap<ContractId, HashMap<AssetId, u64>> = HashMap::new();
+
+        for contracts in contract_ids.chunks(CONTRACT_BALANCES_BATCH_SIZE) {
+            let first_pages = self
+                .uncached_client()
+                .contract_balances_many(contracts, NUM_RESULTS_PER_REQUEST)
+                .await?;
+
+            for (contract, mut page) in contracts.iter().zip(first_pages) {
+                let balances = contract_balances.entry(*contract).or_default();
+                loop {
+                    balances.extend(page.results.into_iter().map(
+                        |ContractBalance {
+                             contract: _,
+                             amount,
+                             asset_id,
+                         }| (asset_id, amount),
+                    ));
+                    if !page.has_next_page {
+                        break;
+                    }
+
+                    let pagination = PaginationRequest {
+                        cursor: page.cursor,
+                        results: NUM_RESULTS_PER_REQUEST,
+                        direction: PageDirection::Forward,
+                    };
+                    page = self
+                        .uncached_client()
+                        .contract_balances(contract, pagination)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(contract_balances)
+    }
+
     pub async fn get_transaction_by_id(&self, tx_id: &TxId) -> Result<Option<TransactionResponse>> {
-        Ok(self
-            .uncached_client()
-            .transaction(tx_id)
-            .await?
-            .map(Into::into))
+        Ok(self.cached_client.transaction(tx_id).await?.map(Into::into))
     }
 
     pub async fn get_transactions(
@@ -765,16 +1086,12 @@ impl Provider {
     }
 
     pub async fn block(&self, block_id: &Bytes32) -> Result<Option<Block>> {
-        Ok(self
-            .uncached_client()
-            .block(block_id)
-            .await?
-            .map(Into::into))
+        Ok(self.cached_client.block(block_id).await?.map(Into::into))
     }
 
     pub async fn block_by_height(&self, height: BlockHeight) -> Result<Option<Block>> {
         Ok(self
-            .uncached_client()
+            .cached_client
             .block_by_height(height)
             .await?
             .map(Into::into))
@@ -896,8 +1213,26 @@ impl Provider {
         self
     }
 
+    /// Adds `middleware` around every RPC the provider makes. Middleware added first runs
+    /// outermost.
+    pub fn with_middleware(mut self, middleware: impl ProviderMiddleware + 'static) -> Self {
+        self.uncached_client_mut()
+            .push_middleware(Arc::new(middleware));
+
+        self
+    }
+
     pub async fn contract_exists(&self, contract_id: &ContractId) -> Result<bool> {
-        Ok(self.uncached_client().contract_exists(contract_id).await?)
+        self.cached_client.contract_exists(contract_id).await
+    }
+
+    /// Returns the bytecode of the contract deployed at `contract_id`, if any.
+    pub async fn contract_bytecode(&self, contract_id: &ContractId) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .cached_client
+            .contract(contract_id)
+            .await?
+            .map(|contract| contract.bytecode))
     }
 
     fn uncached_client(&self) -> &RetryableClient {
@@ -909,6 +1244,7 @@ impl Provider {
     }
 }
 
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl DryRunner for Provider {
     async fn dry_run(&self, tx: FuelTransaction) -> Result<DryRun> {
@@ -958,4 +1294,11 @@ impl DryRunner for Provider {
     ) -> Result<FuelTransaction> {
         Ok(self.uncached_client().estimate_predicates(tx).await?)
     }
+
+    async fn recent_tips(&self, block_count: u32) -> Result<Vec<u64>> {
+        Ok(self
+            .uncached_client()
+            .recent_tips(i32::try_from(block_count).unwrap_or(i32::MAX))
+            .await?)
+    }
 }
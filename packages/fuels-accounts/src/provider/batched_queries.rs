@@ -0,0 +1,243 @@
+//! Queries combining the same field for several owners into a single request.
+//!
+//! Every owner gets its own alias, e.g. `owner_0: balances(filter: ..., first: 100) { ... }`,
+//! and the response is split back per owner. The selection sets are taken from the queries of
+//! `fuel-core-client`, so that the responses decode into its types.
+
+use std::{collections::HashMap, fmt::Write, io};
+
+use cynic::{Operation, QueryBuilder, QueryFragment, queries::SelectionBuilder};
+use fuel_core_client::client::{
+    pagination::{PageDirection, PaginatedResult, PaginationRequest},
+    schema::{
+        balance::{BalanceConnection, BalancesConnectionArgs, BalancesQuery},
+        coins::{CoinConnection, CoinsConnectionArgs, CoinsQuery},
+        contract::{
+            ContractBalanceConnection, ContractBalancesConnectionArgs, ContractBalancesQuery,
+        },
+        schema,
+    },
+    types::{Balance, ContractBalance, coins::Coin},
+};
+use fuel_types::{Address, AssetId, ContractId};
+use serde::Deserialize;
+
+/// The response of a query with a field per alias.
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct Aliased<T>(HashMap<String, T>);
+
+impl<T> QueryFragment for Aliased<T> {
+    type SchemaType = schema::Query;
+    type VariablesFields = ();
+
+    // the query is written by `aliased_operation`, never built by cynic
+    fn query(_builder: SelectionBuilder<'_, Self::SchemaType, Self::VariablesFields>) {}
+}
+
+impl<T> Aliased<T> {
+    /// Returns the responses in the order the fields were queried.
+    pub(crate) fn split<R: From<T>>(mut self, count: usize) -> io::Result<Vec<R>> {
+        (0..count)
+            .map(|idx| {
+                self.0.remove(&alias(idx)).map(Into::into).ok_or_else(|| {
+                    io::Error::other(format!("response is missing the field `{}`", alias(idx)))
+                })
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn balances_many_query(
+    owners: &[Address],
+    first: i32,
+) -> io::Result<Operation<Aliased<BalanceConnection>, ()>> {
+    let template = BalancesQuery::build(BalancesConnectionArgs::from((
+        Address::zeroed().into(),
+        first_page(first),
+    )));
+    let fields = owners
+        .iter()
+        .map(|owner| format!("balances(filter: {{owner: \"{owner:#x}\"}}, first: {first})"));
+
+    aliased_operation("BalancesManyQuery", &template.query, fields)
+}
+
+pub(crate) fn coins_many_query(
+    owners: &[Address],
+    asset_id: &AssetId,
+    first: i32,
+) -> io::Result<Operation<Aliased<CoinConnection>, ()>> {
+    let template = CoinsQuery::build(CoinsConnectionArgs::from((
+        Address::zeroed().into(),
+        Some(AssetId::zeroed().into()),
+        first_page(first),
+    )));
+    let fields = owners.iter().map(|owner| {
+        format!(
+            "coins(filter: {{owner: \"{owner:#x}\", assetId: \"{asset_id:#x}\"}}, first: {first})"
+        )
+    });
+
+    aliased_operation("CoinsManyQuery", &template.query, fields)
+}
+
+pub(crate) fn contract_balances_many_query(
+    contracts: &[ContractId],
+    first: i32,
+) -> io::Result<Operation<Aliased<ContractBalanceConnection>, ()>> {
+    let template = ContractBalancesQuery::build(ContractBalancesConnectionArgs::from((
+        ContractId::zeroed().into(),
+        first_page(first),
+    )));
+    let fields = contracts.iter().map(|contract| {
+        format!("contractBalances(filter: {{contract: \"{contract:#x}\"}}, first: {first})")
+    });
+
+    aliased_operation("ContractBalancesManyQuery", &template.query, fields)
+}
+
+pub(crate) type Balances = PaginatedResult<Balance, String>;
+pub(crate) type Coins = PaginatedResult<Coin, String>;
+pub(crate) type ContractBalances = PaginatedResult<ContractBalance, String>;
+
+fn first_page(results: i32) -> PaginationRequest<String> {
+    PaginationRequest {
+        cursor: None,
+        results,
+        direction: PageDirection::Forward,
+    }
+}
+
+fn alias(idx: usize) -> String {
+    format!("owner_{idx}")
+}
+
+/// Queries every field in `fields` under its own alias, selecting what the single field of
+/// `template` selects.
+fn aliased_operation<T>(
+    name: &'static str,
+    template: &str,
+    fields: impl Iterator<Item = String>,
+) -> io::Result<Operation<Aliased<T>, ()>> {
+    let selection_set = selection_set(template)?;
+
+    let mut query = format!("query {name} {{\n");
+    for (idx, field) in fields.enumerate() {
+        writeln!(query, "  {}: {field} {selection_set}", alias(idx)).expect("infallible");
+    }
+    query.push('}');
+
+    let mut operation = Operation::new(query, ());
+    operation.operation_name = Some(name.into());
+
+    Ok(operation)
+}
+
+/// The selection set of the single field queried by `query`, braces included.
+fn selection_set(query: &str) -> io::Result<&str> {
+    let malformed = |reason: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot batch query `{query}`: {reason}"),
+        )
+    };
+
+    let body_start = query.find('{').ok_or_else(|| malformed("it has no body"))? + 1;
+    let start = body_start
+        + query[body_start..]
+            .find('{')
+            .ok_or_else(|| malformed("the queried field has no selection set"))?;
+
+    let mut depth = 0;
+    for (idx, c) in query[start..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Ok(&query[start..=start + idx]),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    Err(malformed("its braces are unbalanced"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn fields_are_aliased_with_the_selection_set_of_the_template() {
+        let owners = [Address::from([1; 32]), Address::from([2; 32])];
+
+        let operation = balances_many_query(&owners, 10).unwrap();
+
+        let expected_field = format!(
+            "owner_1: balances(filter: {{owner: \"{:#x}\"}}, first: 10) {{",
+            owners[1]
+        );
+        assert!(operation.query.starts_with("query BalancesManyQuery {\n"));
+        assert!(operation.query.contains(&expected_field));
+        assert_eq!(operation.query.matches("pageInfo {").count(), 2);
+        assert_eq!(
+            operation.query.matches('{').count(),
+            operation.query.matches('}').count()
+        );
+    }
+
+    #[test]
+    fn malformed_templates_are_rejected() {
+        let templates = [
+            "query Query",
+            "query Query { balances }",
+            "query Query { balances { edges { cursor }",
+        ];
+
+        for template in templates {
+            let err = selection_set(template).unwrap_err();
+
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn responses_are_split_per_alias() {
+        let connection = |amount: &str| {
+            json!({
+                "edges": [{
+                    "cursor": "cursor",
+                    "node": {
+                        "owner": format!("{:#x}", Address::zeroed()),
+                        "amount": amount,
+                        "assetId": format!("{:#x}", fuel_types::AssetId::zeroed()),
+                    }
+                }],
+                "pageInfo": {
+                    "endCursor": "cursor",
+                    "hasNextPage": false,
+                    "hasPreviousPage": false,
+                    "startCursor": "cursor"
+                }
+            })
+        };
+        let response: Aliased<BalanceConnection> = serde_json::from_value(json!({
+            "owner_1": connection("2"),
+            "owner_0": connection("1"),
+        }))
+        .unwrap();
+
+        let balances: Vec<Balances> = response.split(2).unwrap();
+
+        let amounts: Vec<_> = balances
+            .iter()
+            .map(|balances| balances.results[0].amount)
+            .collect();
+        assert_eq!(amounts, [1, 2]);
+
+        let response: Aliased<BalanceConnection> =
+            serde_json::from_value(json!({"owner_0": connection("1")})).unwrap();
+        assert!(response.split::<Balances>(2).is_err());
+    }
+}
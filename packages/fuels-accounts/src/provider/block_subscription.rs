@@ -0,0 +1,197 @@
+use std::{collections::VecDeque, io, time::Duration};
+
+use fuel_core_client::client::pagination::{PageDirection, PaginationRequest};
+use fuel_core_types::services::block_importer::ImportResult;
+use fuels_core::types::{
+    block::Block,
+    errors::{Result, error},
+};
+use futures::{Stream, StreamExt, stream};
+
+use crate::{
+    provider::{Backoff, NUM_RESULTS_PER_REQUEST, Provider},
+    runtime::{self, BoxStream},
+};
+
+/// Bounds on the delay between reestablishing a dropped subscription, so that a node dropping
+/// it repeatedly is not flooded with requests.
+const MIN_RESUBSCRIBE_DELAY: Duration = Duration::from_millis(50);
+const MAX_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(10);
+
+struct BlockSubscription<'a> {
+    provider: &'a Provider,
+    next_height: u32,
+    buffered: VecDeque<Block>,
+    new_blocks: Option<BoxStream<'a, io::Result<ImportResult>>>,
+    /// How many times in a row the subscription ended without yielding a block.
+    dropped_subscriptions: u32,
+}
+
+impl BlockSubscription<'_> {
+    async fn next_block(mut self) -> Result<Option<(Block, Self)>> {
+        loop {
+            if let Some(block) = self.buffered.pop_front() {
+                self.next_height = block.header.height + 1;
+
+                return Ok(Some((block, self)));
+            }
+
+            // Subscribing before querying guarantees that blocks produced in between still
+            // wake us up
+            let mut new_blocks = match self.new_blocks.take() {
+                Some(new_blocks) => new_blocks,
+                None => self
+                    .provider
+                    .uncached_client()
+                    .new_blocks_subscription()
+                    .await
+                    .map(runtime::boxed_stream)?,
+            };
+
+            self.buffered = blocks_from(self.provider, self.next_height).await?.into();
+
+            if !self.buffered.is_empty() {
+                self.new_blocks = Some(new_blocks);
+                continue;
+            }
+
+            // If the subscription errored or ended it is reestablished on the next iteration,
+            // after backing off. Blocks missed in the meantime are picked up by the query.
+            if let Some(Ok(_)) = new_blocks.next().await {
+                self.new_blocks = Some(new_blocks);
+                self.dropped_subscriptions = 0;
+            } else {
+                let backoff = self.provider.uncached_client().retry_config().interval();
+                runtime::sleep(resubscribe_delay(backoff, self.dropped_subscriptions)).await;
+                self.dropped_subscriptions = self.dropped_subscriptions.saturating_add(1);
+            }
+        }
+    }
+}
+
+fn resubscribe_delay(backoff: &Backoff, dropped_subscriptions: u32) -> Duration {
+    // Caps the attempt so that exponential backoffs don't overflow
+    backoff
+        .wait_duration(dropped_subscriptions.min(16))
+        .clamp(MIN_RESUBSCRIBE_DELAY, MAX_RESUBSCRIBE_DELAY)
+}
+
+// Doesn't borrow the `BlockSubscription` since its `new_blocks` stream is not `Sync`
+async fn blocks_from(provider: &Provider, from_height: u32) -> Result<Vec<Block>> {
+    let request = PaginationRequest {
+        cursor: from_height.checked_sub(1).map(|height| height.to_string()),
+        results: NUM_RESULTS_PER_REQUEST,
+        direction: PageDirection::Forward,
+    };
+
+    let blocks = provider
+        .get_blocks(request)
+        .await?
+        .results
+        .into_iter()
+        .skip_while(|block| block.header.height < from_height)
+        .collect::<Vec<_>>();
+
+    check_contiguous(from_height, &blocks)?;
+
+    Ok(blocks)
+}
+
+fn check_contiguous(from_height: u32, blocks: &[Block]) -> Result<()> {
+    for (expected_height, block) in (from_height..).zip(blocks) {
+        if block.header.height != expected_height {
+            return Err(error!(
+                Provider,
+                "block at height {expected_height} is not available, the node returned block {} instead",
+                block.header.height
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+impl Provider {
+    /// Returns a stream of all blocks starting at `from_height`.
+    ///
+    /// Blocks that were already produced are fetched in pages until the stream catches up with
+    /// the chain. Afterwards it waits on the node's new blocks subscription, which is
+    /// reestablished if the connection is lost, waiting between attempts as set by the backoff of
+    /// the provider's [`RetryConfig`](crate::provider::RetryConfig). Blocks are always yielded in
+    /// order and without gaps. If the node cannot provide a block, an error is yielded and the
+    /// stream ends. It can be resumed by subscribing again from the height following the last
+    /// received block.
+    pub fn subscribe_blocks(&self, from_height: u32) -> impl Stream<Item = Result<Block>> + '_ {
+        let subscription = BlockSubscription {
+            provider: self,
+            next_height: from_height,
+            buffered: VecDeque::new(),
+            new_blocks: None,
+            dropped_subscriptions: 0,
+        };
+
+        stream::try_unfold(subscription, BlockSubscription::next_block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels_core::types::{Bytes32, block::Header};
+
+    use super::*;
+
+    fn block_at(height: u32) -> Block {
+        Block {
+            id: Bytes32::zeroed(),
+            header: Header {
+                id: Bytes32::zeroed(),
+                da_height: 0,
+                transactions_count: 0,
+                message_receipt_count: 0,
+                transactions_root: Bytes32::zeroed(),
+                message_outbox_root: Bytes32::zeroed(),
+                event_inbox_root: Bytes32::zeroed(),
+                consensus_parameters_version: 0,
+                state_transition_bytecode_version: 0,
+                height,
+                prev_root: Bytes32::zeroed(),
+                time: None,
+                application_hash: Bytes32::zeroed(),
+            },
+            transactions: vec![],
+        }
+    }
+
+    #[test]
+    fn gaps_between_blocks_are_detected() {
+        let blocks = [block_at(3), block_at(4)];
+        assert!(check_contiguous(3, &blocks).is_ok());
+
+        let err = check_contiguous(2, &blocks).expect_err("should fail");
+        assert!(
+            err.to_string()
+                .contains("block at height 2 is not available")
+        );
+
+        let blocks = [block_at(3), block_at(5)];
+        let err = check_contiguous(3, &blocks).expect_err("should fail");
+        assert!(
+            err.to_string()
+                .contains("block at height 4 is not available")
+        );
+    }
+
+    #[test]
+    fn resubscribing_backs_off_within_bounds() {
+        let backoff = Backoff::Exponential(Duration::from_millis(100));
+        let delays: Vec<_> = (0..4).map(|n| resubscribe_delay(&backoff, n)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800].map(Duration::from_millis).to_vec()
+        );
+        assert_eq!(resubscribe_delay(&backoff, u32::MAX), MAX_RESUBSCRIBE_DELAY);
+
+        let no_backoff = Backoff::Fixed(Duration::ZERO);
+        assert_eq!(resubscribe_delay(&no_backoff, 0), MIN_RESUBSCRIBE_DELAY);
+    }
+}
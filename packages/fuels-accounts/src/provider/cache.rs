@@ -1,20 +1,35 @@
-use std::{sync::Arc, time::Duration};
+use std::{future::Future, sync::Arc, time::Duration};
 
+use async_lock::RwLock;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use fuel_core_client::client::types::NodeInfo;
-use fuel_tx::ConsensusParameters;
-use fuels_core::types::errors::Result;
-use tokio::sync::RwLock;
+use fuel_core_client::client::types::{
+    Blob, Block, ChainInfo, Contract, NodeInfo, TransactionResponse, TransactionStatus,
+    primitives::BlockId,
+};
+use fuel_tx::{BlobId, ConsensusParameters, TxId};
+use fuel_types::{BlockHeight, ContractId};
+use fuels_core::types::errors::{Result, error};
+
+use super::cache_storage::{CacheEntry, CacheKey, CacheStorage, InMemoryCacheStorage, Stored};
 
 #[cfg_attr(test, mockall::automock)]
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait CacheableRpcs {
     async fn consensus_parameters(&self) -> Result<ConsensusParameters>;
     async fn node_info(&self) -> Result<NodeInfo>;
+    async fn chain_info(&self) -> Result<ChainInfo>;
+    async fn block(&self, id: &BlockId) -> Result<Option<Block>>;
+    async fn block_by_height(&self, height: BlockHeight) -> Result<Option<Block>>;
+    async fn transaction(&self, id: &TxId) -> Result<Option<TransactionResponse>>;
+    async fn transaction_status(&self, id: &TxId) -> Result<TransactionStatus>;
+    async fn blob(&self, id: BlobId) -> Result<Option<Blob>>;
+    async fn contract_exists(&self, id: &ContractId) -> Result<bool>;
+    async fn contract(&self, id: &ContractId) -> Result<Option<Contract>>;
 }
 
-trait Clock {
+pub(crate) trait Clock {
     fn now(&self) -> DateTime<Utc>;
 }
 
@@ -31,6 +46,52 @@ impl Default for TtlConfig {
     }
 }
 
+// ANCHOR: cache_config
+/// What the provider caches besides the values of [`TtlConfig`]. Nothing by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheConfig {
+    /// The chain info holds the latest block, so it goes stale with every new block. Not cached
+    /// if zero.
+    pub chain_info_ttl: Duration,
+    /// The immutable data kept in the [`CacheStorage`]. It never goes stale.
+    pub immutable_data: CachedData,
+}
+// ANCHOR_END: cache_config
+
+/// Selects the kinds of immutable data cached by the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedData {
+    /// Blocks by id and by height.
+    pub blocks: bool,
+    /// Transactions and their statuses, including the receipts, once they succeeded or failed.
+    pub transactions: bool,
+    pub blobs: bool,
+    /// The existence and bytecode of deployed contracts.
+    pub contracts: bool,
+}
+
+impl CachedData {
+    pub const ALL: Self = Self {
+        blocks: true,
+        transactions: true,
+        blobs: true,
+        contracts: true,
+    };
+
+    pub const NONE: Self = Self {
+        blocks: false,
+        transactions: false,
+        blobs: false,
+        contracts: false,
+    };
+}
+
+impl Default for CachedData {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
 #[derive(Debug, Clone)]
 struct Dated<T> {
     value: T,
@@ -55,8 +116,11 @@ impl Clock for SystemClock {
 pub struct CachedClient<Client, Clock = SystemClock> {
     client: Client,
     ttl_config: TtlConfig,
+    cache_config: CacheConfig,
     cached_consensus_params: Arc<RwLock<Option<Dated<ConsensusParameters>>>>,
     cached_node_info: Arc<RwLock<Option<Dated<NodeInfo>>>>,
+    cached_chain_info: Arc<RwLock<Option<Dated<ChainInfo>>>>,
+    storage: Arc<dyn CacheStorage>,
     clock: Clock,
 }
 
@@ -65,8 +129,11 @@ impl<Client, Clock> CachedClient<Client, Clock> {
         Self {
             client,
             ttl_config: ttl,
+            cache_config: Default::default(),
             cached_consensus_params: Default::default(),
             cached_node_info: Default::default(),
+            cached_chain_info: Default::default(),
+            storage: Arc::new(InMemoryCacheStorage::default()),
             clock,
         }
     }
@@ -75,6 +142,14 @@ impl<Client, Clock> CachedClient<Client, Clock> {
         self.ttl_config = ttl
     }
 
+    pub fn set_cache_config(&mut self, config: CacheConfig) {
+        self.cache_config = config
+    }
+
+    pub fn set_storage(&mut self, storage: Arc<dyn CacheStorage>) {
+        self.storage = storage
+    }
+
     pub fn inner(&self) -> &Client {
         &self.client
     }
@@ -90,10 +165,61 @@ where
 {
     pub async fn clear(&self) {
         *self.cached_consensus_params.write().await = None;
+        *self.cached_chain_info.write().await = None;
+    }
+}
+
+impl<Client, Clk> CachedClient<Client, Clk>
+where
+    Clk: Clock + Send + Sync,
+    Client: CacheableRpcs + Send + Sync,
+{
+    /// Serves the value of `entry` from the storage if `enabled`. Otherwise `fetch`es it, storing
+    /// it if found and `immutable`.
+    async fn immutable<T: Stored>(
+        &self,
+        enabled: bool,
+        entry: CacheEntry,
+        fetch: impl Future<Output = Result<Option<T>>>,
+        immutable: impl FnOnce(&T) -> bool,
+    ) -> Result<Option<T>> {
+        if !enabled {
+            return fetch.await;
+        }
+
+        let key = CacheKey {
+            chain_id: self.consensus_parameters().await?.chain_id(),
+            entry,
+        };
+
+        if let Some(bytes) = self.storage.get(&key).await? {
+            let value = T::decode(&bytes)
+                .map_err(|e| error!(Other, "could not decode cached `{key}`: {e}"))?;
+
+            return Ok(Some(value));
+        }
+
+        let value = fetch.await?;
+        if let Some(value) = value.as_ref().filter(|value| immutable(value)) {
+            let bytes = value
+                .encode()
+                .map_err(|e| error!(Other, "could not encode `{key}` for the cache: {e}"))?;
+            self.storage.insert(key, bytes).await?;
+        }
+
+        Ok(value)
     }
 }
 
-#[async_trait]
+fn is_finalized(status: &TransactionStatus) -> bool {
+    matches!(
+        status,
+        TransactionStatus::Success { .. } | TransactionStatus::Failure { .. }
+    )
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl<Client, Clk> CacheableRpcs for CachedClient<Client, Clk>
 where
     Clk: Clock + Send + Sync,
@@ -127,6 +253,114 @@ where
         Ok(fresh_parameters)
     }
 
+    async fn chain_info(&self) -> Result<ChainInfo> {
+        let ttl = self.cache_config.chain_info_ttl;
+        if ttl.is_zero() {
+            return self.client.chain_info().await;
+        }
+
+        {
+            let read_lock = self.cached_chain_info.read().await;
+            if let Some(entry) = read_lock.as_ref()
+                && !entry.is_stale(self.clock.now(), ttl)
+            {
+                return Ok(entry.value.clone());
+            }
+        }
+
+        let mut write_lock = self.cached_chain_info.write().await;
+
+        // because it could have been updated since we last checked
+        if let Some(entry) = write_lock.as_ref()
+            && !entry.is_stale(self.clock.now(), ttl)
+        {
+            return Ok(entry.value.clone());
+        }
+
+        let fresh_chain_info = self.client.chain_info().await?;
+        *write_lock = Some(Dated {
+            value: fresh_chain_info.clone(),
+            date: self.clock.now(),
+        });
+
+        Ok(fresh_chain_info)
+    }
+
+    async fn block(&self, id: &BlockId) -> Result<Option<Block>> {
+        let enabled = self.cache_config.immutable_data.blocks;
+        self.immutable(
+            enabled,
+            CacheEntry::Block(*id),
+            self.client.block(id),
+            |_| true,
+        )
+        .await
+    }
+
+    async fn block_by_height(&self, height: BlockHeight) -> Result<Option<Block>> {
+        let enabled = self.cache_config.immutable_data.blocks;
+        let fetch = self.client.block_by_height(height);
+        self.immutable(enabled, CacheEntry::BlockByHeight(height), fetch, |_| true)
+            .await
+    }
+
+    async fn transaction(&self, id: &TxId) -> Result<Option<TransactionResponse>> {
+        let enabled = self.cache_config.immutable_data.transactions;
+        self.immutable(
+            enabled,
+            CacheEntry::Transaction(*id),
+            self.client.transaction(id),
+            |response| is_finalized(&response.status),
+        )
+        .await
+    }
+
+    async fn transaction_status(&self, id: &TxId) -> Result<TransactionStatus> {
+        let enabled = self.cache_config.immutable_data.transactions;
+        let fetch = async { self.client.transaction_status(id).await.map(Some) };
+        let status = self
+            .immutable(
+                enabled,
+                CacheEntry::TransactionStatus(*id),
+                fetch,
+                is_finalized,
+            )
+            .await?;
+
+        Ok(status.expect("fetched statuses are always present"))
+    }
+
+    async fn blob(&self, id: BlobId) -> Result<Option<Blob>> {
+        let enabled = self.cache_config.immutable_data.blobs;
+        self.immutable(enabled, CacheEntry::Blob(id), self.client.blob(id), |_| {
+            true
+        })
+        .await
+    }
+
+    async fn contract_exists(&self, id: &ContractId) -> Result<bool> {
+        let enabled = self.cache_config.immutable_data.contracts;
+        let fetch = async { self.client.contract_exists(id).await.map(Some) };
+        let exists = self
+            .immutable(enabled, CacheEntry::ContractExists(*id), fetch, |exists| {
+                *exists
+            })
+            .await?;
+
+        Ok(exists.unwrap_or_default())
+    }
+
+    async fn contract(&self, id: &ContractId) -> Result<Option<Contract>> {
+        let enabled = self.cache_config.immutable_data.contracts;
+        self.immutable(
+            enabled,
+            CacheEntry::Contract(*id),
+            self.client.contract(id),
+            |_| true,
+        )
+        .await
+    }
+
     async fn node_info(&self) -> Result<NodeInfo> {
         // must borrow from consensus_parameters to keep the change non-breaking
         let ttl = self.ttl_config.consensus_parameters;
@@ -420,4 +654,95 @@ mod tests {
         assert_eq!(first_call, original_node_info);
         assert_eq!(second_call, changed_node_info);
     }
+
+    #[tokio::test]
+    async fn finalized_transaction_statuses_are_stored() {
+        // given
+        let submitted = TransactionStatus::Submitted {
+            submitted_at: tai64::Tai64(1),
+        };
+        let failed = TransactionStatus::Failure {
+            block_height: 1.into(),
+            time: tai64::Tai64(2),
+            total_gas: 0,
+            total_fee: 0,
+            reason: "Revert(0)".to_string(),
+            program_state: None,
+            receipts: vec![],
+        };
+
+        let api = {
+            let mut api = MockCacheableRpcs::new();
+            let statuses = Mutex::new(vec![failed.clone(), submitted.clone()]);
+            api.expect_transaction_status()
+                .times(2)
+                .returning(move |_| Ok(statuses.lock().unwrap().pop().unwrap()));
+            api.expect_consensus_parameters()
+                .once()
+                .return_once(|| Ok(ConsensusParameters::default()));
+            api
+        };
+        let mut sut = CachedClient::new(api, TtlConfig::default(), TestClock::default());
+        sut.set_cache_config(CacheConfig {
+            immutable_data: CachedData::ALL,
+            ..Default::default()
+        });
+        let tx_id = TxId::zeroed();
+
+        // when
+        let statuses = [
+            sut.transaction_status(&tx_id).await.unwrap(),
+            sut.transaction_status(&tx_id).await.unwrap(),
+            sut.transaction_status(&tx_id).await.unwrap(),
+        ];
+
+        // then
+        // mock validates that the final status was fetched only once
+        assert_eq!(statuses, [submitted, failed.clone(), failed]);
+    }
+
+    #[tokio::test]
+    async fn immutable_data_is_only_stored_if_enabled() {
+        // given
+        let api = {
+            let mut api = MockCacheableRpcs::new();
+            api.expect_contract_exists()
+                .times(4)
+                .returning(|id| Ok(*id == ContractId::zeroed()));
+            api.expect_consensus_parameters()
+                .once()
+                .return_once(|| Ok(ConsensusParameters::default()));
+            api
+        };
+        let mut sut = CachedClient::new(api, TtlConfig::default(), TestClock::default());
+        sut.set_cache_config(CacheConfig {
+            immutable_data: CachedData::ALL,
+            ..Default::default()
+        });
+        let missing = ContractId::from([1; 32]);
+
+        // when
+        let deployed = sut.contract_exists(&ContractId::zeroed()).await.unwrap();
+        let deployed_again = sut.contract_exists(&ContractId::zeroed()).await.unwrap();
+        let not_deployed = sut.contract_exists(&missing).await.unwrap();
+        let not_deployed_again = sut.contract_exists(&missing).await.unwrap();
+
+        sut.set_cache_config(CacheConfig::default());
+        sut.set_storage(Arc::new(InMemoryCacheStorage::default()));
+        let fetched = sut.contract_exists(&ContractId::zeroed()).await.unwrap();
+        let stored = sut
+            .storage
+            .get(&CacheKey {
+                chain_id: ChainId::default(),
+                entry: CacheEntry::ContractExists(ContractId::zeroed()),
+            })
+            .await
+            .unwrap();
+
+        // then
+        // mock validates that only deployed contracts were stored
+        assert!(deployed && deployed_again && fetched);
+        assert!(!not_deployed && !not_deployed_again);
+        assert!(stored.is_none());
+    }
 }
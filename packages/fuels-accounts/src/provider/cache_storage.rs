@@ -0,0 +1,363 @@
+//! Storage for the immutable data cached by a [`Provider`](super::Provider).
+//!
+//! Values are stored as bytes so that a storage can keep them anywhere, e.g. on disk to be
+//! reused across restarts. They are encoded with `postcard` by the provider.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Debug, Display},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use fuel_core_client::client::types::{
+    Blob, Block, Contract, TransactionResponse, TransactionStatus,
+    block::{Consensus, Genesis, Header, PoAConsensus},
+    primitives::{BlockId, Hash, MerkleRoot, PublicKey, Signature, TransactionId},
+};
+use fuel_tx::{BlobId, TxId};
+use fuel_types::{BlockHeight, ChainId, ContractId, Salt};
+use fuels_core::types::errors::Result;
+use serde::{Deserialize, Serialize, Serializer};
+use tai64::Tai64;
+
+/// Identifies an immutable value cached by the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// The chain the value belongs to, so that providers of different chains can share a
+    /// storage.
+    pub chain_id: ChainId,
+    pub entry: CacheEntry,
+}
+
+/// Formats the key as a path, e.g. `0/block_by_height/42`, usable as a file name by storages
+/// keeping the values on disk.
+impl Display for CacheKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", *self.chain_id, self.entry)
+    }
+}
+
+/// The value a [`CacheKey`] identifies on its chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheEntry {
+    Block(BlockId),
+    BlockByHeight(BlockHeight),
+    /// A transaction that succeeded or failed.
+    Transaction(TxId),
+    /// The final status of a transaction, including its receipts.
+    TransactionStatus(TxId),
+    Blob(BlobId),
+    /// Only set for deployed contracts.
+    ContractExists(ContractId),
+    Contract(ContractId),
+}
+
+impl Display for CacheEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Block(id) => write!(f, "block/{id}"),
+            Self::BlockByHeight(height) => write!(f, "block_by_height/{}", **height),
+            Self::Transaction(id) => write!(f, "transaction/{id}"),
+            Self::TransactionStatus(id) => write!(f, "transaction_status/{id}"),
+            Self::Blob(id) => write!(f, "blob/{id}"),
+            Self::ContractExists(id) => write!(f, "contract_exists/{id}"),
+            Self::Contract(id) => write!(f, "contract/{id}"),
+        }
+    }
+}
+
+/// Keeps the immutable data cached by a provider. Entries never go stale, so a storage is free
+/// to keep them for as long as it wants.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait CacheStorage: Debug + Send + Sync {
+    async fn get(&self, key: &CacheKey) -> Result<Option<Vec<u8>>>;
+
+    async fn insert(&self, key: CacheKey, value: Vec<u8>) -> Result<()>;
+}
+
+/// Keeps up to `capacity` entries in memory, evicting the oldest ones first.
+#[derive(Debug)]
+pub struct InMemoryCacheStorage {
+    capacity: usize,
+    entries: Mutex<InMemoryEntries>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryEntries {
+    values: HashMap<CacheKey, Vec<u8>>,
+    insertion_order: VecDeque<CacheKey>,
+}
+
+impl InMemoryCacheStorage {
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Default::default(),
+        }
+    }
+}
+
+impl Default for InMemoryCacheStorage {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl CacheStorage for InMemoryCacheStorage {
+    async fn get(&self, key: &CacheKey) -> Result<Option<Vec<u8>>> {
+        let entries = self.entries.lock().expect("not poisoned");
+
+        Ok(entries.values.get(key).cloned())
+    }
+
+    async fn insert(&self, key: CacheKey, value: Vec<u8>) -> Result<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+
+        let mut entries = self.entries.lock().expect("not poisoned");
+        if entries.values.insert(key, value).is_none() {
+            entries.insertion_order.push_back(key);
+        }
+
+        while entries.insertion_order.len() > self.capacity {
+            let oldest = entries
+                .insertion_order
+                .pop_front()
+                .expect("more entries than the capacity");
+            entries.values.remove(&oldest);
+        }
+
+        Ok(())
+    }
+}
+
+/// A value that can be kept in a [`CacheStorage`].
+pub(crate) trait Stored: Sized {
+    fn encode(&self) -> postcard::Result<Vec<u8>>;
+
+    fn decode(bytes: &[u8]) -> postcard::Result<Self>;
+}
+
+macro_rules! stored_with_serde {
+    ($($ty:ty),* $(,)?) => {$(
+        impl Stored for $ty {
+            fn encode(&self) -> postcard::Result<Vec<u8>> {
+                postcard::to_allocvec(self)
+            }
+
+            fn decode(bytes: &[u8]) -> postcard::Result<Self> {
+                postcard::from_bytes(bytes)
+            }
+        }
+    )*};
+}
+
+stored_with_serde!(bool, TransactionResponse, TransactionStatus);
+
+/// For the types of `fuel-core-client` not implementing serde, through a remote definition.
+macro_rules! stored_with_remote {
+    ($($ty:ty => $def:ty),* $(,)?) => {$(
+        impl Stored for $ty {
+            fn encode(&self) -> postcard::Result<Vec<u8>> {
+                struct Encode<'a>(&'a $ty);
+
+                impl Serialize for Encode<'_> {
+                    fn serialize<S: Serializer>(
+                        &self,
+                        serializer: S,
+                    ) -> std::result::Result<S::Ok, S::Error> {
+                        <$def>::serialize(self.0, serializer)
+                    }
+                }
+
+                postcard::to_allocvec(&Encode(self))
+            }
+
+            fn decode(bytes: &[u8]) -> postcard::Result<Self> {
+                <$def>::deserialize(&mut postcard::Deserializer::from_bytes(bytes))
+            }
+        }
+    )*};
+}
+
+stored_with_remote!(Block => BlockDef, Blob => BlobDef, Contract => ContractDef);
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Block")]
+struct BlockDef {
+    id: BlockId,
+    #[serde(with = "HeaderDef")]
+    header: Header,
+    #[serde(with = "ConsensusDef")]
+    consensus: Consensus,
+    transactions: Vec<TransactionId>,
+    block_producer: Option<PublicKey>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Header")]
+struct HeaderDef {
+    id: BlockId,
+    da_height: u64,
+    consensus_parameters_version: u32,
+    state_transition_bytecode_version: u32,
+    transactions_count: u16,
+    message_receipt_count: u32,
+    transactions_root: MerkleRoot,
+    message_outbox_root: MerkleRoot,
+    event_inbox_root: MerkleRoot,
+    height: u32,
+    prev_root: MerkleRoot,
+    time: Tai64,
+    application_hash: Hash,
+    #[cfg(feature = "fault-proving")]
+    tx_id_commitment: Option<Hash>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Consensus")]
+enum ConsensusDef {
+    Genesis(#[serde(with = "GenesisDef")] Genesis),
+    PoAConsensus(#[serde(with = "PoAConsensusDef")] PoAConsensus),
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Genesis")]
+struct GenesisDef {
+    chain_config_hash: Hash,
+    coins_root: MerkleRoot,
+    contracts_root: MerkleRoot,
+    messages_root: MerkleRoot,
+    transactions_root: MerkleRoot,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PoAConsensus")]
+struct PoAConsensusDef {
+    signature: Signature,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Blob")]
+struct BlobDef {
+    id: BlobId,
+    bytecode: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Contract")]
+struct ContractDef {
+    id: ContractId,
+    bytecode: Vec<u8>,
+    salt: Salt,
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_core_client::client::types::TransactionType;
+    use fuel_tx::{Receipt, Transaction};
+
+    use super::*;
+
+    fn block() -> Block {
+        Block {
+            id: [1; 32].into(),
+            header: Header {
+                id: [1; 32].into(),
+                da_height: 2,
+                consensus_parameters_version: 3,
+                state_transition_bytecode_version: 4,
+                transactions_count: 1,
+                message_receipt_count: 0,
+                transactions_root: [5; 32].into(),
+                message_outbox_root: [6; 32].into(),
+                event_inbox_root: [7; 32].into(),
+                height: 42,
+                prev_root: [8; 32].into(),
+                time: Tai64(1_000),
+                application_hash: [9; 32].into(),
+                #[cfg(feature = "fault-proving")]
+                tx_id_commitment: None,
+            },
+            consensus: Consensus::PoAConsensus(PoAConsensus {
+                signature: Signature::from_bytes([10; 64]),
+            }),
+            transactions: vec![[11; 32].into()],
+            block_producer: None,
+        }
+    }
+
+    #[test]
+    fn keys_are_namespaced_by_chain() {
+        let key = |chain_id: u64| CacheKey {
+            chain_id: ChainId::new(chain_id),
+            entry: CacheEntry::BlockByHeight(42.into()),
+        };
+
+        assert_eq!(key(0).to_string(), "0/block_by_height/42");
+        assert_ne!(key(0), key(1));
+    }
+
+    #[test]
+    fn cached_values_round_trip() {
+        let block = block();
+        assert_eq!(Block::decode(&block.encode().unwrap()).unwrap(), block);
+
+        let contract = Contract {
+            id: [1; 32].into(),
+            bytecode: vec![1, 2, 3],
+            salt: [2; 32].into(),
+        };
+        let decoded = Contract::decode(&contract.encode().unwrap()).unwrap();
+        assert_eq!(
+            (decoded.id, decoded.bytecode, decoded.salt),
+            (contract.id, contract.bytecode, contract.salt)
+        );
+
+        let response = TransactionResponse {
+            transaction: TransactionType::Known(Transaction::default()),
+            status: TransactionStatus::Failure {
+                block_height: 42.into(),
+                time: Tai64(1_000),
+                reason: "Revert(0)".to_string(),
+                program_state: None,
+                receipts: vec![Receipt::revert(ContractId::zeroed(), 0, 1, 2)],
+                total_gas: 10,
+                total_fee: 1,
+            },
+        };
+        assert_eq!(
+            TransactionResponse::decode(&response.encode().unwrap()).unwrap(),
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_storage_evicts_the_oldest_entries() -> Result<()> {
+        // given
+        let storage = InMemoryCacheStorage::new(2);
+        let key = |height: u32| CacheKey {
+            chain_id: ChainId::default(),
+            entry: CacheEntry::BlockByHeight(height.into()),
+        };
+
+        // when
+        for height in 1..=3u32 {
+            storage.insert(key(height), vec![height as u8]).await?;
+        }
+
+        // then
+        assert_eq!(storage.get(&key(1)).await?, None);
+        assert_eq!(storage.get(&key(3)).await?, Some(vec![3]));
+
+        Ok(())
+    }
+}
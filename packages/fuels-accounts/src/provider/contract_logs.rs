@@ -0,0 +1,177 @@
+use fuel_tx::{Receipt, TxId};
+use fuels_core::{
+    codec::LogDecoder,
+    traits::{Parameterize, Tokenizable},
+    types::{
+        ContractId,
+        block::Block,
+        errors::Result,
+        transaction::{Transaction, TransactionType},
+        tx_status::TxStatus,
+    },
+};
+use futures::{Stream, TryStreamExt, stream};
+
+use crate::provider::Provider;
+
+/// The logs of type `T` emitted by a contract in a single block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractLogs<T> {
+    /// Height of the block the logs were found in. Streaming can be resumed from
+    /// `block_height + 1` once these logs have been processed.
+    pub block_height: u32,
+    /// The decoded logs, in the order they were emitted, together with the id of the
+    /// transaction that emitted them.
+    pub logs: Vec<(TxId, T)>,
+}
+
+struct ContractLogsQuery<'a> {
+    provider: &'a Provider,
+    contract_id: ContractId,
+    log_decoder: LogDecoder,
+}
+
+impl ContractLogsQuery<'_> {
+    async fn logs_in_block<T>(&self, block: &Block) -> Result<Vec<(TxId, T)>>
+    where
+        T: Tokenizable + Parameterize + 'static,
+    {
+        let mut logs = vec![];
+        for tx_id in &block.transactions {
+            let tx_id = TxId::from(*tx_id);
+            for log in self.logs_in_tx::<T>(&tx_id).await? {
+                logs.push((tx_id, log));
+            }
+        }
+
+        Ok(logs)
+    }
+
+    async fn logs_in_tx<T>(&self, tx_id: &TxId) -> Result<Vec<T>>
+    where
+        T: Tokenizable + Parameterize + 'static,
+    {
+        let Some(response) = self.provider.get_transaction_by_id(tx_id).await? else {
+            return Ok(vec![]);
+        };
+
+        // Only scripts can call contracts and they must declare them as inputs
+        let touches_contract = match &response.transaction {
+            TransactionType::Script(tx) => tx
+                .inputs()
+                .iter()
+                .any(|input| input.contract_id() == Some(&self.contract_id)),
+            _ => false,
+        };
+
+        match response.status {
+            TxStatus::Success(success) if touches_contract => {
+                decode_contract_logs(&self.log_decoder, &self.contract_id, &success.receipts)
+            }
+            _ => Ok(vec![]),
+        }
+    }
+}
+
+fn decode_contract_logs<T>(
+    log_decoder: &LogDecoder,
+    contract_id: &ContractId,
+    receipts: &[Receipt],
+) -> Result<Vec<T>>
+where
+    T: Tokenizable + Parameterize + 'static,
+{
+    let contract_receipts = receipts
+        .iter()
+        .filter(|receipt| match receipt {
+            Receipt::Log { id, .. } | Receipt::LogData { id, .. } => id == contract_id,
+            _ => false,
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    log_decoder.decode_logs_with_type::<T>(&contract_receipts)
+}
+
+impl Provider {
+    /// Returns a stream of the logs of type `T` emitted by `contract_id`, starting at block
+    /// `from_height` and following the chain as new blocks are produced.
+    ///
+    /// Blocks are taken from [`Provider::subscribe_blocks`] and only those containing at least
+    /// one matching log are yielded. The `log_decoder` is usually taken from the `abigen!`
+    /// generated contract instance via `log_decoder()`.
+    ///
+    /// The stream ends after the first error. Since every item carries the height of its block,
+    /// a new stream can be started from `block_height + 1` of the last processed item.
+    pub fn stream_contract_logs<T>(
+        &self,
+        contract_id: ContractId,
+        log_decoder: LogDecoder,
+        from_height: u32,
+    ) -> impl Stream<Item = Result<ContractLogs<T>>> + '_
+    where
+        T: Tokenizable + Parameterize + 'static,
+    {
+        let blocks = Box::pin(self.subscribe_blocks(from_height));
+        let query = ContractLogsQuery {
+            provider: self,
+            contract_id,
+            log_decoder,
+        };
+
+        stream::try_unfold((blocks, query), |(mut blocks, query)| async move {
+            while let Some(block) = blocks.try_next().await? {
+                let logs = query.logs_in_block::<T>(&block).await?;
+
+                if !logs.is_empty() {
+                    let contract_logs = ContractLogs {
+                        block_height: block.header.height,
+                        logs,
+                    };
+
+                    return Ok(Some((contract_logs, (blocks, query))));
+                }
+            }
+
+            Ok(None)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels_core::{
+        codec::{LogFormatter, log_formatters_lookup},
+        types::errors::Result,
+    };
+
+    use super::*;
+
+    #[test]
+    fn only_logs_of_the_given_contract_are_decoded() -> Result<()> {
+        let contract_id = ContractId::from([1; 32]);
+        let other_contract_id = ContractId::from([2; 32]);
+
+        let mut log_formatters = log_formatters_lookup(
+            vec![("0".to_string(), LogFormatter::new_log::<u64>())],
+            contract_id,
+        );
+        log_formatters.extend(log_formatters_lookup(
+            vec![("0".to_string(), LogFormatter::new_log::<u64>())],
+            other_contract_id,
+        ));
+        let log_decoder = LogDecoder::new(log_formatters, Default::default());
+
+        let receipts = [
+            Receipt::log(contract_id, 7, 0, 0, 0, 0, 0),
+            Receipt::log(other_contract_id, 8, 0, 0, 0, 0, 0),
+            Receipt::log(contract_id, 9, 0, 0, 0, 0, 0),
+        ];
+
+        let logs = decode_contract_logs::<u64>(&log_decoder, &contract_id, &receipts)?;
+
+        assert_eq!(logs, vec![7, 9]);
+
+        Ok(())
+    }
+}
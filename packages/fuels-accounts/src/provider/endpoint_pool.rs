@@ -0,0 +1,403 @@
+//! Load balancing and failover across the nodes of a provider connected through
+//! [`Provider::connect_with_pool`](super::Provider::connect_with_pool).
+//!
+//! Every attempt of a request is routed to one of the healthy endpoints. An endpoint that cannot
+//! be reached or fails to answer a request is marked unhealthy, so that a retried attempt goes to
+//! another one, until the next background health check finds it healthy again. Errors the node
+//! answers with, such as a rejected transaction, say nothing about its health.
+
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    io,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use fuel_core_client::client::FuelClient;
+use fuel_tx::TxId;
+use fuels_core::types::errors::{Result, error};
+
+use super::{
+    node_api::{NodeApi, NodeError, TransportClient},
+    transport::HttpTransport,
+};
+use crate::runtime::{self, Instant};
+
+/// How many submitted transactions are remembered to route their status requests.
+const MAX_PINNED_TXS: usize = 1024;
+
+/// Whether `err` means that the endpoint could not be reached or did not answer properly, as
+/// opposed to the node answering with an error about the request itself.
+pub(crate) fn is_endpoint_failure(err: &io::Error) -> bool {
+    // e.g. an unknown transaction or a response that doesn't convert into the SDK types
+    if matches!(
+        err.kind(),
+        io::ErrorKind::NotFound | io::ErrorKind::InvalidData | io::ErrorKind::InvalidInput
+    ) {
+        return false;
+    }
+
+    !NodeError::is_node_error(err)
+}
+
+/// How a provider picks the endpoint serving a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Routing {
+    /// Cycles through the healthy endpoints.
+    #[default]
+    RoundRobin,
+    /// Prefers the healthy endpoint with the lowest average latency. Endpoints not measured yet
+    /// are tried first.
+    LowestLatency,
+}
+
+// ANCHOR: endpoint_pool_config
+#[derive(Debug, Clone)]
+pub struct EndpointPoolConfig {
+    pub routing: Routing,
+    /// How often every endpoint is checked through its `health` query.
+    pub health_check_interval: Duration,
+    /// How long a health check may take before the endpoint is considered unhealthy.
+    pub health_check_timeout: Duration,
+}
+// ANCHOR_END: endpoint_pool_config
+
+impl Default for EndpointPoolConfig {
+    fn default() -> Self {
+        Self {
+            routing: Routing::default(),
+            health_check_interval: Duration::from_secs(5),
+            health_check_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// The state of an endpoint of the pool, as last observed by the provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    pub url: String,
+    pub healthy: bool,
+    /// Average latency of the health checks and requests served by the endpoint.
+    pub latency: Option<Duration>,
+}
+
+struct Endpoint {
+    url: String,
+    api: Box<dyn NodeApi>,
+    healthy: AtomicBool,
+    latency: Mutex<Option<Duration>>,
+}
+
+impl Debug for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Endpoint")
+            .field("url", &self.url)
+            .field("healthy", &self.healthy)
+            .field("latency", &self.latency)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Endpoint {
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    fn latency(&self) -> Option<Duration> {
+        *self.latency.lock().expect("not poisoned")
+    }
+
+    /// Keeps an exponential moving average, each sample weighing a fifth.
+    fn record_latency(&self, sample: Duration) {
+        let mut latency = self.latency.lock().expect("not poisoned");
+        *latency = Some(match *latency {
+            Some(average) => (average * 4 + sample) / 5,
+            None => sample,
+        });
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct EndpointPool {
+    endpoints: Vec<Endpoint>,
+    routing: Routing,
+    next: AtomicUsize,
+    pinned: Mutex<VecDeque<(TxId, usize)>>,
+}
+
+impl EndpointPool {
+    pub(crate) fn connect(urls: &[impl AsRef<str>], routing: Routing) -> Result<Self> {
+        let endpoints = urls
+            .iter()
+            .map(|url| {
+                let client = FuelClient::new(url).map_err(|e| error!(Provider, "{e}"))?;
+                let transport =
+                    HttpTransport::new(url.as_ref()).map_err(|e| error!(Provider, "{e}"))?;
+                // decodes the errors the node answered with into a `NodeError`
+                let api: Box<dyn NodeApi> =
+                    Box::new(TransportClient::new(Arc::new(transport), client));
+
+                Ok((url.as_ref().to_string(), api))
+            })
+            .collect::<Result<_>>()?;
+
+        Self::new(endpoints, routing)
+    }
+
+    fn new(endpoints: Vec<(String, Box<dyn NodeApi>)>, routing: Routing) -> Result<Self> {
+        if endpoints.is_empty() {
+            return Err(error!(Provider, "an endpoint pool needs at least one url"));
+        }
+
+        let endpoints = endpoints
+            .into_iter()
+            .map(|(url, api)| Endpoint {
+                url,
+                api,
+                healthy: AtomicBool::new(true),
+                latency: Mutex::new(None),
+            })
+            .collect();
+
+        Ok(Self {
+            endpoints,
+            routing,
+            next: AtomicUsize::new(0),
+            pinned: Default::default(),
+        })
+    }
+
+    pub(crate) fn url(&self, idx: usize) -> &str {
+        &self.endpoints[idx].url
+    }
+
+    pub(crate) fn api(&self, idx: usize) -> &dyn NodeApi {
+        self.endpoints[idx].api.as_ref()
+    }
+
+    pub(crate) fn statuses(&self) -> Vec<EndpointStatus> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| EndpointStatus {
+                url: endpoint.url.clone(),
+                healthy: endpoint.is_healthy(),
+                latency: endpoint.latency(),
+            })
+            .collect()
+    }
+
+    /// Picks the endpoint for the next attempt of a request. Requests about a transaction
+    /// submitted through the pool go to the endpoint that received it, as long as it is healthy.
+    ///
+    /// If no endpoint is healthy, all of them are cycled through.
+    pub(crate) fn pick(&self, about_tx: Option<&TxId>) -> usize {
+        if let Some(idx) = about_tx.and_then(|tx_id| self.pinned_endpoint(tx_id))
+            && self.endpoints[idx].is_healthy()
+        {
+            return idx;
+        }
+
+        let healthy: Vec<_> = (0..self.endpoints.len())
+            .filter(|&idx| self.endpoints[idx].is_healthy())
+            .collect();
+        if healthy.is_empty() {
+            return self.next.fetch_add(1, Ordering::Relaxed) % self.endpoints.len();
+        }
+
+        match self.routing {
+            Routing::RoundRobin => {
+                healthy[self.next.fetch_add(1, Ordering::Relaxed) % healthy.len()]
+            }
+            Routing::LowestLatency => healthy
+                .into_iter()
+                .min_by_key(|&idx| self.endpoints[idx].latency())
+                .expect("at least one healthy endpoint"),
+        }
+    }
+
+    /// Records the outcome of an attempt served by the endpoint at `idx`, `answered` being whether
+    /// the node answered it, successfully or not.
+    pub(crate) fn report(&self, idx: usize, answered: bool, elapsed: Duration) {
+        let endpoint = &self.endpoints[idx];
+        if answered {
+            endpoint.record_latency(elapsed);
+        } else {
+            endpoint.healthy.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Routes the status requests of `tx_id` to the endpoint at `idx`.
+    pub(crate) fn pin(&self, tx_id: TxId, idx: usize) {
+        let mut pinned = self.pinned.lock().expect("not poisoned");
+        pinned.retain(|(pinned_id, _)| *pinned_id != tx_id);
+        if pinned.len() == MAX_PINNED_TXS {
+            pinned.pop_front();
+        }
+        pinned.push_back((tx_id, idx));
+    }
+
+    fn pinned_endpoint(&self, tx_id: &TxId) -> Option<usize> {
+        self.pinned
+            .lock()
+            .expect("not poisoned")
+            .iter()
+            .find_map(|(pinned_id, idx)| (pinned_id == tx_id).then_some(*idx))
+    }
+
+    pub(crate) async fn check_health(&self, timeout: Duration) {
+        let checks = self.endpoints.iter().map(|endpoint| async move {
+            let started = Instant::now();
+            let healthy = matches!(
+                runtime::timeout(timeout, endpoint.api.health()).await,
+                Some(Ok(true))
+            );
+
+            endpoint.healthy.store(healthy, Ordering::Relaxed);
+            if healthy {
+                endpoint.record_latency(started.elapsed());
+            }
+        });
+
+        futures::future::join_all(checks).await;
+    }
+
+    /// Checks the health of the endpoints every `interval` until the pool is dropped.
+    pub(crate) fn spawn_health_checks(pool: &Arc<Self>, config: &EndpointPoolConfig) {
+        let pool = Arc::downgrade(pool);
+        let (interval, timeout) = (config.health_check_interval, config.health_check_timeout);
+
+        // the pool was just checked when connecting
+        runtime::spawn(async move {
+            loop {
+                runtime::sleep(interval).await;
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                pool.check_health(timeout).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::provider::transport::{RecordedExchange, RecordedTransport, Recording};
+
+    fn endpoint(url: &str, healthy: bool) -> (String, Box<dyn NodeApi>) {
+        endpoint_answering(url, json!({"data": {"health": healthy}}))
+    }
+
+    fn endpoint_answering(url: &str, health_response: Value) -> (String, Box<dyn NodeApi>) {
+        let transport = RecordedTransport::new(Recording {
+            url: url.to_string(),
+            exchanges: vec![RecordedExchange::new(
+                "Health",
+                Value::Null,
+                health_response,
+            )],
+        });
+
+        (
+            url.to_string(),
+            Box::new(TransportClient::new(
+                Arc::new(transport),
+                FuelClient::new(url).unwrap(),
+            )),
+        )
+    }
+
+    fn pool(healthy: &[bool], routing: Routing) -> EndpointPool {
+        let endpoints = healthy
+            .iter()
+            .enumerate()
+            .map(|(idx, &healthy)| endpoint(&format!("http://node-{idx}"), healthy))
+            .collect();
+
+        EndpointPool::new(endpoints, routing).unwrap()
+    }
+
+    #[tokio::test]
+    async fn round_robin_skips_unhealthy_endpoints() {
+        // given
+        let pool = pool(&[true, false, true], Routing::RoundRobin);
+
+        // when
+        pool.check_health(Duration::from_secs(1)).await;
+
+        // then
+        let picked: Vec<_> = (0..4).map(|_| pool.pick(None)).collect();
+        assert_eq!(picked, [0, 2, 0, 2]);
+        assert!(!pool.statuses()[1].healthy);
+        assert!(pool.statuses()[0].latency.is_some());
+    }
+
+    #[test]
+    fn failed_endpoints_are_avoided_until_no_endpoint_is_healthy() {
+        let pool = pool(&[true, true], Routing::LowestLatency);
+        pool.report(0, true, Duration::from_millis(10));
+        pool.report(1, true, Duration::from_millis(50));
+        assert_eq!(pool.pick(None), 0);
+
+        pool.report(0, false, Duration::ZERO);
+        assert_eq!(pool.pick(None), 1);
+
+        pool.report(1, false, Duration::ZERO);
+        let picked: Vec<_> = (0..2).map(|_| pool.pick(None)).collect();
+        assert_eq!(picked, [0, 1]);
+    }
+
+    #[test]
+    fn transaction_requests_go_to_the_endpoint_that_received_it() {
+        let pool = pool(&[true, true, true], Routing::RoundRobin);
+        let tx_id = TxId::from([1; 32]);
+        pool.pin(tx_id, 2);
+
+        assert!((0..3).all(|_| pool.pick(Some(&tx_id)) == 2));
+        assert_eq!(pool.pick(Some(&TxId::from([2; 32]))), 0);
+
+        pool.report(2, false, Duration::ZERO);
+        assert_ne!(pool.pick(Some(&tx_id)), 2);
+    }
+
+    #[tokio::test]
+    async fn only_failures_to_answer_are_endpoint_failures() {
+        async fn health_error(response: Value) -> io::Error {
+            let (_, api) = endpoint_answering("http://node-0", response);
+
+            api.health().await.unwrap_err()
+        }
+
+        let unreachable =
+            EndpointPool::connect(&["http://127.0.0.1:1"], Routing::RoundRobin).expect("valid url");
+
+        let answered = [
+            health_error(json!({"errors": [{"message": "transaction is already in the pool"}]}))
+                .await,
+            health_error(json!({
+                "data": null,
+                "extensions": {"fuel_block_height_precondition_failed": true}
+            }))
+            .await,
+            io::Error::new(io::ErrorKind::NotFound, "transaction not found"),
+            io::Error::new(io::ErrorKind::InvalidData, "invalid transaction status"),
+        ];
+        let failed = [
+            unreachable.api(0).health().await.unwrap_err(),
+            // the wording of an error doesn't make it one the node answered with
+            io::Error::other("Response errors; transaction is already in the pool"),
+            io::Error::other("error sending request for url (http://node-0/v1/graphql)"),
+            io::Error::new(io::ErrorKind::TimedOut, "request timed out"),
+            io::Error::new(io::ErrorKind::ConnectionRefused, "connection refused"),
+        ];
+
+        assert!(!answered.iter().any(is_endpoint_failure));
+        assert!(failed.iter().all(is_endpoint_failure));
+    }
+}
@@ -0,0 +1,297 @@
+//! Interception of the RPCs made by a [`Provider`](super::Provider).
+//!
+//! Middleware runs inside the retry layer, once per attempt, so an error it returns is retried
+//! like any other failed request. Values served from the provider cache never reach it.
+
+use std::{fmt::Debug, future::Future, io, sync::Arc};
+
+use super::transport::GraphQlRequest;
+use crate::runtime::{self, BoxFuture, MaybeSend};
+use async_trait::async_trait;
+
+/// An RPC about to be made by the provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcCall {
+    method: &'static str,
+    request: Option<GraphQlRequest>,
+}
+
+impl RpcCall {
+    pub(crate) fn new(method: &'static str) -> Self {
+        Self {
+            method,
+            request: None,
+        }
+    }
+
+    pub(crate) fn with_request(mut self, request: GraphQlRequest) -> Self {
+        self.request = Some(request);
+        self
+    }
+
+    /// The name of the RPC, e.g. `submit` or `dry_run_opt`.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The GraphQL request sent for the RPC.
+    ///
+    /// Only known to providers connected through a custom
+    /// [`GraphQlTransport`](super::transport::GraphQlTransport). The default transport of
+    /// `fuel-core-client` builds its requests internally.
+    pub fn request(&self) -> Option<&GraphQlRequest> {
+        self.request.as_ref()
+    }
+
+    /// The GraphQL request sent for the RPC, e.g. to add headers to it before running
+    /// [`Next`].
+    pub fn request_mut(&mut self) -> Option<&mut GraphQlRequest> {
+        self.request.as_mut()
+    }
+
+    pub(crate) fn into_request(self) -> Option<GraphQlRequest> {
+        self.request
+    }
+}
+
+/// Intercepts every RPC made by a provider, e.g. to log, measure, rate limit or fail requests.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{io, time::Instant};
+///
+/// use async_trait::async_trait;
+/// use fuels_accounts::provider::{Next, ProviderMiddleware, RpcCall};
+///
+/// #[derive(Debug)]
+/// struct Timing;
+///
+/// #[async_trait]
+/// impl ProviderMiddleware for Timing {
+///     async fn handle(&self, rpc: RpcCall, next: Next<'_>) -> io::Result<()> {
+///         let method = rpc.method();
+///         let start = Instant::now();
+///         let result = next.run(rpc).await;
+///         println!("{method} took {:?}", start.elapsed());
+///
+///         result
+///     }
+/// }
+/// ```
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ProviderMiddleware: Debug + Send + Sync {
+    /// Handles `rpc`, usually by running the `next` middleware with it and returning its result.
+    ///
+    /// Returning without running `next` fails the attempt: with the returned error, or with an
+    /// error reporting the request as dropped if it is `Ok`.
+    async fn handle(&self, rpc: RpcCall, next: Next<'_>) -> io::Result<()>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type Request<'a> = Box<dyn FnOnce(RpcCall) -> BoxFuture<'a, io::Result<()>> + Send + 'a>;
+#[cfg(target_arch = "wasm32")]
+type Request<'a> = Box<dyn FnOnce(RpcCall) -> BoxFuture<'a, io::Result<()>> + 'a>;
+
+/// The rest of the middleware stack, ending with the request itself.
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn ProviderMiddleware>],
+    request: Request<'a>,
+}
+
+impl Next<'_> {
+    /// Runs the rest of the stack with `rpc`, as changed by the current middleware.
+    pub async fn run(self, rpc: RpcCall) -> io::Result<()> {
+        match self.middleware.split_first() {
+            Some((current, rest)) => {
+                let next = Next {
+                    middleware: rest,
+                    request: self.request,
+                };
+
+                current.handle(rpc, next).await
+            }
+            None => (self.request)(rpc).await,
+        }
+    }
+}
+
+/// Runs `request` through `middleware`, the first one being the outermost. The request is made
+/// with the call as changed by the middleware.
+pub(crate) async fn intercept<T, Fut>(
+    middleware: &[Arc<dyn ProviderMiddleware>],
+    rpc: RpcCall,
+    request: impl FnOnce(RpcCall) -> Fut + MaybeSend,
+) -> io::Result<T>
+where
+    T: MaybeSend,
+    Fut: Future<Output = io::Result<T>> + MaybeSend,
+{
+    if middleware.is_empty() {
+        return request(rpc).await;
+    }
+
+    let method = rpc.method();
+    let mut response = None;
+    let next = Next {
+        middleware,
+        request: Box::new(|rpc| {
+            runtime::boxed_future(async {
+                response = Some(request(rpc).await?);
+                Ok(())
+            })
+        }),
+    };
+    next.run(rpc).await?;
+
+    response.ok_or_else(|| {
+        io::Error::other(format!(
+            "request `{method}` was dropped by the provider middleware"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use super::*;
+    use crate::provider::{Backoff, RetryConfig, retry_util};
+
+    #[derive(Debug, Default)]
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl ProviderMiddleware for Recorder {
+        async fn handle(&self, rpc: RpcCall, next: Next<'_>) -> io::Result<()> {
+            let method = rpc.method();
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} before {method}", self.name));
+            let result = next.run(rpc).await;
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} after {method}", self.name));
+
+            result
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct FailFirst {
+        failures_left: Mutex<usize>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl ProviderMiddleware for FailFirst {
+        async fn handle(&self, rpc: RpcCall, next: Next<'_>) -> io::Result<()> {
+            {
+                let mut failures_left = self.failures_left.lock().unwrap();
+                if *failures_left > 0 {
+                    *failures_left -= 1;
+                    return Err(io::Error::other("injected failure"));
+                }
+            }
+
+            next.run(rpc).await
+        }
+    }
+
+    #[derive(Debug)]
+    struct Swallow;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl ProviderMiddleware for Swallow {
+        async fn handle(&self, _rpc: RpcCall, _next: Next<'_>) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_runs_in_order_around_the_request() {
+        // given
+        let log = Arc::new(Mutex::new(vec![]));
+        let middleware: Vec<Arc<dyn ProviderMiddleware>> = vec![
+            Arc::new(Recorder {
+                name: "outer",
+                log: log.clone(),
+            }),
+            Arc::new(Recorder {
+                name: "inner",
+                log: log.clone(),
+            }),
+        ];
+
+        // when
+        let response = intercept(&middleware, RpcCall::new("node_info"), |_| async {
+            log.lock().unwrap().push("request".to_string());
+            Ok(42)
+        })
+        .await
+        .unwrap();
+
+        // then
+        assert_eq!(response, 42);
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "outer before node_info",
+                "inner before node_info",
+                "request",
+                "inner after node_info",
+                "outer after node_info",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn injected_failures_are_retried() {
+        // given
+        let middleware: Vec<Arc<dyn ProviderMiddleware>> = vec![Arc::new(FailFirst {
+            failures_left: Mutex::new(2),
+        })];
+        let requests = Mutex::new(0);
+        let retry_config = RetryConfig::new(3, Backoff::Fixed(Duration::ZERO)).unwrap();
+
+        // when
+        let response = retry_util::retry(
+            || {
+                intercept(&middleware, RpcCall::new("submit"), |_| async {
+                    *requests.lock().unwrap() += 1;
+                    Ok("tx_id")
+                })
+            },
+            &retry_config,
+            |result| result.is_err(),
+        )
+        .await
+        .unwrap();
+
+        // then
+        assert_eq!(response, "tx_id");
+        assert_eq!(*requests.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn dropped_requests_fail() {
+        let middleware: Vec<Arc<dyn ProviderMiddleware>> = vec![Arc::new(Swallow)];
+
+        let err = intercept(&middleware, RpcCall::new("health"), |_| async { Ok(true) })
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "request `health` was dropped by the provider middleware"
+        );
+    }
+}
@@ -0,0 +1,1493 @@
+use std::{fmt::Debug, io, sync::Arc};
+
+use async_trait::async_trait;
+use custom_queries::{
+    ContractExistsQuery, IsUserAccountQuery, IsUserAccountVariables, RecentTipsQuery,
+};
+use cynic::{MutationBuilder, Operation, QueryBuilder, QueryFragment, SubscriptionBuilder};
+use fuel_core_client::{
+    client::{
+        FuelClient,
+        pagination::{PaginatedResult, PaginationRequest},
+        schema::{
+            self, Bytes, ConnectionArgs, HexString, U32,
+            balance::{BalanceArgs, BalancesConnectionArgs},
+            blob::BlobByIdArgs,
+            block::{BlockByHeightArgs, BlockByIdArgs, ProduceBlockArgs},
+            coins::{CoinsConnectionArgs, CoinsToSpendArgs, ExcludeInput, SpendQueryElementInput},
+            contract::{
+                ContractBalanceQueryArgs, ContractBalancesConnectionArgs, ContractByIdArgs,
+            },
+            gas_price::{BlockHorizonArgs, EstimateGasPrice},
+            message::{MessageProofArgs, OwnedMessagesConnectionArgs},
+            tx::{
+                DryRunArg, StatusChangeSubscriptionArgs, SubmitAndAwaitStatusArg,
+                TransactionsByOwnerConnectionArgs, TxArg, TxIdArgs, TxWithEstimatedPredicatesArg,
+            },
+        },
+        types::{
+            Balance, Blob, Block, ChainInfo, Coin, CoinType, Contract, ContractBalance, Message,
+            MessageProof, NodeInfo, TransactionResponse, TransactionStatus,
+            gas_price::LatestGasPrice,
+            primitives::{BlockId, TransactionId},
+        },
+    },
+    reqwest_ext::FuelGraphQlResponse,
+};
+use fuel_core_types::services::{
+    block_importer::ImportResult, executor::TransactionExecutionStatus,
+};
+use fuel_tx::{BlobId, Transaction, TxId, UtxoId};
+use fuel_types::{Address, AssetId, BlockHeight, ContractId, Nonce, canonical::Serialize};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use tai64::Tai64;
+
+use super::{
+    batched_queries::{self, Balances, Coins, ContractBalances},
+    middleware::{self, ProviderMiddleware, RpcCall},
+    transport::{GraphQlRequest, GraphQlTransport},
+};
+use crate::runtime::{self, BoxStream};
+
+/// The RPCs of a node used by a [`Provider`](super::Provider), made through `FuelClient`, a
+/// custom [`GraphQlTransport`] or any other backend given to
+/// [`Provider::connect_with_backend`](super::Provider::connect_with_backend).
+///
+/// The types are the ones of `fuel-core-client`. Every RPC fails as unsupported unless
+/// implemented, so a backend only implements the ones it can serve.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait NodeApi: Debug + Send + Sync {
+    async fn health(&self) -> io::Result<bool> {
+        unsupported("health")
+    }
+
+    async fn node_info(&self) -> io::Result<NodeInfo> {
+        unsupported("node_info")
+    }
+
+    async fn chain_info(&self) -> io::Result<ChainInfo> {
+        unsupported("chain_info")
+    }
+
+    async fn latest_gas_price(&self) -> io::Result<LatestGasPrice> {
+        unsupported("latest_gas_price")
+    }
+
+    async fn estimate_gas_price(&self, _block_horizon: u32) -> io::Result<EstimateGasPrice> {
+        unsupported("estimate_gas_price")
+    }
+
+    async fn estimate_predicates(&self, _tx: &mut Transaction) -> io::Result<()> {
+        unsupported("estimate_predicates")
+    }
+
+    async fn dry_run_opt(
+        &self,
+        _txs: &[Transaction],
+        _utxo_validation: Option<bool>,
+        _gas_price: Option<u64>,
+        _at_height: Option<BlockHeight>,
+    ) -> io::Result<Vec<TransactionExecutionStatus>> {
+        unsupported("dry_run_opt")
+    }
+
+    async fn submit(&self, _tx: &Transaction) -> io::Result<TransactionId> {
+        unsupported("submit")
+    }
+
+    async fn submit_and_await_commit(&self, _tx: &Transaction) -> io::Result<TransactionStatus> {
+        unsupported("submit_and_await_commit")
+    }
+
+    async fn submit_and_await_status<'a>(
+        &'a self,
+        _tx: &'a Transaction,
+        _include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        unsupported("submit_and_await_status")
+    }
+
+    async fn transaction(&self, _id: &TxId) -> io::Result<Option<TransactionResponse>> {
+        unsupported("transaction")
+    }
+
+    async fn transaction_status(&self, _id: &TxId) -> io::Result<TransactionStatus> {
+        unsupported("transaction_status")
+    }
+
+    async fn subscribe_transaction_status<'a>(
+        &'a self,
+        _id: &'a TxId,
+        _include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        unsupported("subscribe_transaction_status")
+    }
+
+    async fn await_transaction_commit(&self, _id: &TxId) -> io::Result<TransactionStatus> {
+        unsupported("await_transaction_commit")
+    }
+
+    async fn transactions(
+        &self,
+        _request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        unsupported("transactions")
+    }
+
+    async fn transactions_by_owner(
+        &self,
+        _owner: &Address,
+        _request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        unsupported("transactions_by_owner")
+    }
+
+    async fn produce_blocks(
+        &self,
+        _blocks_to_produce: u32,
+        _start_timestamp: Option<u64>,
+    ) -> io::Result<BlockHeight> {
+        unsupported("produce_blocks")
+    }
+
+    async fn block(&self, _id: &BlockId) -> io::Result<Option<Block>> {
+        unsupported("block")
+    }
+
+    async fn block_by_height(&self, _height: BlockHeight) -> io::Result<Option<Block>> {
+        unsupported("block_by_height")
+    }
+
+    async fn blocks(
+        &self,
+        _request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Block, String>> {
+        unsupported("blocks")
+    }
+
+    /// The tips of the transactions in the latest `block_count` blocks, in a single request.
+    async fn recent_tips(&self, _block_count: i32) -> io::Result<Vec<u64>> {
+        unsupported("recent_tips")
+    }
+
+    async fn new_blocks_subscription(&self) -> io::Result<BoxStream<'_, io::Result<ImportResult>>> {
+        unsupported("new_blocks_subscription")
+    }
+
+    async fn blob(&self, _id: BlobId) -> io::Result<Option<Blob>> {
+        unsupported("blob")
+    }
+
+    async fn blob_exists(&self, _id: BlobId) -> io::Result<bool> {
+        unsupported("blob_exists")
+    }
+
+    async fn coins(
+        &self,
+        _owner: &Address,
+        _asset_id: Option<&AssetId>,
+        _request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Coin, String>> {
+        unsupported("coins")
+    }
+
+    async fn coins_to_spend(
+        &self,
+        _owner: &Address,
+        _spend_query: Vec<(AssetId, u128, Option<u16>)>,
+        _excluded_ids: Option<(Vec<UtxoId>, Vec<Nonce>)>,
+    ) -> io::Result<Vec<Vec<CoinType>>> {
+        unsupported("coins_to_spend")
+    }
+
+    async fn balance(&self, _owner: &Address, _asset_id: Option<&AssetId>) -> io::Result<u128> {
+        unsupported("balance")
+    }
+
+    async fn balances(
+        &self,
+        _owner: &Address,
+        _request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Balance, String>> {
+        unsupported("balances")
+    }
+
+    /// The first `first` balances of every owner, in a single request.
+    async fn balances_many(
+        &self,
+        _owners: &[Address],
+        _first: i32,
+    ) -> io::Result<Vec<PaginatedResult<Balance, String>>> {
+        unsupported("balances_many")
+    }
+
+    /// The first `first` coins of `asset_id` of every owner, in a single request.
+    async fn coins_many(
+        &self,
+        _owners: &[Address],
+        _asset_id: &AssetId,
+        _first: i32,
+    ) -> io::Result<Vec<PaginatedResult<Coin, String>>> {
+        unsupported("coins_many")
+    }
+
+    async fn contract_balance(
+        &self,
+        _id: &ContractId,
+        _asset: Option<&AssetId>,
+    ) -> io::Result<u64> {
+        unsupported("contract_balance")
+    }
+
+    async fn contract_balances(
+        &self,
+        _contract: &ContractId,
+        _request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<ContractBalance, String>> {
+        unsupported("contract_balances")
+    }
+
+    /// The first `first` balances of every contract, in a single request.
+    async fn contract_balances_many(
+        &self,
+        _contracts: &[ContractId],
+        _first: i32,
+    ) -> io::Result<Vec<PaginatedResult<ContractBalance, String>>> {
+        unsupported("contract_balances_many")
+    }
+
+    async fn contract_exists(&self, _id: &ContractId) -> io::Result<bool> {
+        unsupported("contract_exists")
+    }
+
+    async fn contract(&self, _id: &ContractId) -> io::Result<Option<Contract>> {
+        unsupported("contract")
+    }
+
+    async fn messages(
+        &self,
+        _owner: Option<&Address>,
+        _request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Message, String>> {
+        unsupported("messages")
+    }
+
+    async fn message_proof(
+        &self,
+        _transaction_id: &TxId,
+        _nonce: &Nonce,
+        _commit_block_id: Option<&BlockId>,
+        _commit_block_height: Option<BlockHeight>,
+    ) -> io::Result<MessageProof> {
+        unsupported("message_proof")
+    }
+
+    async fn is_user_account(&self, _address: [u8; 32]) -> io::Result<bool> {
+        unsupported("is_user_account")
+    }
+}
+
+fn unsupported<T>(method: &str) -> io::Result<T> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("`{method}` is not supported by the node backend"),
+    ))
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NodeApi for FuelClient {
+    // The subscriptions of `fuel-core-client` are native only, in the browser they go through a
+    // `GraphQlTransport`.
+    async fn health(&self) -> io::Result<bool> {
+        FuelClient::health(self).await
+    }
+
+    async fn node_info(&self) -> io::Result<NodeInfo> {
+        FuelClient::node_info(self).await
+    }
+
+    async fn chain_info(&self) -> io::Result<ChainInfo> {
+        FuelClient::chain_info(self).await
+    }
+
+    async fn latest_gas_price(&self) -> io::Result<LatestGasPrice> {
+        FuelClient::latest_gas_price(self).await
+    }
+
+    async fn estimate_gas_price(&self, block_horizon: u32) -> io::Result<EstimateGasPrice> {
+        FuelClient::estimate_gas_price(self, block_horizon).await
+    }
+
+    async fn estimate_predicates(&self, tx: &mut Transaction) -> io::Result<()> {
+        FuelClient::estimate_predicates(self, tx).await
+    }
+
+    async fn dry_run_opt(
+        &self,
+        txs: &[Transaction],
+        utxo_validation: Option<bool>,
+        gas_price: Option<u64>,
+        at_height: Option<BlockHeight>,
+    ) -> io::Result<Vec<TransactionExecutionStatus>> {
+        FuelClient::dry_run_opt(self, txs, utxo_validation, gas_price, at_height).await
+    }
+
+    async fn submit(&self, tx: &Transaction) -> io::Result<TransactionId> {
+        FuelClient::submit(self, tx).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn submit_and_await_commit(&self, tx: &Transaction) -> io::Result<TransactionStatus> {
+        FuelClient::submit_and_await_commit(self, tx).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn submit_and_await_status<'a>(
+        &'a self,
+        tx: &'a Transaction,
+        include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        let stream =
+            FuelClient::submit_and_await_status_opt(self, tx, None, Some(include_preconfirmation))
+                .await?;
+
+        Ok(runtime::boxed_stream(stream))
+    }
+
+    async fn transaction(&self, id: &TxId) -> io::Result<Option<TransactionResponse>> {
+        FuelClient::transaction(self, id).await
+    }
+
+    async fn transaction_status(&self, id: &TxId) -> io::Result<TransactionStatus> {
+        FuelClient::transaction_status(self, id).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn subscribe_transaction_status<'a>(
+        &'a self,
+        id: &'a TxId,
+        include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        let stream =
+            FuelClient::subscribe_transaction_status_opt(self, id, Some(include_preconfirmation))
+                .await?;
+
+        Ok(runtime::boxed_stream(stream))
+    }
+
+    async fn await_transaction_commit(&self, id: &TxId) -> io::Result<TransactionStatus> {
+        FuelClient::await_transaction_commit(self, id).await
+    }
+
+    async fn transactions(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        FuelClient::transactions(self, request).await
+    }
+
+    async fn transactions_by_owner(
+        &self,
+        owner: &Address,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        FuelClient::transactions_by_owner(self, owner, request).await
+    }
+
+    async fn produce_blocks(
+        &self,
+        blocks_to_produce: u32,
+        start_timestamp: Option<u64>,
+    ) -> io::Result<BlockHeight> {
+        FuelClient::produce_blocks(self, blocks_to_produce, start_timestamp).await
+    }
+
+    async fn block(&self, id: &BlockId) -> io::Result<Option<Block>> {
+        FuelClient::block(self, id).await
+    }
+
+    async fn block_by_height(&self, height: BlockHeight) -> io::Result<Option<Block>> {
+        FuelClient::block_by_height(self, height).await
+    }
+
+    async fn blocks(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Block, String>> {
+        FuelClient::blocks(self, request).await
+    }
+
+    async fn recent_tips(&self, block_count: i32) -> io::Result<Vec<u64>> {
+        let response = self.query(recent_tips_query(block_count)).await?;
+
+        Ok(recent_tips(response))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn new_blocks_subscription(&self) -> io::Result<BoxStream<'_, io::Result<ImportResult>>> {
+        Ok(runtime::boxed_stream(
+            FuelClient::new_blocks_subscription(self).await?,
+        ))
+    }
+
+    async fn blob(&self, id: BlobId) -> io::Result<Option<Blob>> {
+        FuelClient::blob(self, id).await
+    }
+
+    async fn blob_exists(&self, id: BlobId) -> io::Result<bool> {
+        FuelClient::blob_exists(self, id).await
+    }
+
+    async fn coins(
+        &self,
+        owner: &Address,
+        asset_id: Option<&AssetId>,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Coin, String>> {
+        FuelClient::coins(self, owner, asset_id, request).await
+    }
+
+    async fn coins_to_spend(
+        &self,
+        owner: &Address,
+        spend_query: Vec<(AssetId, u128, Option<u16>)>,
+        excluded_ids: Option<(Vec<UtxoId>, Vec<Nonce>)>,
+    ) -> io::Result<Vec<Vec<CoinType>>> {
+        FuelClient::coins_to_spend(self, owner, spend_query, excluded_ids).await
+    }
+
+    async fn balance(&self, owner: &Address, asset_id: Option<&AssetId>) -> io::Result<u128> {
+        FuelClient::balance(self, owner, asset_id).await
+    }
+
+    async fn balances(
+        &self,
+        owner: &Address,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Balance, String>> {
+        FuelClient::balances(self, owner, request).await
+    }
+
+    async fn balances_many(&self, owners: &[Address], first: i32) -> io::Result<Vec<Balances>> {
+        let query = batched_queries::balances_many_query(owners, first)?;
+
+        self.query(query).await?.split(owners.len())
+    }
+
+    async fn coins_many(
+        &self,
+        owners: &[Address],
+        asset_id: &AssetId,
+        first: i32,
+    ) -> io::Result<Vec<Coins>> {
+        let query = batched_queries::coins_many_query(owners, asset_id, first)?;
+
+        self.query(query).await?.split(owners.len())
+    }
+
+    async fn contract_balance(&self, id: &ContractId, asset: Option<&AssetId>) -> io::Result<u64> {
+        FuelClient::contract_balance(self, id, asset).await
+    }
+
+    async fn contract_balances(
+        &self,
+        contract: &ContractId,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<ContractBalance, String>> {
+        FuelClient::contract_balances(self, contract, request).await
+    }
+
+    async fn contract_balances_many(
+        &self,
+        contracts: &[ContractId],
+        first: i32,
+    ) -> io::Result<Vec<ContractBalances>> {
+        let query = batched_queries::contract_balances_many_query(contracts, first)?;
+
+        self.query(query).await?.split(contracts.len())
+    }
+
+    async fn contract_exists(&self, id: &ContractId) -> io::Result<bool> {
+        let response = self.query(contract_exists_query(id)).await?;
+
+        Ok(contract_exists(response, id))
+    }
+
+    async fn contract(&self, id: &ContractId) -> io::Result<Option<Contract>> {
+        FuelClient::contract(self, id).await
+    }
+
+    async fn messages(
+        &self,
+        owner: Option<&Address>,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Message, String>> {
+        FuelClient::messages(self, owner, request).await
+    }
+
+    async fn message_proof(
+        &self,
+        transaction_id: &TxId,
+        nonce: &Nonce,
+        commit_block_id: Option<&BlockId>,
+        commit_block_height: Option<BlockHeight>,
+    ) -> io::Result<MessageProof> {
+        FuelClient::message_proof(
+            self,
+            transaction_id,
+            nonce,
+            commit_block_id,
+            commit_block_height,
+        )
+        .await
+    }
+
+    async fn is_user_account(&self, address: [u8; 32]) -> io::Result<bool> {
+        let response = self.query(is_user_account_query(address)).await?;
+
+        Ok(is_user_account(response))
+    }
+}
+
+/// Makes the same requests as `FuelClient`, through a custom transport.
+///
+/// The operations and their responses are the ones of `fuel-core-client`, and the responses are
+/// decoded by the `FuelClient` of the same node. Only the supported node version is queried, so
+/// unlike `FuelClient` there is no fallback for the chain info of nodes older than 0.48.
+///
+/// The middleware of the provider runs around every request, which it can see and change.
+#[derive(Debug, Clone)]
+pub(crate) struct TransportClient {
+    transport: Arc<dyn GraphQlTransport>,
+    client: FuelClient,
+    middleware: Vec<Arc<dyn ProviderMiddleware>>,
+}
+
+impl TransportClient {
+    pub(crate) fn new(transport: Arc<dyn GraphQlTransport>, client: FuelClient) -> Self {
+        Self {
+            transport,
+            client,
+            middleware: vec![],
+        }
+    }
+
+    pub(crate) fn push_middleware(&mut self, middleware: Arc<dyn ProviderMiddleware>) {
+        self.middleware.push(middleware);
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        self.transport.url()
+    }
+
+    /// Sends `operation` on behalf of the RPC `method`.
+    async fn query<ResponseData, Vars>(
+        &self,
+        method: &'static str,
+        operation: Operation<ResponseData, Vars>,
+    ) -> io::Result<ResponseData>
+    where
+        ResponseData: DeserializeOwned + QueryFragment + 'static,
+        Vars: serde::Serialize,
+    {
+        let rpc = RpcCall::new(method).with_request(GraphQlRequest::from_operation(&operation)?);
+        let response = middleware::intercept(&self.middleware, rpc, |rpc| async {
+            self.transport.query(sent_request(rpc)?).await
+        })
+        .await?;
+
+        self.decode_response(response)
+    }
+
+    /// Opens the subscription built from `variables` on behalf of the RPC `method`.
+    async fn subscribe<ResponseData, Vars>(
+        &self,
+        method: &'static str,
+        variables: Vars,
+    ) -> io::Result<BoxStream<'_, io::Result<ResponseData>>>
+    where
+        ResponseData: DeserializeOwned + QueryFragment + SubscriptionBuilder<Vars> + Send + 'static,
+        Vars: serde::Serialize,
+    {
+        let request = GraphQlRequest::from_operation(&ResponseData::build(variables))?;
+        let rpc = RpcCall::new(method).with_request(request);
+        let stream = middleware::intercept(&self.middleware, rpc, |rpc| async {
+            self.transport.subscribe(sent_request(rpc)?).await
+        })
+        .await?;
+
+        Ok(runtime::boxed_stream(
+            stream.map(|response| self.decode_response(response?)),
+        ))
+    }
+
+    /// Errors the node answered with, as opposed to a response that isn't a GraphQL one, are
+    /// wrapped into a [`NodeError`].
+    fn decode_response<ResponseData>(&self, response: serde_json::Value) -> io::Result<ResponseData>
+    where
+        ResponseData: DeserializeOwned + 'static,
+    {
+        let response: FuelGraphQlResponse<ResponseData> = serde_json::from_value(response)?;
+        let answered_with_error = response
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.fuel_block_height_precondition_failed)
+            == Some(true)
+            || (response.response.data.is_none() && response.response.errors.is_some());
+
+        self.client.decode_response(response).map_err(|err| {
+            if answered_with_error {
+                io::Error::other(NodeError(err))
+            } else {
+                err
+            }
+        })
+    }
+}
+
+/// An error a node answered a request with, e.g. GraphQL errors or an unmet block height
+/// precondition, as opposed to a failure to reach the node or to get a response out of it.
+#[derive(Debug)]
+pub(crate) struct NodeError(io::Error);
+
+impl NodeError {
+    pub(crate) fn is_node_error(err: &io::Error) -> bool {
+        err.get_ref().is_some_and(|inner| inner.is::<NodeError>())
+    }
+}
+
+impl std::fmt::Display for NodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for NodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+fn sent_request(rpc: RpcCall) -> io::Result<GraphQlRequest> {
+    let method = rpc.method();
+
+    rpc.into_request().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("the middleware removed the request of `{method}`"),
+        )
+    })
+}
+
+/// Waits for the first final status of `statuses`, as `FuelClient::await_transaction_commit` does.
+async fn final_status(
+    mut statuses: BoxStream<'_, io::Result<TransactionStatus>>,
+) -> io::Result<TransactionStatus> {
+    while let Some(status) = statuses.next().await {
+        let status = status?;
+        if status.is_final() {
+            return Ok(status);
+        }
+    }
+
+    Err(io::Error::other(
+        "status subscription ended before reaching a final status",
+    ))
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NodeApi for TransportClient {
+    async fn health(&self) -> io::Result<bool> {
+        let query = schema::Health::build(());
+
+        Ok(self.query("health", query).await?.health)
+    }
+
+    async fn node_info(&self) -> io::Result<NodeInfo> {
+        let query = schema::node_info::QueryNodeInfo::build(());
+
+        Ok(self.query("node_info", query).await?.node_info.into())
+    }
+
+    async fn chain_info(&self) -> io::Result<ChainInfo> {
+        let query = schema::chain::ChainQuery::build(());
+
+        Ok(self.query("chain_info", query).await?.chain.try_into()?)
+    }
+
+    async fn latest_gas_price(&self) -> io::Result<LatestGasPrice> {
+        let query = schema::gas_price::QueryLatestGasPrice::build(());
+
+        Ok(self
+            .query("latest_gas_price", query)
+            .await?
+            .latest_gas_price
+            .into())
+    }
+
+    async fn estimate_gas_price(&self, block_horizon: u32) -> io::Result<EstimateGasPrice> {
+        let query = schema::gas_price::QueryEstimateGasPrice::build(BlockHorizonArgs {
+            block_horizon: Some(block_horizon.into()),
+        });
+
+        Ok(self
+            .query("estimate_gas_price", query)
+            .await?
+            .estimate_gas_price)
+    }
+
+    async fn estimate_predicates(&self, tx: &mut Transaction) -> io::Result<()> {
+        let query = schema::tx::EstimatePredicates::build(TxArg {
+            tx: HexString(Bytes(tx.to_bytes())),
+        });
+        *tx = self
+            .query("estimate_predicates", query)
+            .await?
+            .estimate_predicates
+            .try_into()?;
+
+        Ok(())
+    }
+
+    async fn dry_run_opt(
+        &self,
+        txs: &[Transaction],
+        utxo_validation: Option<bool>,
+        gas_price: Option<u64>,
+        at_height: Option<BlockHeight>,
+    ) -> io::Result<Vec<TransactionExecutionStatus>> {
+        let query = schema::tx::DryRun::build(DryRunArg {
+            txs: txs
+                .iter()
+                .map(|tx| HexString(Bytes(tx.to_bytes())))
+                .collect(),
+            utxo_validation,
+            gas_price: gas_price.map(Into::into),
+            block_height: at_height.map(Into::into),
+        });
+
+        self.query("dry_run_opt", query)
+            .await?
+            .dry_run
+            .into_iter()
+            .map(|status| status.try_into().map_err(Into::into))
+            .collect()
+    }
+
+    async fn submit(&self, tx: &Transaction) -> io::Result<TransactionId> {
+        let query = schema::tx::Submit::build(TxWithEstimatedPredicatesArg {
+            tx: HexString(Bytes(tx.to_bytes())),
+            estimate_predicates: None,
+        });
+
+        Ok(self.query("submit", query).await?.submit.id.into())
+    }
+
+    async fn submit_and_await_commit(&self, tx: &Transaction) -> io::Result<TransactionStatus> {
+        final_status(self.submit_and_await_status(tx, false).await?).await
+    }
+
+    async fn submit_and_await_status<'a>(
+        &'a self,
+        tx: &'a Transaction,
+        include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        let stream = self
+            .subscribe::<schema::tx::SubmitAndAwaitStatusSubscription, _>(
+                "submit_and_await_status",
+                SubmitAndAwaitStatusArg {
+                    tx: HexString(Bytes(tx.to_bytes())),
+                    estimate_predicates: None,
+                    include_preconfirmation: Some(include_preconfirmation),
+                },
+            )
+            .await?;
+
+        Ok(runtime::boxed_stream(stream.map(|response| {
+            Ok(response?.submit_and_await_status.try_into()?)
+        })))
+    }
+
+    async fn transaction(&self, id: &TxId) -> io::Result<Option<TransactionResponse>> {
+        let query = schema::tx::TransactionQuery::build(TxIdArgs { id: (*id).into() });
+
+        Ok(self
+            .query("transaction", query)
+            .await?
+            .transaction
+            .map(TryInto::try_into)
+            .transpose()?)
+    }
+
+    async fn transaction_status(&self, id: &TxId) -> io::Result<TransactionStatus> {
+        let query = schema::tx::TransactionStatusQuery::build(TxIdArgs { id: (*id).into() });
+
+        let status = self
+            .query("transaction_status", query)
+            .await?
+            .transaction
+            .and_then(|tx| tx.status)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("status not found for transaction {id}"),
+                )
+            })?;
+
+        Ok(status.try_into()?)
+    }
+
+    async fn subscribe_transaction_status<'a>(
+        &'a self,
+        id: &'a TxId,
+        include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        let stream = self
+            .subscribe::<schema::tx::StatusChangeSubscription, _>(
+                "subscribe_transaction_status",
+                StatusChangeSubscriptionArgs {
+                    id: (*id).into(),
+                    include_preconfirmation: Some(include_preconfirmation),
+                },
+            )
+            .await?;
+
+        Ok(runtime::boxed_stream(
+            stream.map(|response| Ok(response?.status_change.try_into()?)),
+        ))
+    }
+
+    async fn await_transaction_commit(&self, id: &TxId) -> io::Result<TransactionStatus> {
+        final_status(self.subscribe_transaction_status(id, false).await?).await
+    }
+
+    async fn transactions(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        let query = schema::tx::TransactionsQuery::build(ConnectionArgs::from(request));
+
+        Ok(self
+            .query("transactions", query)
+            .await?
+            .transactions
+            .try_into()?)
+    }
+
+    async fn transactions_by_owner(
+        &self,
+        owner: &Address,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        let owner: schema::Address = (*owner).into();
+        let query = schema::tx::TransactionsByOwnerQuery::build(
+            TransactionsByOwnerConnectionArgs::from((owner, request)),
+        );
+
+        Ok(self
+            .query("transactions_by_owner", query)
+            .await?
+            .transactions_by_owner
+            .try_into()?)
+    }
+
+    async fn produce_blocks(
+        &self,
+        blocks_to_produce: u32,
+        start_timestamp: Option<u64>,
+    ) -> io::Result<BlockHeight> {
+        let query = schema::block::BlockMutation::build(ProduceBlockArgs {
+            blocks_to_produce: blocks_to_produce.into(),
+            start_timestamp: start_timestamp.map(|timestamp| Tai64(timestamp).into()),
+        });
+
+        Ok(self
+            .query("produce_blocks", query)
+            .await?
+            .produce_blocks
+            .into())
+    }
+
+    async fn block(&self, id: &BlockId) -> io::Result<Option<Block>> {
+        let query = schema::block::BlockByIdQuery::build(BlockByIdArgs {
+            id: Some((*id).into()),
+        });
+
+        Ok(self
+            .query("block", query)
+            .await?
+            .block
+            .map(TryInto::try_into)
+            .transpose()?)
+    }
+
+    async fn block_by_height(&self, height: BlockHeight) -> io::Result<Option<Block>> {
+        let query = schema::block::BlockByHeightQuery::build(BlockByHeightArgs {
+            height: Some(U32(height.into())),
+        });
+
+        Ok(self
+            .query("block_by_height", query)
+            .await?
+            .block
+            .map(TryInto::try_into)
+            .transpose()?)
+    }
+
+    async fn blocks(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Block, String>> {
+        let query = schema::block::BlocksQuery::build(ConnectionArgs::from(request));
+
+        Ok(self.query("blocks", query).await?.blocks.try_into()?)
+    }
+
+    async fn recent_tips(&self, block_count: i32) -> io::Result<Vec<u64>> {
+        let response = self
+            .query("recent_tips", recent_tips_query(block_count))
+            .await?;
+
+        Ok(recent_tips(response))
+    }
+
+    async fn new_blocks_subscription(&self) -> io::Result<BoxStream<'_, io::Result<ImportResult>>> {
+        let stream = self
+            .subscribe::<schema::block::NewBlocksSubscription, _>("new_blocks_subscription", ())
+            .await?;
+
+        Ok(runtime::boxed_stream(stream.map(|response| {
+            postcard::from_bytes(response?.new_blocks.0.0.as_slice())
+                .map_err(|e| io::Error::other(format!("Failed to deserialize ImportResult: {e:?}")))
+        })))
+    }
+
+    async fn blob(&self, id: BlobId) -> io::Result<Option<Blob>> {
+        let query = schema::blob::BlobByIdQuery::build(BlobByIdArgs { id: id.into() });
+
+        Ok(self.query("blob", query).await?.blob.map(Into::into))
+    }
+
+    async fn blob_exists(&self, id: BlobId) -> io::Result<bool> {
+        let query = schema::blob::BlobExistsQuery::build(BlobByIdArgs { id: id.into() });
+
+        Ok(self.query("blob_exists", query).await?.blob.is_some())
+    }
+
+    async fn coins(
+        &self,
+        owner: &Address,
+        asset_id: Option<&AssetId>,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Coin, String>> {
+        let owner: schema::Address = (*owner).into();
+        let asset_id = asset_id.map(|id| (*id).into());
+        let query =
+            schema::coins::CoinsQuery::build(CoinsConnectionArgs::from((owner, asset_id, request)));
+
+        Ok(self.query("coins", query).await?.coins.into())
+    }
+
+    async fn coins_to_spend(
+        &self,
+        owner: &Address,
+        spend_query: Vec<(AssetId, u128, Option<u16>)>,
+        excluded_ids: Option<(Vec<UtxoId>, Vec<Nonce>)>,
+    ) -> io::Result<Vec<Vec<CoinType>>> {
+        let owner: schema::Address = (*owner).into();
+        let spend_query = spend_query
+            .into_iter()
+            .map(|(asset_id, amount, max)| SpendQueryElementInput {
+                asset_id: asset_id.into(),
+                amount: amount.into(),
+                max: max.map(Into::into),
+            })
+            .collect();
+        let excluded_ids: Option<ExcludeInput> = excluded_ids.map(Into::into);
+        let query = schema::coins::CoinsToSpendQuery::build(CoinsToSpendArgs::from((
+            owner,
+            spend_query,
+            excluded_ids,
+        )));
+
+        Ok(self
+            .query("coins_to_spend", query)
+            .await?
+            .coins_to_spend
+            .into_iter()
+            .map(|coins| coins.into_iter().map(Into::into).collect())
+            .collect())
+    }
+
+    async fn balance(&self, owner: &Address, asset_id: Option<&AssetId>) -> io::Result<u128> {
+        let query = schema::balance::BalanceQuery::build(BalanceArgs {
+            owner: (*owner).into(),
+            asset_id: asset_id.map(|id| (*id).into()).unwrap_or_default(),
+        });
+        let balance: Balance = self.query("balance", query).await?.balance.into();
+
+        Ok(balance.amount)
+    }
+
+    async fn balances(
+        &self,
+        owner: &Address,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Balance, String>> {
+        let owner: schema::Address = (*owner).into();
+        let query =
+            schema::balance::BalancesQuery::build(BalancesConnectionArgs::from((owner, request)));
+
+        Ok(self.query("balances", query).await?.balances.into())
+    }
+
+    async fn balances_many(&self, owners: &[Address], first: i32) -> io::Result<Vec<Balances>> {
+        let query = batched_queries::balances_many_query(owners, first)?;
+
+        self.query("balances_many", query)
+            .await?
+            .split(owners.len())
+    }
+
+    async fn coins_many(
+        &self,
+        owners: &[Address],
+        asset_id: &AssetId,
+        first: i32,
+    ) -> io::Result<Vec<Coins>> {
+        let query = batched_queries::coins_many_query(owners, asset_id, first)?;
+
+        self.query("coins_many", query).await?.split(owners.len())
+    }
+
+    async fn contract_balance(&self, id: &ContractId, asset: Option<&AssetId>) -> io::Result<u64> {
+        let query = schema::contract::ContractBalanceQuery::build(ContractBalanceQueryArgs {
+            id: (*id).into(),
+            asset: asset.map(|id| (*id).into()).unwrap_or_default(),
+        });
+        let balance: ContractBalance = self
+            .query("contract_balance", query)
+            .await?
+            .contract_balance
+            .into();
+
+        Ok(balance.amount)
+    }
+
+    async fn contract_balances(
+        &self,
+        contract: &ContractId,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<ContractBalance, String>> {
+        let contract_id: schema::ContractId = (*contract).into();
+        let query = schema::contract::ContractBalancesQuery::build(
+            ContractBalancesConnectionArgs::from((contract_id, request)),
+        );
+
+        Ok(self
+            .query("contract_balances", query)
+            .await?
+            .contract_balances
+            .into())
+    }
+
+    async fn contract_balances_many(
+        &self,
+        contracts: &[ContractId],
+        first: i32,
+    ) -> io::Result<Vec<ContractBalances>> {
+        let query = batched_queries::contract_balances_many_query(contracts, first)?;
+
+        self.query("contract_balances_many", query)
+            .await?
+            .split(contracts.len())
+    }
+
+    async fn contract_exists(&self, id: &ContractId) -> io::Result<bool> {
+        let response = self
+            .query("contract_exists", contract_exists_query(id))
+            .await?;
+
+        Ok(contract_exists(response, id))
+    }
+
+    async fn contract(&self, id: &ContractId) -> io::Result<Option<Contract>> {
+        let query =
+            schema::contract::ContractByIdQuery::build(ContractByIdArgs { id: (*id).into() });
+
+        Ok(self
+            .query("contract", query)
+            .await?
+            .contract
+            .map(Into::into))
+    }
+
+    async fn messages(
+        &self,
+        owner: Option<&Address>,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Message, String>> {
+        let owner: Option<schema::Address> = owner.map(|owner| (*owner).into());
+        let query = schema::message::OwnedMessageQuery::build(OwnedMessagesConnectionArgs::from((
+            owner, request,
+        )));
+
+        Ok(self.query("messages", query).await?.messages.into())
+    }
+
+    async fn message_proof(
+        &self,
+        transaction_id: &TxId,
+        nonce: &Nonce,
+        commit_block_id: Option<&BlockId>,
+        commit_block_height: Option<BlockHeight>,
+    ) -> io::Result<MessageProof> {
+        let query = schema::message::MessageProofQuery::build(MessageProofArgs {
+            transaction_id: (*transaction_id).into(),
+            nonce: (*nonce).into(),
+            commit_block_id: commit_block_id.map(|id| (*id).into()),
+            commit_block_height: commit_block_height.map(Into::into),
+        });
+
+        Ok(self
+            .query("message_proof", query)
+            .await?
+            .message_proof
+            .try_into()?)
+    }
+
+    async fn is_user_account(&self, address: [u8; 32]) -> io::Result<bool> {
+        let response = self
+            .query("is_user_account", is_user_account_query(address))
+            .await?;
+
+        Ok(is_user_account(response))
+    }
+}
+
+fn contract_exists_query(id: &ContractId) -> Operation<ContractExistsQuery, ContractByIdArgs> {
+    ContractExistsQuery::build(ContractByIdArgs { id: (*id).into() })
+}
+
+fn contract_exists(response: ContractExistsQuery, id: &ContractId) -> bool {
+    response
+        .contract
+        .map(|contract| ContractId::from(contract.id) == *id)
+        .unwrap_or(false)
+}
+
+fn recent_tips_query(block_count: i32) -> Operation<RecentTipsQuery, ConnectionArgs> {
+    RecentTipsQuery::build(ConnectionArgs {
+        after: None,
+        before: None,
+        first: None,
+        last: Some(block_count),
+    })
+}
+
+fn recent_tips(response: RecentTipsQuery) -> Vec<u64> {
+    response
+        .blocks
+        .nodes
+        .into_iter()
+        .flat_map(|block| block.transactions)
+        // mint transactions have no policies
+        .filter_map(|tx| tx.policies?.tip)
+        .map(Into::into)
+        .collect()
+}
+
+fn is_user_account_query(
+    address: [u8; 32],
+) -> Operation<IsUserAccountQuery, IsUserAccountVariables> {
+    IsUserAccountQuery::build(IsUserAccountVariables {
+        blob_id: BlobId::from(address).into(),
+        contract_id: ContractId::from(address).into(),
+        transaction_id: TransactionId::from(address).into(),
+    })
+}
+
+fn is_user_account(response: IsUserAccountQuery) -> bool {
+    let is_resource =
+        response.blob.is_some() || response.contract.is_some() || response.transaction.is_some();
+
+    !is_resource
+}
+
+mod custom_queries {
+    use fuel_core_client::client::schema::{
+        BlobId, ConnectionArgsFields, ContractId, TransactionId, U64,
+        blob::BlobIdFragment,
+        contract::{ContractByIdArgsFields, ContractIdFragment},
+        schema,
+        tx::TransactionIdFragment,
+    };
+
+    #[derive(cynic::QueryVariables, Debug, Clone)]
+    pub struct IsUserAccountVariables {
+        pub blob_id: BlobId,
+        pub contract_id: ContractId,
+        pub transaction_id: TransactionId,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(
+        graphql_type = "Query",
+        variables = "IsUserAccountVariables",
+        schema_path = "./src/schema/schema.sdl"
+    )]
+    pub struct IsUserAccountQuery {
+        #[arguments(id: $blob_id)]
+        pub blob: Option<BlobIdFragment>,
+        #[arguments(id: $contract_id)]
+        pub contract: Option<ContractIdFragment>,
+        #[arguments(id: $transaction_id)]
+        pub transaction: Option<TransactionIdFragment>,
+    }
+
+    #[derive(cynic::QueryFragment, Clone, Debug)]
+    #[cynic(
+        schema_path = "./src/schema/schema.sdl",
+        graphql_type = "Query",
+        variables = "ContractByIdArgs"
+    )]
+    pub struct ContractExistsQuery {
+        #[arguments(id: $id)]
+        pub contract: Option<ContractIdFragment>,
+    }
+
+    /// The latest blocks with only the tips of their transactions, instead of fetching each
+    /// transaction on its own.
+    #[derive(cynic::QueryFragment, Clone, Debug)]
+    #[cynic(
+        schema_path = "./src/schema/schema.sdl",
+        graphql_type = "Query",
+        variables = "ConnectionArgs"
+    )]
+    pub struct RecentTipsQuery {
+        #[arguments(after: $after, before: $before, first: $first, last: $last)]
+        pub blocks: BlockTipsConnection,
+    }
+
+    #[derive(cynic::QueryFragment, Clone, Debug)]
+    #[cynic(
+        schema_path = "./src/schema/schema.sdl",
+        graphql_type = "BlockConnection"
+    )]
+    pub struct BlockTipsConnection {
+        pub nodes: Vec<BlockTips>,
+    }
+
+    #[derive(cynic::QueryFragment, Clone, Debug)]
+    #[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Block")]
+    pub struct BlockTips {
+        pub transactions: Vec<TransactionTip>,
+    }
+
+    #[derive(cynic::QueryFragment, Clone, Debug)]
+    #[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Transaction")]
+    pub struct TransactionTip {
+        pub policies: Option<PoliciesTip>,
+    }
+
+    #[derive(cynic::QueryFragment, Clone, Debug)]
+    #[cynic(schema_path = "./src/schema/schema.sdl", graphql_type = "Policies")]
+    pub struct PoliciesTip {
+        pub tip: Option<U64>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Mutex};
+
+    use async_trait::async_trait;
+    use fuels_core::types::DryRunner;
+    use serde_json::{Value, json};
+
+    use crate::provider::{
+        Next, Provider, ProviderMiddleware, RpcCall,
+        transport::{
+            GraphQlRequest, GraphQlTransport, RecordedExchange, RecordedTransport, Recording,
+        },
+    };
+
+    fn node_info_response(node_version: &str) -> Value {
+        json!({
+            "data": {
+                "nodeInfo": {
+                    "utxoValidation": true,
+                    "vmBacktrace": false,
+                    "maxTx": "4064",
+                    "maxGas": "30000000000",
+                    "maxSize": "131072000",
+                    "maxDepth": "10",
+                    "nodeVersion": node_version,
+                    "indexation": {
+                        "balances": true,
+                        "coinsToSpend": true,
+                        "assetMetadata": true
+                    },
+                    "txPoolStats": {
+                        "txCount": "0",
+                        "totalGas": "0",
+                        "totalSize": "0"
+                    }
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn provider_runs_over_a_recorded_transport() {
+        // given
+        let transport = RecordedTransport::new(Recording {
+            url: "http://localhost:4000".to_string(),
+            exchanges: vec![
+                RecordedExchange::new("QueryNodeInfo", Value::Null, node_info_response("0.48.0")),
+                RecordedExchange::new("Health", Value::Null, json!({"data": {"health": true}})),
+                RecordedExchange::new(
+                    "QueryLatestGasPrice",
+                    Value::Null,
+                    json!({"errors": [{"message": "gas price unavailable"}]}),
+                ),
+            ],
+        });
+
+        // when
+        let provider = Provider::connect_with_transport(transport).await.unwrap();
+
+        // then
+        assert_eq!(provider.url(), "http://localhost:4000");
+        assert!(provider.healthy().await.unwrap());
+        assert_eq!(provider.node_info().await.unwrap().node_version, "0.48.0");
+
+        let err = provider.latest_gas_price().await.unwrap_err();
+        assert!(err.to_string().contains("gas price unavailable"));
+
+        let err = provider.chain_info().await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("no recorded response for `ChainQuery`")
+        );
+    }
+
+    #[tokio::test]
+    async fn recent_tips_are_fetched_with_their_blocks() {
+        // given
+        let block = |tips: &[Option<&str>]| {
+            let transactions: Vec<_> = tips
+                .iter()
+                .map(|tip| match tip {
+                    Some(tip) => json!({"policies": {"tip": tip}}),
+                    None => json!({"policies": null}),
+                })
+                .collect();
+
+            json!({"transactions": transactions})
+        };
+        let transport = RecordedTransport::new(Recording {
+            url: "http://localhost:4000".to_string(),
+            exchanges: vec![
+                RecordedExchange::new("QueryNodeInfo", Value::Null, node_info_response("0.48.0")),
+                RecordedExchange::new(
+                    "RecentTipsQuery",
+                    json!({"after": null, "before": null, "first": null, "last": 2}),
+                    json!({"data": {"blocks": {"nodes": [
+                        block(&[Some("10"), None]),
+                        block(&[Some("0"), Some("25"), None]),
+                    ]}}}),
+                ),
+            ],
+        });
+        let provider = Provider::connect_with_transport(transport).await.unwrap();
+
+        // when
+        let tips = DryRunner::recent_tips(&provider, 2).await.unwrap();
+
+        // then
+        assert_eq!(tips, [10, 0, 25]);
+    }
+
+    #[derive(Debug)]
+    struct SeenHeaders {
+        recorded: RecordedTransport,
+        headers: Mutex<Vec<Option<String>>>,
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl GraphQlTransport for SeenHeaders {
+        fn url(&self) -> &str {
+            self.recorded.url()
+        }
+
+        async fn query(&self, request: GraphQlRequest) -> io::Result<Value> {
+            self.headers
+                .lock()
+                .unwrap()
+                .push(request.headers.get("x-signature").cloned());
+
+            self.recorded.query(request).await
+        }
+    }
+
+    #[derive(Debug)]
+    struct Sign;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl ProviderMiddleware for Sign {
+        async fn handle(&self, mut rpc: RpcCall, next: Next<'_>) -> io::Result<()> {
+            let request = rpc.request_mut().expect("sent through a transport");
+            let signature = format!(
+                "{}:{}",
+                request.operation_name.clone().unwrap_or_default(),
+                request.query.len()
+            );
+            request.headers.insert("x-signature".to_string(), signature);
+
+            next.run(rpc).await
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_sees_and_changes_the_requests_of_a_transport() {
+        // given
+        let transport = std::sync::Arc::new(SeenHeaders {
+            recorded: RecordedTransport::new(Recording {
+                url: "http://localhost:4000".to_string(),
+                exchanges: vec![
+                    RecordedExchange::new(
+                        "QueryNodeInfo",
+                        Value::Null,
+                        node_info_response("0.48.0"),
+                    ),
+                    RecordedExchange::new("Health", Value::Null, json!({"data": {"health": true}})),
+                ],
+            }),
+            headers: Mutex::default(),
+        });
+        let provider = Provider::connect_with_transport(transport.clone())
+            .await
+            .unwrap()
+            .with_middleware(Sign);
+
+        // when
+        let healthy = provider.healthy().await.unwrap();
+
+        // then
+        assert!(healthy);
+        let headers = transport.headers.lock().unwrap();
+        assert_eq!(headers[0], None);
+        assert!(
+            headers[1]
+                .as_ref()
+                .is_some_and(|signature| signature.starts_with("Health:"))
+        );
+    }
+}
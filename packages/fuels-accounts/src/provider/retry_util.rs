@@ -2,6 +2,8 @@ use std::{fmt::Debug, future::Future, num::NonZeroU32, time::Duration};
 
 use fuels_core::types::errors::{Result, error};
 
+use crate::runtime;
+
 /// A set of strategies to control retry intervals between attempts.
 ///
 /// The `Backoff` enum defines different strategies for managing intervals between retry attempts.
@@ -90,6 +92,10 @@ impl RetryConfig {
             interval,
         })
     }
+
+    pub(crate) fn interval(&self) -> &Backoff {
+        &self.interval
+    }
 }
 
 impl Default for RetryConfig {
@@ -142,7 +148,7 @@ where
             return result;
         }
 
-        tokio::time::sleep(retry_config.interval.wait_duration(attempt)).await;
+        runtime::sleep(retry_config.interval.wait_duration(attempt)).await;
     }
 
     last_result.expect("should not happen")
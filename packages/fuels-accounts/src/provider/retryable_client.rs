@@ -1,30 +1,37 @@
-use std::{future::Future, io};
+use std::{future::Future, io, sync::Arc};
 
 use async_trait::async_trait;
-use custom_queries::{ContractExistsQuery, IsUserAccountQuery, IsUserAccountVariables};
-use cynic::QueryBuilder;
 use fuel_core_client::client::{
     FuelClient,
     pagination::{PaginatedResult, PaginationRequest},
-    schema::contract::ContractByIdArgs,
     types::{
-        Balance, Blob, Block, ChainInfo, Coin, CoinType, ContractBalance, Message, MessageProof,
-        NodeInfo, TransactionResponse, TransactionStatus,
+        Balance, Blob, Block, ChainInfo, Coin, CoinType, Contract, ContractBalance, Message,
+        MessageProof, NodeInfo, TransactionResponse, TransactionStatus,
         gas_price::{EstimateGasPrice, LatestGasPrice},
         primitives::{BlockId, TransactionId},
     },
 };
-use fuel_core_types::services::executor::TransactionExecutionStatus;
+use fuel_core_types::services::{
+    block_importer::ImportResult, executor::TransactionExecutionStatus,
+};
 use fuel_tx::{BlobId, ConsensusParameters, Transaction, TxId, UtxoId};
 use fuel_types::{Address, AssetId, BlockHeight, ContractId, Nonce};
 use fuels_core::types::errors::{Error, Result, error};
 use futures::Stream;
 
 use super::{
+    batched_queries::{Balances, Coins, ContractBalances},
     cache::CacheableRpcs,
+    endpoint_pool::{self, EndpointPool, EndpointPoolConfig, EndpointStatus},
+    middleware::{self, ProviderMiddleware, RpcCall},
+    node_api::{NodeApi, TransportClient},
     supported_versions::{self, VersionCompatibility},
+    transport::GraphQlTransport,
+};
+use crate::{
+    provider::{RetryConfig, retry_util},
+    runtime::{Instant, MaybeSend},
 };
-use crate::provider::{RetryConfig, retry_util};
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum RequestError {
@@ -43,11 +50,16 @@ impl From<RequestError> for Error {
 #[derive(Debug, Clone)]
 pub(crate) struct RetryableClient {
     client: FuelClient,
+    transport: Option<TransportClient>,
+    backend: Option<Arc<dyn NodeApi>>,
+    pool: Option<Arc<EndpointPool>>,
     retry_config: RetryConfig,
+    middleware: Vec<Arc<dyn ProviderMiddleware>>,
     prepend_warning: Option<String>,
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl CacheableRpcs for RetryableClient {
     async fn consensus_parameters(&self) -> Result<ConsensusParameters> {
         Ok(self.chain_info().await?.consensus_parameters)
@@ -56,6 +68,38 @@ impl CacheableRpcs for RetryableClient {
     async fn node_info(&self) -> Result<NodeInfo> {
         Ok(self.node_info().await?)
     }
+
+    async fn chain_info(&self) -> Result<ChainInfo> {
+        Ok(self.chain_info().await?)
+    }
+
+    async fn block(&self, id: &BlockId) -> Result<Option<Block>> {
+        Ok(self.block(id).await?)
+    }
+
+    async fn block_by_height(&self, height: BlockHeight) -> Result<Option<Block>> {
+        Ok(self.block_by_height(height).await?)
+    }
+
+    async fn transaction(&self, id: &TxId) -> Result<Option<TransactionResponse>> {
+        Ok(self.transaction(id).await?)
+    }
+
+    async fn transaction_status(&self, id: &TxId) -> Result<TransactionStatus> {
+        Ok(self.transaction_status(id).await?)
+    }
+
+    async fn blob(&self, id: BlobId) -> Result<Option<Blob>> {
+        Ok(self.blob(id).await?)
+    }
+
+    async fn contract_exists(&self, id: &ContractId) -> Result<bool> {
+        Ok(self.contract_exists(id).await?)
+    }
+
+    async fn contract(&self, id: &ContractId) -> Result<Option<Contract>> {
+        Ok(self.contract(id).await?)
+    }
 }
 
 impl RetryableClient {
@@ -70,11 +114,83 @@ impl RetryableClient {
 
         Ok(Self {
             client,
+            transport: None,
+            backend: None,
+            pool: None,
+            retry_config,
+            middleware: vec![],
+            prepend_warning: warning,
+        })
+    }
+
+    pub(crate) async fn connect_with_transport(
+        transport: Arc<dyn GraphQlTransport>,
+        retry_config: RetryConfig,
+    ) -> Result<Self> {
+        let client = FuelClient::new(transport.url()).map_err(|e| error!(Provider, "{e}"))?;
+        let transport = TransportClient::new(transport, client.clone());
+
+        let node_info = transport.node_info().await?;
+        let warning = Self::version_compatibility_warning(&node_info)?;
+
+        Ok(Self {
+            client,
+            transport: Some(transport),
+            backend: None,
+            pool: None,
+            retry_config,
+            middleware: vec![],
+            prepend_warning: warning,
+        })
+    }
+
+    pub(crate) async fn connect_with_backend(
+        url: &str,
+        backend: Arc<dyn NodeApi>,
+        retry_config: RetryConfig,
+    ) -> Result<Self> {
+        let client = FuelClient::new(url).map_err(|e| error!(Provider, "{e}"))?;
+
+        let node_info = backend.node_info().await?;
+        let warning = Self::version_compatibility_warning(&node_info)?;
+
+        Ok(Self {
+            client,
+            transport: None,
+            backend: Some(backend),
+            pool: None,
             retry_config,
+            middleware: vec![],
             prepend_warning: warning,
         })
     }
 
+    pub(crate) async fn connect_with_pool(
+        urls: &[impl AsRef<str>],
+        config: EndpointPoolConfig,
+        retry_config: RetryConfig,
+    ) -> Result<Self> {
+        let pool = Arc::new(EndpointPool::connect(urls, config.routing)?);
+        let client = FuelClient::new(pool.url(0)).map_err(|e| error!(Provider, "{e}"))?;
+
+        pool.check_health(config.health_check_timeout).await;
+        EndpointPool::spawn_health_checks(&pool, &config);
+
+        let mut client = Self {
+            client,
+            transport: None,
+            backend: None,
+            pool: Some(pool),
+            retry_config,
+            middleware: vec![],
+            prepend_warning: None,
+        };
+        let node_info = client.node_info().await?;
+        client.prepend_warning = Self::version_compatibility_warning(&node_info)?;
+
+        Ok(client)
+    }
+
     fn version_compatibility_warning(node_info: &NodeInfo) -> Result<Option<String>> {
         let node_version = node_info
             .node_version
@@ -100,22 +216,68 @@ impl RetryableClient {
     }
 
     pub(crate) fn url(&self) -> &str {
-        self.client.get_default_url().as_str()
+        match (&self.transport, &self.pool) {
+            (Some(transport), _) => transport.url(),
+            (None, Some(pool)) => pool.url(0),
+            (None, None) => self.client.get_default_url().as_str(),
+        }
+    }
+
+    pub(crate) fn endpoints(&self) -> Vec<EndpointStatus> {
+        self.pool
+            .as_ref()
+            .map(|pool| pool.statuses())
+            .unwrap_or_default()
     }
 
     pub fn client(&self) -> &FuelClient {
         &self.client
     }
 
+    pub(crate) fn retry_config(&self) -> &RetryConfig {
+        &self.retry_config
+    }
+
     pub(crate) fn set_retry_config(&mut self, retry_config: RetryConfig) {
         self.retry_config = retry_config;
     }
 
-    async fn wrap<T, Fut>(&self, action: impl Fn() -> Fut) -> RequestResult<T>
+    pub(crate) fn push_middleware(&mut self, middleware: Arc<dyn ProviderMiddleware>) {
+        if let Some(transport) = &mut self.transport {
+            transport.push_middleware(middleware.clone());
+        }
+        self.middleware.push(middleware);
+    }
+
+    async fn wrap<'a, T, Fut>(
+        &'a self,
+        method: &'static str,
+        action: impl Fn(&'a dyn NodeApi) -> Fut + Sync,
+    ) -> RequestResult<T>
+    where
+        T: MaybeSend,
+        Fut: Future<Output = io::Result<T>> + MaybeSend,
+    {
+        self.wrap_routed(method, None, action)
+            .await
+            .map(|(response, _)| response)
+    }
+
+    /// Like `wrap`, but routes the request to the endpoint that received the transaction
+    /// `about_tx`. Also returns the endpoint of the pool that served the request, if any.
+    async fn wrap_routed<'a, T, Fut>(
+        &'a self,
+        method: &'static str,
+        about_tx: Option<&TxId>,
+        action: impl Fn(&'a dyn NodeApi) -> Fut + Sync,
+    ) -> RequestResult<(T, Option<usize>)>
     where
-        Fut: Future<Output = io::Result<T>>,
+        T: MaybeSend,
+        Fut: Future<Output = io::Result<T>> + MaybeSend,
     {
-        retry_util::retry(action, &self.retry_config, |result| result.is_err())
+        let attempt = || self.attempt(method, about_tx, &action);
+
+        retry_util::retry(attempt, &self.retry_config, |result| result.is_err())
             .await
             .map_err(|e| {
                 let msg = if let Some(warning) = &self.prepend_warning {
@@ -127,28 +289,72 @@ impl RetryableClient {
             })
     }
 
+    async fn attempt<'a, T, Fut>(
+        &'a self,
+        method: &'static str,
+        about_tx: Option<&TxId>,
+        action: impl FnOnce(&'a dyn NodeApi) -> Fut + MaybeSend,
+    ) -> io::Result<(T, Option<usize>)>
+    where
+        T: MaybeSend,
+        Fut: Future<Output = io::Result<T>> + MaybeSend,
+    {
+        if let Some(transport) = &self.transport {
+            // the transport runs the middleware around the GraphQL requests themselves
+            return Ok((action(transport).await?, None));
+        }
+
+        let rpc = RpcCall::new(method);
+        let Some(pool) = &self.pool else {
+            let api: &dyn NodeApi = match &self.backend {
+                Some(backend) => backend.as_ref(),
+                None => &self.client,
+            };
+            let response = middleware::intercept(&self.middleware, rpc, |_| action(api)).await?;
+
+            return Ok((response, None));
+        };
+
+        let idx = pool.pick(about_tx);
+        let started = Instant::now();
+        let result = middleware::intercept(&self.middleware, rpc, |_| action(pool.api(idx))).await;
+        let answered = !matches!(&result, Err(err) if endpoint_pool::is_endpoint_failure(err));
+        pool.report(idx, answered, started.elapsed());
+
+        Ok((result?, Some(idx)))
+    }
+
     // DELEGATION START
     pub async fn health(&self) -> RequestResult<bool> {
-        self.wrap(|| self.client.health()).await
+        self.wrap("health", |api| api.health()).await
     }
 
     pub async fn transaction(&self, id: &TxId) -> RequestResult<Option<TransactionResponse>> {
-        self.wrap(|| self.client.transaction(id)).await
+        self.wrap_routed("transaction", Some(id), |api| api.transaction(id))
+            .await
+            .map(|(response, _)| response)
     }
 
     pub(crate) async fn chain_info(&self) -> RequestResult<ChainInfo> {
-        self.wrap(|| self.client.chain_info()).await
+        self.wrap("chain_info", |api| api.chain_info()).await
     }
 
     pub async fn await_transaction_commit(&self, id: &TxId) -> RequestResult<TransactionStatus> {
-        self.wrap(|| self.client.await_transaction_commit(id)).await
+        self.wrap_routed("await_transaction_commit", Some(id), |api| {
+            api.await_transaction_commit(id)
+        })
+        .await
+        .map(|(response, _)| response)
     }
 
     pub async fn submit_and_await_commit(
         &self,
         tx: &Transaction,
     ) -> RequestResult<TransactionStatus> {
-        self.wrap(|| self.client.submit_and_await_commit(tx)).await
+        self.wrap("submit_and_await_commit", |api| {
+            api.submit_and_await_commit(tx)
+        })
+        .await
     }
 
     pub async fn submit_and_await_status<'a>(
@@ -156,9 +362,8 @@ impl RetryableClient {
         tx: &'a Transaction,
         include_preconfirmation: bool,
     ) -> RequestResult<impl Stream<Item = io::Result<TransactionStatus>> + 'a> {
-        self.wrap(|| {
-            self.client
-                .submit_and_await_status_opt(tx, None, Some(include_preconfirmation))
+        self.wrap("submit_and_await_status", |api| {
+            api.submit_and_await_status(tx, include_preconfirmation)
         })
         .await
     }
@@ -168,47 +373,62 @@ impl RetryableClient {
         id: &'a TxId,
         include_preconfirmation: bool,
     ) -> RequestResult<impl Stream<Item = io::Result<TransactionStatus>> + 'a> {
-        self.wrap(|| {
-            self.client
-                .subscribe_transaction_status_opt(id, Some(include_preconfirmation))
+        self.wrap_routed("subscribe_transaction_status", Some(id), |api| {
+            api.subscribe_transaction_status(id, include_preconfirmation)
         })
         .await
+        .map(|(response, _)| response)
     }
 
     pub async fn submit(&self, tx: &Transaction) -> RequestResult<TransactionId> {
-        self.wrap(|| self.client.submit(tx)).await
+        let (tx_id, endpoint) = self
+            .wrap_routed("submit", None, |api| api.submit(tx))
+            .await?;
+        if let (Some(pool), Some(idx)) = (&self.pool, endpoint) {
+            pool.pin(tx_id, idx);
+        }
+
+        Ok(tx_id)
     }
 
     pub async fn transaction_status(&self, id: &TxId) -> RequestResult<TransactionStatus> {
-        self.wrap(|| self.client.transaction_status(id)).await
+        self.wrap_routed("transaction_status", Some(id), |api| {
+            api.transaction_status(id)
+        })
+        .await
+        .map(|(response, _)| response)
     }
 
     pub async fn node_info(&self) -> RequestResult<NodeInfo> {
-        self.wrap(|| self.client.node_info()).await
+        self.wrap("node_info", |api| api.node_info()).await
     }
 
     pub async fn blob(&self, blob_id: BlobId) -> RequestResult<Option<Blob>> {
-        self.wrap(|| self.client.blob(blob_id)).await
+        self.wrap("blob", |api| api.blob(blob_id)).await
     }
 
     pub async fn blob_exists(&self, blob_id: BlobId) -> RequestResult<bool> {
-        self.wrap(|| self.client.blob_exists(blob_id)).await
+        self.wrap("blob_exists", |api| api.blob_exists(blob_id))
+            .await
     }
 
     pub async fn latest_gas_price(&self) -> RequestResult<LatestGasPrice> {
-        self.wrap(|| self.client.latest_gas_price()).await
+        self.wrap("latest_gas_price", |api| api.latest_gas_price())
+            .await
     }
 
     pub async fn estimate_gas_price(&self, block_horizon: u32) -> RequestResult<EstimateGasPrice> {
-        self.wrap(|| self.client.estimate_gas_price(block_horizon))
-            .await
-            .map(Into::into)
+        self.wrap("estimate_gas_price", |api| {
+            api.estimate_gas_price(block_horizon)
+        })
+        .await
+        .map(Into::into)
     }
 
     pub async fn estimate_predicates(&self, tx: &Transaction) -> RequestResult<Transaction> {
-        self.wrap(|| async {
+        self.wrap("estimate_predicates", |api| async move {
             let mut new_tx = tx.clone();
-            self.client.estimate_predicates(&mut new_tx).await?;
+            api.estimate_predicates(&mut new_tx).await?;
             Ok(new_tx)
         })
         .await
@@ -218,7 +438,8 @@ impl RetryableClient {
         &self,
         tx: &[Transaction],
     ) -> RequestResult<Vec<TransactionExecutionStatus>> {
-        self.wrap(|| self.client.dry_run(tx)).await
+        self.wrap("dry_run", |api| api.dry_run_opt(tx, None, None, None))
+            .await
     }
 
     pub async fn dry_run_opt(
@@ -228,9 +449,8 @@ impl RetryableClient {
         gas_price: Option<u64>,
         at_height: Option<BlockHeight>,
     ) -> RequestResult<Vec<TransactionExecutionStatus>> {
-        self.wrap(|| {
-            self.client
-                .dry_run_opt(tx, utxo_validation, gas_price, at_height)
+        self.wrap("dry_run_opt", |api| {
+            api.dry_run_opt(tx, utxo_validation, gas_price, at_height)
         })
         .await
     }
@@ -241,8 +461,10 @@ impl RetryableClient {
         asset_id: Option<&AssetId>,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Coin, String>> {
-        self.wrap(move || self.client.coins(owner, asset_id, request.clone()))
-            .await
+        self.wrap("coins", move |api| {
+            api.coins(owner, asset_id, request.clone())
+        })
+        .await
     }
 
     pub async fn coins_to_spend(
@@ -251,9 +473,8 @@ impl RetryableClient {
         spend_query: Vec<(AssetId, u128, Option<u16>)>,
         excluded_ids: Option<(Vec<UtxoId>, Vec<Nonce>)>,
     ) -> RequestResult<Vec<Vec<CoinType>>> {
-        self.wrap(move || {
-            self.client
-                .coins_to_spend(owner, spend_query.clone(), excluded_ids.clone())
+        self.wrap("coins_to_spend", move |api| {
+            api.coins_to_spend(owner, spend_query.clone(), excluded_ids.clone())
         })
         .await
     }
@@ -263,7 +484,8 @@ impl RetryableClient {
         owner: &Address,
         asset_id: Option<&AssetId>,
     ) -> RequestResult<u128> {
-        self.wrap(|| self.client.balance(owner, asset_id)).await
+        self.wrap("balance", |api| api.balance(owner, asset_id))
+            .await
     }
 
     pub async fn contract_balance(
@@ -271,7 +493,8 @@ impl RetryableClient {
         id: &ContractId,
         asset: Option<&AssetId>,
     ) -> RequestResult<u64> {
-        self.wrap(|| self.client.contract_balance(id, asset)).await
+        self.wrap("contract_balance", |api| api.contract_balance(id, asset))
+            .await
     }
 
     pub async fn contract_balances(
@@ -279,8 +502,10 @@ impl RetryableClient {
         contract: &ContractId,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<ContractBalance, String>> {
-        self.wrap(|| self.client.contract_balances(contract, request.clone()))
-            .await
+        self.wrap("contract_balances", |api| {
+            api.contract_balances(contract, request.clone())
+        })
+        .await
     }
 
     pub async fn balances(
@@ -288,15 +513,50 @@ impl RetryableClient {
         owner: &Address,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Balance, String>> {
-        self.wrap(|| self.client.balances(owner, request.clone()))
+        self.wrap("balances", |api| api.balances(owner, request.clone()))
             .await
     }
 
+    pub async fn recent_tips(&self, block_count: i32) -> RequestResult<Vec<u64>> {
+        self.wrap("recent_tips", |api| api.recent_tips(block_count))
+            .await
+    }
+
+    pub async fn balances_many(
+        &self,
+        owners: &[Address],
+        first: i32,
+    ) -> RequestResult<Vec<Balances>> {
+        self.wrap("balances_many", |api| api.balances_many(owners, first))
+            .await
+    }
+
+    pub async fn coins_many(
+        &self,
+        owners: &[Address],
+        asset_id: &AssetId,
+        first: i32,
+    ) -> RequestResult<Vec<Coins>> {
+        self.wrap("coins_many", |api| api.coins_many(owners, asset_id, first))
+            .await
+    }
+
+    pub async fn contract_balances_many(
+        &self,
+        contracts: &[ContractId],
+        first: i32,
+    ) -> RequestResult<Vec<ContractBalances>> {
+        self.wrap("contract_balances_many", |api| {
+            api.contract_balances_many(contracts, first)
+        })
+        .await
+    }
+
     pub async fn transactions(
         &self,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<TransactionResponse, String>> {
-        self.wrap(|| self.client.transactions(request.clone()))
+        self.wrap("transactions", |api| api.transactions(request.clone()))
             .await
     }
 
@@ -305,8 +565,10 @@ impl RetryableClient {
         owner: &Address,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<TransactionResponse, String>> {
-        self.wrap(|| self.client.transactions_by_owner(owner, request.clone()))
-            .await
+        self.wrap("transactions_by_owner", |api| {
+            api.transactions_by_owner(owner, request.clone())
+        })
+        .await
     }
 
     pub async fn produce_blocks(
@@ -314,26 +576,35 @@ impl RetryableClient {
         blocks_to_produce: u32,
         start_timestamp: Option<u64>,
     ) -> RequestResult<BlockHeight> {
-        self.wrap(|| {
-            self.client
-                .produce_blocks(blocks_to_produce, start_timestamp)
+        self.wrap("produce_blocks", |api| {
+            api.produce_blocks(blocks_to_produce, start_timestamp)
         })
         .await
     }
 
     pub async fn block(&self, id: &BlockId) -> RequestResult<Option<Block>> {
-        self.wrap(|| self.client.block(id)).await
+        self.wrap("block", |api| api.block(id)).await
     }
 
     pub async fn block_by_height(&self, height: BlockHeight) -> RequestResult<Option<Block>> {
-        self.wrap(|| self.client.block_by_height(height)).await
+        self.wrap("block_by_height", |api| api.block_by_height(height))
+            .await
     }
 
     pub async fn blocks(
         &self,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Block, String>> {
-        self.wrap(|| self.client.blocks(request.clone())).await
+        self.wrap("blocks", |api| api.blocks(request.clone())).await
+    }
+
+    pub async fn new_blocks_subscription(
+        &self,
+    ) -> RequestResult<impl Stream<Item = io::Result<ImportResult>> + '_> {
+        self.wrap("new_blocks_subscription", |api| {
+            api.new_blocks_subscription()
+        })
+        .await
     }
 
     pub async fn messages(
@@ -341,7 +612,7 @@ impl RetryableClient {
         owner: Option<&Address>,
         request: PaginationRequest<String>,
     ) -> RequestResult<PaginatedResult<Message, String>> {
-        self.wrap(|| self.client.messages(owner, request.clone()))
+        self.wrap("messages", |api| api.messages(owner, request.clone()))
             .await
     }
 
@@ -353,90 +624,27 @@ impl RetryableClient {
         commit_block_id: Option<&BlockId>,
         commit_block_height: Option<BlockHeight>,
     ) -> RequestResult<MessageProof> {
-        self.wrap(|| {
-            self.client
-                .message_proof(transaction_id, nonce, commit_block_id, commit_block_height)
+        self.wrap("message_proof", |api| {
+            api.message_proof(transaction_id, nonce, commit_block_id, commit_block_height)
         })
         .await
     }
 
     pub async fn contract_exists(&self, contract_id: &ContractId) -> RequestResult<bool> {
-        self.wrap(|| {
-            let query = ContractExistsQuery::build(ContractByIdArgs {
-                id: (*contract_id).into(),
-            });
-            self.client.query(query)
-        })
-        .await
-        .map(|query| {
-            query
-                .contract
-                .map(|contract| ContractId::from(contract.id) == *contract_id)
-                .unwrap_or(false)
-        })
+        self.wrap("contract_exists", |api| api.contract_exists(contract_id))
+            .await
+    }
+
+    pub async fn contract(&self, contract_id: &ContractId) -> RequestResult<Option<Contract>> {
+        self.wrap("contract", |api| api.contract(contract_id)).await
     }
     // DELEGATION END
 
     pub async fn is_user_account(&self, address: [u8; 32]) -> Result<bool> {
-        let blob_id = BlobId::from(address);
-        let contract_id = ContractId::from(address);
-        let transaction_id = TransactionId::from(address);
-
-        let query = IsUserAccountQuery::build(IsUserAccountVariables {
-            blob_id: blob_id.into(),
-            contract_id: contract_id.into(),
-            transaction_id: transaction_id.into(),
-        });
-
-        let response = self.client.query(query).await?;
-
-        let is_resource = response.blob.is_some()
-            || response.contract.is_some()
-            || response.transaction.is_some();
-
-        Ok(!is_resource)
-    }
-}
+        let (is_user_account, _) = self
+            .attempt("is_user_account", None, |api| api.is_user_account(address))
+            .await?;
 
-mod custom_queries {
-    use fuel_core_client::client::schema::{
-        BlobId, ContractId, TransactionId,
-        blob::BlobIdFragment,
-        contract::{ContractByIdArgsFields, ContractIdFragment},
-        schema,
-        tx::TransactionIdFragment,
-    };
-
-    #[derive(cynic::QueryVariables, Debug, Clone)]
-    pub struct IsUserAccountVariables {
-        pub blob_id: BlobId,
-        pub contract_id: ContractId,
-        pub transaction_id: TransactionId,
-    }
-
-    #[derive(cynic::QueryFragment, Debug)]
-    #[cynic(
-        graphql_type = "Query",
-        variables = "IsUserAccountVariables",
-        schema_path = "./src/schema/schema.sdl"
-    )]
-    pub struct IsUserAccountQuery {
-        #[arguments(id: $blob_id)]
-        pub blob: Option<BlobIdFragment>,
-        #[arguments(id: $contract_id)]
-        pub contract: Option<ContractIdFragment>,
-        #[arguments(id: $transaction_id)]
-        pub transaction: Option<TransactionIdFragment>,
-    }
-
-    #[derive(cynic::QueryFragment, Clone, Debug)]
-    #[cynic(
-        schema_path = "./src/schema/schema.sdl",
-        graphql_type = "Query",
-        variables = "ContractByIdArgs"
-    )]
-    pub struct ContractExistsQuery {
-        #[arguments(id: $id)]
-        pub contract: Option<ContractIdFragment>,
+        Ok(is_user_account)
     }
 }
@@ -0,0 +1,550 @@
+//! Custom transports for the GraphQL requests made by a [`Provider`](super::Provider).
+//!
+//! A provider connected through [`Provider::connect_with_transport`](super::Provider::connect_with_transport)
+//! serializes every request into a [`GraphQlRequest`] and hands it to its [`GraphQlTransport`]
+//! instead of sending it over the HTTP client of `fuel-core-client`.
+//!
+//! [`HttpTransport`] sends the requests over HTTP through a caller-configured `reqwest` client,
+//! and through `fetch` in the browser. [`RecordingTransport`] and [`RecordedTransport`] capture
+//! and replay the exchanges of a session.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    io,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::runtime;
+pub use crate::runtime::BoxStream;
+
+/// A GraphQL query, mutation or subscription, serialized as the body of the request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlRequest {
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+    #[serde(default)]
+    pub variables: Value,
+    /// Headers to send along with the request, e.g. added by a
+    /// [`ProviderMiddleware`](super::ProviderMiddleware). They are not part of the body.
+    #[serde(skip)]
+    pub headers: HashMap<String, String>,
+}
+
+impl GraphQlRequest {
+    pub(crate) fn from_operation(operation: &impl Serialize) -> io::Result<Self> {
+        Ok(serde_json::from_value(serde_json::to_value(operation)?)?)
+    }
+}
+
+/// Sends the GraphQL requests of a provider to a node.
+///
+/// Responses are the JSON bodies returned by the node, i.e. objects holding the `data`, `errors`
+/// and `extensions` of the response.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait GraphQlTransport: Debug + Send + Sync {
+    /// The URL of the node, as reported by [`Provider::url`](super::Provider::url).
+    ///
+    /// [`Provider::client`](super::Provider::client) connects to it directly, bypassing the
+    /// transport.
+    fn url(&self) -> &str;
+
+    async fn query(&self, request: GraphQlRequest) -> io::Result<Value>;
+
+    /// Opens a subscription, e.g. to the status of a transaction, returning every response the
+    /// node sends.
+    async fn subscribe(
+        &self,
+        request: GraphQlRequest,
+    ) -> io::Result<BoxStream<'static, io::Result<Value>>> {
+        Err(io::Error::other(format!(
+            "transport `{}` does not support subscriptions, cannot run `{}`",
+            self.url(),
+            request.operation_name.unwrap_or_default()
+        )))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: GraphQlTransport + ?Sized> GraphQlTransport for Arc<T> {
+    fn url(&self) -> &str {
+        (**self).url()
+    }
+
+    async fn query(&self, request: GraphQlRequest) -> io::Result<Value> {
+        (**self).query(request).await
+    }
+
+    async fn subscribe(
+        &self,
+        request: GraphQlRequest,
+    ) -> io::Result<BoxStream<'static, io::Result<Value>>> {
+        (**self).subscribe(request).await
+    }
+}
+
+/// Sends the requests over HTTP, like `fuel-core-client` does, through a [`reqwest::Client`]
+/// configured by the caller, e.g. with custom TLS roots, a proxy or default authentication
+/// headers.
+///
+/// On `wasm32` the requests are made with the `fetch` API of the browser. Subscriptions are read
+/// from the server-sent events of the node.
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    url: String,
+    client: reqwest::Client,
+    query_url: reqwest::Url,
+    subscription_url: reqwest::Url,
+}
+
+impl HttpTransport {
+    /// Connects to the node at `url`, e.g. `http://127.0.0.1:4000`, with a default client.
+    pub fn new(url: impl Into<String>) -> io::Result<Self> {
+        Self::with_client(url, reqwest::Client::new())
+    }
+
+    /// Connects to the node at `url` through `client`.
+    pub fn with_client(url: impl Into<String>, client: reqwest::Client) -> io::Result<Self> {
+        let url = url.into();
+
+        Ok(Self {
+            query_url: node_endpoint(&url, "/v1/graphql")?,
+            subscription_url: node_endpoint(&url, "/v1/graphql-sub")?,
+            url,
+            client,
+        })
+    }
+
+    fn post(&self, url: &reqwest::Url, request: &GraphQlRequest) -> reqwest::RequestBuilder {
+        let mut post = self.client.post(url.clone()).json(request);
+        for (name, value) in &request.headers {
+            post = post.header(name, value);
+        }
+
+        post
+    }
+}
+
+/// The endpoint at `path` of the node at `url`, completed with a scheme like `FuelClient` does.
+fn node_endpoint(url: &str, path: &str) -> io::Result<reqwest::Url> {
+    let url = if url.starts_with("http") {
+        url.to_string()
+    } else {
+        format!("http://{url}")
+    };
+
+    let mut url = reqwest::Url::parse(&url).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid node url `{url}`: {e}"),
+        )
+    })?;
+    url.set_path(path);
+
+    Ok(url)
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GraphQlTransport for HttpTransport {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn query(&self, request: GraphQlRequest) -> io::Result<Value> {
+        self.post(&self.query_url, &request)
+            .send()
+            .await
+            .map_err(io::Error::other)?
+            .json()
+            .await
+            .map_err(io::Error::other)
+    }
+
+    async fn subscribe(
+        &self,
+        request: GraphQlRequest,
+    ) -> io::Result<BoxStream<'static, io::Result<Value>>> {
+        let response = self
+            .post(&self.subscription_url, &request)
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(io::Error::other)?;
+
+        Ok(runtime::boxed_stream(event_data(response.bytes_stream())))
+    }
+}
+
+/// Parses the `data` of every server-sent event of `chunks` as JSON. Events without data, like
+/// keep-alive comments, are skipped.
+fn event_data<B, E>(
+    chunks: impl Stream<Item = Result<B, E>>,
+) -> impl Stream<Item = io::Result<Value>>
+where
+    B: AsRef<[u8]>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let chunks = Box::pin(chunks);
+
+    futures::stream::unfold(
+        (chunks, Vec::<u8>::new()),
+        |(mut chunks, mut buffer)| async move {
+            loop {
+                if let Some(end) = buffer.windows(2).position(|window| window == b"\n\n") {
+                    let event: Vec<u8> = buffer.drain(..end + 2).collect();
+                    let Some(data) = data_lines(&String::from_utf8_lossy(&event)) else {
+                        continue;
+                    };
+                    let value = serde_json::from_str(&data).map_err(|e| {
+                        io::Error::new(io::ErrorKind::InvalidData, format!("invalid event: {e}"))
+                    });
+
+                    return Some((value, (chunks, buffer)));
+                }
+
+                match chunks.next().await? {
+                    // line endings are normalized so that events always end with an empty line
+                    Ok(chunk) => {
+                        buffer.extend(chunk.as_ref().iter().filter(|&&byte| byte != b'\r'))
+                    }
+                    Err(e) => return Some((Err(io::Error::other(e)), (chunks, buffer))),
+                }
+            }
+        },
+    )
+}
+
+fn data_lines(event: &str) -> Option<String> {
+    let data: Vec<_> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+
+    (!data.is_empty()).then(|| data.join("\n"))
+}
+
+/// A request together with the responses the node sent for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub operation_name: Option<String>,
+    pub variables: Value,
+    /// The response of a query or mutation, or every response of a subscription.
+    pub responses: Vec<Value>,
+}
+
+impl RecordedExchange {
+    pub fn new(operation_name: impl Into<String>, variables: Value, response: Value) -> Self {
+        Self {
+            operation_name: Some(operation_name.into()),
+            variables,
+            responses: vec![response],
+        }
+    }
+
+    fn answers(&self, request: &GraphQlRequest) -> bool {
+        self.operation_name == request.operation_name && self.variables == request.variables
+    }
+}
+
+/// The exchanges recorded by a [`RecordingTransport`], in the order they happened.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Recording {
+    pub url: String,
+    pub exchanges: Vec<RecordedExchange>,
+}
+
+/// Records the exchanges of another transport so that they can be replayed by a
+/// [`RecordedTransport`].
+#[derive(Debug, Clone)]
+pub struct RecordingTransport<T> {
+    transport: T,
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl<T: GraphQlTransport> RecordingTransport<T> {
+    pub fn new(transport: T) -> Self {
+        let recording = Recording {
+            url: transport.url().to_string(),
+            exchanges: vec![],
+        };
+
+        Self {
+            transport,
+            recording: Arc::new(Mutex::new(recording)),
+        }
+    }
+
+    pub fn recording(&self) -> Recording {
+        self.recording.lock().expect("not poisoned").clone()
+    }
+
+    fn record(&self, request: &GraphQlRequest) -> usize {
+        let mut recording = self.recording.lock().expect("not poisoned");
+        recording.exchanges.push(RecordedExchange {
+            operation_name: request.operation_name.clone(),
+            variables: request.variables.clone(),
+            responses: vec![],
+        });
+
+        recording.exchanges.len() - 1
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: GraphQlTransport> GraphQlTransport for RecordingTransport<T> {
+    fn url(&self) -> &str {
+        self.transport.url()
+    }
+
+    async fn query(&self, request: GraphQlRequest) -> io::Result<Value> {
+        let response = self.transport.query(request.clone()).await?;
+
+        let idx = self.record(&request);
+        self.recording.lock().expect("not poisoned").exchanges[idx]
+            .responses
+            .push(response.clone());
+
+        Ok(response)
+    }
+
+    async fn subscribe(
+        &self,
+        request: GraphQlRequest,
+    ) -> io::Result<BoxStream<'static, io::Result<Value>>> {
+        let stream = self.transport.subscribe(request.clone()).await?;
+
+        let idx = self.record(&request);
+        let recording = Arc::clone(&self.recording);
+        let stream = stream.inspect(move |response| {
+            if let Ok(response) = response {
+                recording.lock().expect("not poisoned").exchanges[idx]
+                    .responses
+                    .push(response.clone());
+            }
+        });
+
+        Ok(runtime::boxed_stream(stream))
+    }
+}
+
+/// Serves the responses of a [`Recording`] without connecting to a node.
+///
+/// Requests are answered with the recorded exchanges having the same operation name and
+/// variables, in the order they were recorded. Once they are used up, the last one keeps being
+/// served.
+#[derive(Debug)]
+pub struct RecordedTransport {
+    url: String,
+    exchanges: Vec<RecordedExchange>,
+    served: Mutex<HashMap<usize, usize>>,
+}
+
+impl RecordedTransport {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            url: recording.url,
+            exchanges: recording.exchanges,
+            served: Default::default(),
+        }
+    }
+
+    fn responses(&self, request: &GraphQlRequest) -> io::Result<Vec<Value>> {
+        let matching: Vec<_> = (0..self.exchanges.len())
+            .filter(|&idx| self.exchanges[idx].answers(request))
+            .collect();
+
+        let Some(&last) = matching.last() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "no recorded response for `{}` with variables `{}`",
+                    request.operation_name.as_deref().unwrap_or_default(),
+                    request.variables
+                ),
+            ));
+        };
+
+        let mut served = self.served.lock().expect("not poisoned");
+        let times_served = served.entry(last).or_default();
+        let idx = matching.get(*times_served).copied().unwrap_or(last);
+        *times_served += 1;
+
+        Ok(self.exchanges[idx].responses.clone())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GraphQlTransport for RecordedTransport {
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn query(&self, request: GraphQlRequest) -> io::Result<Value> {
+        self.responses(&request)?.into_iter().next().ok_or_else(|| {
+            io::Error::other(format!(
+                "recorded exchange `{}` has no response",
+                request.operation_name.unwrap_or_default()
+            ))
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        request: GraphQlRequest,
+    ) -> io::Result<BoxStream<'static, io::Result<Value>>> {
+        let responses = self.responses(&request)?;
+
+        Ok(runtime::boxed_stream(futures::stream::iter(
+            responses.into_iter().map(Ok),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn request(operation_name: &str, variables: Value) -> GraphQlRequest {
+        GraphQlRequest {
+            query: String::new(),
+            operation_name: Some(operation_name.to_string()),
+            variables,
+            headers: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn http_transport_posts_to_the_graphql_endpoints_of_the_node() -> io::Result<()> {
+        let transport = HttpTransport::new("127.0.0.1:4000")?;
+
+        assert_eq!(transport.url(), "127.0.0.1:4000");
+        assert_eq!(
+            transport.query_url.as_str(),
+            "http://127.0.0.1:4000/v1/graphql"
+        );
+        assert_eq!(
+            transport.subscription_url.as_str(),
+            "http://127.0.0.1:4000/v1/graphql-sub"
+        );
+
+        let err = HttpTransport::new("http://[::1").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn server_sent_events_are_parsed_across_chunks() -> io::Result<()> {
+        // given
+        let chunks = [
+            "data: {\"data\":",
+            " 1}\r\n\r\n: keep-alive\n\n",
+            "data: {\"data\": 2}\n\ndata: {",
+        ];
+
+        // when
+        let events: Vec<_> = event_data(futures::stream::iter(chunks.map(Ok::<_, io::Error>)))
+            .collect()
+            .await;
+
+        // then
+        assert_eq!(
+            events.into_iter().collect::<io::Result<Vec<_>>>()?,
+            [json!({"data": 1}), json!({"data": 2})]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn recordings_are_replayed_in_order() -> io::Result<()> {
+        // given
+        let recorded = RecordedTransport::new(Recording {
+            url: "http://localhost:4000".to_string(),
+            exchanges: vec![
+                RecordedExchange::new("Health", Value::Null, json!({"data": {"health": false}})),
+                RecordedExchange::new("Health", Value::Null, json!({"data": {"health": true}})),
+            ],
+        });
+        let transport = RecordingTransport::new(recorded);
+
+        // when
+        let mut responses = vec![];
+        for _ in 0..3 {
+            responses.push(transport.query(request("Health", Value::Null)).await?);
+        }
+        let missing = transport
+            .query(request("Health", json!({"id": "0x01"})))
+            .await;
+
+        // then
+        assert_eq!(
+            responses,
+            [
+                json!({"data": {"health": false}}),
+                json!({"data": {"health": true}}),
+                json!({"data": {"health": true}}),
+            ]
+        );
+        assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        let recording = transport.recording();
+        assert_eq!(recording.url, "http://localhost:4000");
+        assert_eq!(recording.exchanges.len(), 3);
+        assert_eq!(
+            recording.exchanges[0].responses,
+            [json!({"data": {"health": false}})]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn subscriptions_are_recorded_as_they_are_consumed() -> io::Result<()> {
+        // given
+        let responses = vec![
+            json!({"data": {"status": 1}}),
+            json!({"data": {"status": 2}}),
+        ];
+        let recorded = RecordedTransport::new(Recording {
+            url: String::new(),
+            exchanges: vec![RecordedExchange {
+                operation_name: Some("StatusChange".to_string()),
+                variables: Value::Null,
+                responses: responses.clone(),
+            }],
+        });
+        let transport = RecordingTransport::new(recorded);
+
+        // when
+        let stream = transport
+            .subscribe(request("StatusChange", Value::Null))
+            .await?;
+        let received: Vec<_> = stream.collect().await;
+
+        // then
+        assert_eq!(
+            received.into_iter().collect::<io::Result<Vec<_>>>()?,
+            responses
+        );
+        assert_eq!(transport.recording().exchanges[0].responses, responses);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Timers and background tasks of the provider, run by tokio natively and by the event loop of
+//! the browser on `wasm32`.
+//!
+//! Futures and streams are `Send` natively. On `wasm32` they hold the JS values of `fetch`, which
+//! can't leave the browser thread.
+
+use std::{future::Future, time::Duration};
+
+use futures::Stream;
+pub(crate) use web_time::Instant;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send> MaybeSend for T {}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T {}
+
+/// A boxed stream, `Send` unless on `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxStream<'a, T> = futures::stream::BoxStream<'a, T>;
+/// A boxed stream, `Send` unless on `wasm32`.
+#[cfg(target_arch = "wasm32")]
+pub type BoxStream<'a, T> = futures::stream::LocalBoxStream<'a, T>;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type BoxFuture<'a, T> = futures::future::BoxFuture<'a, T>;
+#[cfg(target_arch = "wasm32")]
+pub(crate) type BoxFuture<'a, T> = futures::future::LocalBoxFuture<'a, T>;
+
+pub(crate) fn boxed_stream<'a, S>(stream: S) -> BoxStream<'a, S::Item>
+where
+    S: Stream + MaybeSend + 'a,
+{
+    Box::pin(stream)
+}
+
+pub(crate) fn boxed_future<'a, F>(future: F) -> BoxFuture<'a, F::Output>
+where
+    F: Future + MaybeSend + 'a,
+{
+    Box::pin(future)
+}
+
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Returns `None` if `future` didn't complete within `duration`.
+pub(crate) async fn timeout<T>(duration: Duration, future: impl Future<Output = T>) -> Option<T> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return tokio::time::timeout(duration, future).await.ok();
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        use futures::future::{Either, select};
+
+        let future = std::pin::pin!(future);
+        let timer = std::pin::pin!(sleep(duration));
+        match select(future, timer).await {
+            Either::Left((output, _)) => Some(output),
+            Either::Right(_) => None,
+        }
+    }
+}
+
+pub(crate) fn spawn(task: impl Future<Output = ()> + MaybeSend + 'static) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::spawn(task);
+
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(task);
+}
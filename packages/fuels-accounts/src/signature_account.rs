@@ -0,0 +1,408 @@
+use async_trait::async_trait;
+use fuel_asm::{GTFArgs, Instruction, RegId, op};
+use fuel_crypto::{Hasher, Message, Signature};
+use fuels_core::{
+    Configurable, Configurables,
+    traits::Signer,
+    types::{
+        Address, AssetId,
+        coin_type_id::CoinTypeId,
+        errors::{Result, error},
+        input::Input,
+        transaction_builders::TransactionBuilder,
+    },
+};
+
+use crate::{
+    Account, ViewOnlyAccount,
+    coin_selection::CoinSelector,
+    predicate::Predicate,
+    provider::Provider,
+    signers::{ed25519::Ed25519Signer, secp256r1::Secp256r1Signer},
+};
+
+// Number of instructions of each predicate, its public key is stored right after them
+const SECP256R1_INSTRUCTIONS: usize = 16;
+const ED25519_INSTRUCTIONS: usize = 14;
+
+/// The signature schemes, besides secp256k1, that the VM can verify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureScheme {
+    /// ECDSA over the P-256 curve, verified with `ECR1`.
+    Secp256r1,
+    /// EdDSA over Curve25519, verified with `ED19`.
+    Ed25519,
+}
+
+impl SignatureScheme {
+    pub fn public_key_len(&self) -> usize {
+        match self {
+            Self::Secp256r1 => 64,
+            Self::Ed25519 => 32,
+        }
+    }
+
+    fn public_key_offset(&self) -> usize {
+        let instructions = match self {
+            Self::Secp256r1 => SECP256R1_INSTRUCTIONS,
+            Self::Ed25519 => ED25519_INSTRUCTIONS,
+        };
+
+        instructions * Instruction::SIZE
+    }
+
+    fn instructions(&self) -> Vec<Instruction> {
+        // registers
+        let key = 0x10;
+        let num_witnesses = 0x11;
+        let len = 0x12;
+        let i = 0x13;
+        let valid = 0x14;
+        let tmp = 0x15;
+        let witness = 0x16;
+        let buf = 0x17;
+
+        let key_offset = self.public_key_offset() as u16;
+
+        let instructions = match self {
+            Self::Secp256r1 => {
+                // absolute instruction indexes used as jump targets
+                let witness_loop = 7;
+                let done = 15;
+
+                vec![
+                    // reserve memory for the recovered public key
+                    op::move_(buf, RegId::SP),
+                    op::cfei(64),
+                    op::addi(key, RegId::IS, key_offset),
+                    op::gtf_args(num_witnesses, RegId::ZERO, GTFArgs::TxWitnessesCount),
+                    op::movi(len, 64),
+                    op::move_(i, RegId::ZERO),
+                    op::move_(valid, RegId::ZERO),
+                    // witness_loop: recover the signer of witness `i`, the tx id is at the start
+                    // of memory
+                    op::eq(tmp, i, num_witnesses),
+                    op::jnzi(tmp, done),
+                    op::gtf_args(witness, i, GTFArgs::WitnessData),
+                    op::ecr1(buf, witness, RegId::ZERO),
+                    op::meq(tmp, buf, key, len),
+                    op::or(valid, valid, tmp),
+                    op::addi(i, i, 1),
+                    op::ji(witness_loop),
+                    // done
+                    op::ret(valid),
+                ]
+            }
+            Self::Ed25519 => {
+                // absolute instruction indexes used as jump targets
+                let witness_loop = 5;
+                let done = 13;
+
+                vec![
+                    op::addi(key, RegId::IS, key_offset),
+                    op::gtf_args(num_witnesses, RegId::ZERO, GTFArgs::TxWitnessesCount),
+                    op::movi(len, 32),
+                    op::move_(i, RegId::ZERO),
+                    op::move_(valid, RegId::ZERO),
+                    // witness_loop: verify witness `i` against the tx id at the start of memory
+                    op::eq(tmp, i, num_witnesses),
+                    op::jnzi(tmp, done),
+                    op::gtf_args(witness, i, GTFArgs::WitnessData),
+                    op::ed19(key, witness, RegId::ZERO, len),
+                    op::eq(tmp, RegId::ERR, RegId::ZERO),
+                    op::or(valid, valid, tmp),
+                    op::addi(i, i, 1),
+                    op::ji(witness_loop),
+                    // done
+                    op::ret(valid),
+                ]
+            }
+        };
+        debug_assert_eq!(
+            instructions.len() * Instruction::SIZE,
+            self.public_key_offset()
+        );
+
+        instructions
+    }
+}
+
+/// Returns the bytecode of the predicate checking signatures of the given scheme, to be
+/// configured with [`SignaturePredicateConfigurables`].
+///
+/// The predicate succeeds if any witness of the transaction holds a signature of the transaction
+/// id by the configured public key. Like the
+/// [multisig predicate](crate::multisig::multisig_predicate_code), its gas does not depend on
+/// which witnesses hold valid signatures.
+pub fn signature_predicate_code(scheme: SignatureScheme) -> Vec<u8> {
+    scheme
+        .instructions()
+        .into_iter()
+        .flat_map(Instruction::to_bytes)
+        .chain(std::iter::repeat_n(0, scheme.public_key_len()))
+        .collect()
+}
+
+/// The public key whose signatures the predicate of [`signature_predicate_code`] accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignaturePredicateConfigurables {
+    scheme: SignatureScheme,
+    public_key: Vec<u8>,
+}
+
+impl SignaturePredicateConfigurables {
+    pub fn new(scheme: SignatureScheme, public_key: impl AsRef<[u8]>) -> Result<Self> {
+        let public_key = public_key.as_ref().to_vec();
+        if public_key.len() != scheme.public_key_len() {
+            return Err(error!(
+                Other,
+                "{scheme:?} public keys must be {} bytes long, got {}",
+                scheme.public_key_len(),
+                public_key.len()
+            ));
+        }
+
+        Ok(Self { scheme, public_key })
+    }
+
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// The configured predicate, e.g. to get its address from a public key alone.
+    pub fn predicate(&self) -> Predicate {
+        Predicate::from_code(signature_predicate_code(self.scheme)).with_configurables(self.clone())
+    }
+}
+
+impl From<SignaturePredicateConfigurables> for Configurables {
+    fn from(config: SignaturePredicateConfigurables) -> Self {
+        Configurables::new(vec![Configurable {
+            offset: config.scheme.public_key_offset() as u64,
+            data: config.public_key,
+        }])
+    }
+}
+
+/// Signs for the predicate of [`signature_predicate_code`].
+///
+/// Unlike a [`Signer`], it has no address able to own coins, its funds are held by the predicate
+/// of a [`SignatureAccount`].
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait PredicateSigner: Clone + Send + Sync + 'static {
+    const SCHEME: SignatureScheme;
+
+    fn public_key_bytes(&self) -> Vec<u8>;
+
+    async fn sign(&self, message: Message) -> Result<Signature>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl PredicateSigner for Secp256r1Signer {
+    const SCHEME: SignatureScheme = SignatureScheme::Secp256r1;
+
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().to_vec()
+    }
+
+    async fn sign(&self, message: Message) -> Result<Signature> {
+        self.sign_message(message)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl PredicateSigner for Ed25519Signer {
+    const SCHEME: SignatureScheme = SignatureScheme::Ed25519;
+
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().to_vec()
+    }
+
+    async fn sign(&self, message: Message) -> Result<Signature> {
+        Ok(self.sign_message(message))
+    }
+}
+
+/// Adds the signatures of a [`PredicateSigner`] as witnesses of a transaction.
+///
+/// Its address, the hash of the public key, only tells it apart from the other signers of the
+/// transaction builder and owns no inputs.
+struct WitnessSigner<S> {
+    signer: S,
+    address: Address,
+}
+
+impl<S: PredicateSigner> WitnessSigner<S> {
+    fn new(signer: S) -> Self {
+        let address = Address::from(*Hasher::hash(signer.public_key_bytes()));
+
+        Self { signer, address }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: PredicateSigner> Signer for WitnessSigner<S> {
+    async fn sign(&self, message: Message) -> Result<Signature> {
+        self.signer.sign(message).await
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+}
+
+/// An account holding its funds in the signature predicate of its signer's public key.
+///
+/// Transactions built through the [`Account`] methods are signed by the signer once built, its
+/// signature being added as a witness for the predicate to find.
+#[derive(Debug, Clone)]
+pub struct SignatureAccount<S> {
+    predicate: Predicate,
+    signer: S,
+}
+
+impl<S: PredicateSigner> SignatureAccount<S> {
+    pub fn new(signer: S) -> Self {
+        let config = SignaturePredicateConfigurables::new(S::SCHEME, signer.public_key_bytes())
+            .expect("signers return public keys of their scheme's length");
+
+        Self {
+            predicate: config.predicate(),
+            signer,
+        }
+    }
+
+    pub fn with_provider(mut self, provider: Provider) -> Self {
+        self.predicate.set_provider(provider);
+        self
+    }
+
+    pub fn set_provider(&mut self, provider: Provider) {
+        self.predicate.set_provider(provider);
+    }
+
+    pub fn with_coin_selector(mut self, coin_selector: impl CoinSelector + 'static) -> Self {
+        self.predicate.set_coin_selector(coin_selector);
+        self
+    }
+
+    pub fn signer(&self) -> &S {
+        &self.signer
+    }
+
+    pub fn predicate(&self) -> &Predicate {
+        &self.predicate
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: PredicateSigner> ViewOnlyAccount for SignatureAccount<S> {
+    fn address(&self) -> Address {
+        self.predicate.address()
+    }
+
+    fn try_provider(&self) -> Result<&Provider> {
+        self.predicate.try_provider()
+    }
+
+    fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+        self.predicate.coin_selector()
+    }
+
+    async fn get_asset_inputs_for_amount(
+        &self,
+        asset_id: AssetId,
+        amount: u128,
+        excluded_coins: Option<Vec<CoinTypeId>>,
+    ) -> Result<Vec<Input>> {
+        self.predicate
+            .get_asset_inputs_for_amount(asset_id, amount, excluded_coins)
+            .await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: PredicateSigner> Account for SignatureAccount<S> {
+    fn add_witnesses<Tb: TransactionBuilder>(&self, tb: &mut Tb) -> Result<()> {
+        tb.add_signer(WitnessSigner::new(self.signer.clone()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicate_test_utils::{
+        assert_code_is_pinned, predicates_pass, set_signature, tx_message, unsigned_tx,
+    };
+
+    async fn predicate_accepts(
+        account_signer: impl PredicateSigner,
+        signer: impl PredicateSigner,
+    ) -> bool {
+        let account = SignatureAccount::new(account_signer);
+        let mut tx = unsigned_tx(account.predicate(), 2);
+
+        let signature = signer.sign(tx_message(&tx)).await.unwrap();
+        set_signature(&mut tx, 1, signature);
+
+        predicates_pass(tx)
+    }
+
+    #[tokio::test]
+    async fn secp256r1_predicate_requires_a_signature_of_its_key() -> Result<()> {
+        let signer = Secp256r1Signer::from_bytes(&[1; 32])?;
+        let other = Secp256r1Signer::from_bytes(&[2; 32])?;
+
+        assert!(predicate_accepts(signer.clone(), signer.clone()).await);
+        assert!(!predicate_accepts(signer, other).await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ed25519_predicate_requires_a_signature_of_its_key() -> Result<()> {
+        let signer = Ed25519Signer::from_bytes(&[1; 32])?;
+        let other = Ed25519Signer::from_bytes(&[2; 32])?;
+
+        assert!(predicate_accepts(signer.clone(), signer.clone()).await);
+        assert!(!predicate_accepts(signer, other).await);
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_code_is_pinned() {
+        assert_code_is_pinned(
+            signature_predicate_code(SignatureScheme::Secp256r1),
+            "932fe99d7ad94af8d28a98e8654f8853deb3b43cea26a0db79196c805c4873f7",
+        );
+        assert_code_is_pinned(
+            signature_predicate_code(SignatureScheme::Ed25519),
+            "25698adff03c40856b2e9106bdbef982adf851e153dd8338443fa205acad1f13",
+        );
+    }
+
+    #[test]
+    fn public_keys_must_match_the_scheme() {
+        assert!(SignaturePredicateConfigurables::new(SignatureScheme::Ed25519, [1; 64]).is_err());
+        assert!(SignaturePredicateConfigurables::new(SignatureScheme::Secp256r1, [1; 32]).is_err());
+
+        let r1 = SignaturePredicateConfigurables::new(SignatureScheme::Secp256r1, [1; 64])
+            .unwrap()
+            .predicate();
+        let ed = SignaturePredicateConfigurables::new(SignatureScheme::Ed25519, [1; 32])
+            .unwrap()
+            .predicate();
+        assert_ne!(r1.address(), ed.address());
+    }
+}
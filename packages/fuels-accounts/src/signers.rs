@@ -4,8 +4,20 @@ pub mod derivation {
     pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/1179993420'/0'/0/0";
 }
 
-#[cfg(any(feature = "signer-aws-kms", feature = "signer-google-kms"))]
+#[cfg(any(
+    feature = "signer-aws-kms",
+    feature = "signer-google-kms",
+    feature = "signer-pkcs11",
+    feature = "signer-vault"
+))]
 pub mod kms;
 
 pub mod fake;
+pub mod message;
 pub mod private_key;
+
+// These keys only sign through the predicate of a `SignatureAccount`
+#[cfg(feature = "std")]
+pub mod ed25519;
+#[cfg(feature = "std")]
+pub mod secp256r1;
@@ -0,0 +1,79 @@
+use ed25519_dalek::{Signer as _, SigningKey};
+use fuel_crypto::{Message, Signature};
+use fuels_core::types::{
+    Bytes32,
+    errors::{Result, error},
+};
+use rand::{CryptoRng, RngCore};
+
+/// Signs with an Ed25519 key, e.g. one imported from another chain.
+///
+/// The message is signed as is, which on-chain can be checked with `ED19`. Unlike secp256k1 keys,
+/// these keys cannot own coins directly, so the signer is not a
+/// [`Signer`](fuels_core::traits::Signer): funds are held by the predicate of a
+/// [`SignatureAccount`](crate::signature_account::SignatureAccount), which signs through
+/// [`PredicateSigner`](crate::signature_account::PredicateSigner).
+#[derive(Clone)]
+pub struct Ed25519Signer {
+    signing_key: SigningKey,
+}
+
+impl std::fmt::Debug for Ed25519Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ed25519Signer")
+            .field("private_key", &"REDACTED")
+            .field("public_key", &self.public_key())
+            .finish()
+    }
+}
+
+impl Ed25519Signer {
+    pub fn new(signing_key: SigningKey) -> Self {
+        Self { signing_key }
+    }
+
+    pub fn from_bytes(private_key: &[u8]) -> Result<Self> {
+        let secret_key = private_key
+            .try_into()
+            .map_err(|_| error!(Other, "ed25519 private keys must be 32 bytes long"))?;
+
+        Ok(Self::new(SigningKey::from_bytes(secret_key)))
+    }
+
+    pub fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        let mut secret_key = [0; 32];
+        rng.fill_bytes(&mut secret_key);
+
+        Self::new(SigningKey::from_bytes(&secret_key))
+    }
+
+    pub fn public_key(&self) -> Bytes32 {
+        Bytes32::new(self.signing_key.verifying_key().to_bytes())
+    }
+
+    pub(crate) fn sign_message(&self, message: Message) -> Signature {
+        let signature = self.signing_key.sign(message.as_ref());
+
+        Signature::from_bytes(signature.to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels_core::types::Bytes64;
+
+    use super::*;
+
+    #[test]
+    fn signatures_verify_against_the_public_key() -> Result<()> {
+        let signer = Ed25519Signer::from_bytes(&[3; 32])?;
+        let message = Message::new("ed25519");
+
+        let signature = Bytes64::from(*signer.sign_message(message));
+
+        fuel_crypto::ed25519::verify(&signer.public_key(), &signature, message.as_ref())?;
+        assert!(fuel_crypto::ed25519::verify(&signer.public_key(), &signature, &[0; 32]).is_err());
+
+        Ok(())
+    }
+}
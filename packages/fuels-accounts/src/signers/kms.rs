@@ -2,6 +2,10 @@
 pub mod aws;
 #[cfg(feature = "signer-google-kms")]
 pub mod google;
+#[cfg(feature = "signer-pkcs11")]
+pub mod pkcs11;
+#[cfg(feature = "signer-vault")]
+pub mod vault;
 
 mod signature_utils {
     use fuel_crypto::{Message, Signature};
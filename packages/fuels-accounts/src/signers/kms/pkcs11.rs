@@ -0,0 +1,290 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+pub use cryptoki;
+use cryptoki::{
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::Session,
+};
+use fuel_crypto::{Message, PublicKey, Signature};
+use fuels_core::{
+    traits::Signer,
+    types::{
+        Address,
+        errors::{Error, Result},
+    },
+};
+use k256::{PublicKey as K256PublicKey, ecdsa::Signature as K256Signature};
+
+use super::signature_utils;
+
+const PKCS11_ERROR_PREFIX: &str = "PKCS#11 Error";
+
+/// DER encoding of the secp256k1 curve OID (1.3.132.0.10), as found in `CKA_EC_PARAMS`.
+const SECP256K1_EC_PARAMS: [u8; 7] = [0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+const UNCOMPRESSED_POINT_LEN: usize = 65;
+
+/// Signs with a secp256k1 key held by a PKCS#11 token, such as an HSM or SoftHSM.
+///
+/// The key pair is looked up by its `CKA_LABEL` in the given session, which must already be
+/// logged in as a user allowed to sign with it.
+#[derive(Clone, Debug)]
+pub struct Pkcs11Signer {
+    key_label: String,
+    session: Arc<Mutex<Session>>,
+    private_key: ObjectHandle,
+    public_key: K256PublicKey,
+    fuel_address: Address,
+}
+
+impl Pkcs11Signer {
+    pub fn new(key_label: impl Into<String>, session: Session) -> Result<Self> {
+        let key_label: String = key_label.into();
+        let private_key = find_key(&session, &key_label, ObjectClass::PRIVATE_KEY)?;
+        let public_key = Self::retrieve_public_key(&session, &key_label)?;
+        let fuel_address = Address::from(*PublicKey::from(public_key).hash());
+
+        Ok(Self {
+            key_label,
+            session: Arc::new(Mutex::new(session)),
+            private_key,
+            public_key,
+            fuel_address,
+        })
+    }
+
+    fn retrieve_public_key(session: &Session, key_label: &str) -> Result<K256PublicKey> {
+        let handle = find_key(session, key_label, ObjectClass::PUBLIC_KEY)?;
+        let attributes = session
+            .get_attributes(handle, &[AttributeType::EcParams, AttributeType::EcPoint])
+            .map_err(format_pkcs11_error)?;
+
+        let mut ec_params = None;
+        let mut ec_point = None;
+        for attribute in attributes {
+            match attribute {
+                Attribute::EcParams(params) => ec_params = Some(params),
+                Attribute::EcPoint(point) => ec_point = Some(point),
+                _ => {}
+            }
+        }
+
+        if ec_params.as_deref() != Some(SECP256K1_EC_PARAMS.as_slice()) {
+            return Err(Error::Other(format!(
+                "{PKCS11_ERROR_PREFIX}: Invalid key type, expected a secp256k1 key"
+            )));
+        }
+
+        let ec_point = ec_point.ok_or_else(|| {
+            Error::Other(format!("{PKCS11_ERROR_PREFIX}: Missing public key point"))
+        })?;
+
+        parse_ec_point(&ec_point)
+    }
+
+    /// Signs on a blocking thread, PKCS#11 calls block until the token answers.
+    async fn request_hsm_signature(&self, message: Message) -> Result<Vec<u8>> {
+        let session = Arc::clone(&self.session);
+        let private_key = self.private_key;
+
+        tokio::task::spawn_blocking(move || {
+            let session = session.lock().map_err(|_| {
+                Error::Other(format!("{PKCS11_ERROR_PREFIX}: Session lock poisoned"))
+            })?;
+
+            session
+                .sign(&Mechanism::Ecdsa, private_key, message.as_ref())
+                .map_err(|e| Error::Other(format!("{PKCS11_ERROR_PREFIX}: Signing failed - {e}")))
+        })
+        .await
+        .map_err(|e| Error::Other(format!("{PKCS11_ERROR_PREFIX}: Signing task failed - {e}")))?
+    }
+
+    pub fn key_label(&self) -> &String {
+        &self.key_label
+    }
+
+    pub fn public_key(&self) -> &K256PublicKey {
+        &self.public_key
+    }
+}
+
+#[async_trait]
+impl Signer for Pkcs11Signer {
+    async fn sign(&self, message: Message) -> Result<Signature> {
+        // `CKM_ECDSA` produces the raw `r || s` encoding rather than DER
+        let signature_raw = self.request_hsm_signature(message).await?;
+
+        let signature_der = K256Signature::from_slice(&signature_raw)
+            .map_err(|_| Error::Other(format!("{PKCS11_ERROR_PREFIX}: Invalid signature")))?
+            .to_der();
+
+        let (normalized_sig, recovery_id) = signature_utils::normalize_signature(
+            signature_der.as_bytes(),
+            message,
+            &self.public_key,
+            PKCS11_ERROR_PREFIX,
+        )?;
+
+        Ok(signature_utils::convert_to_fuel_signature(
+            normalized_sig,
+            recovery_id,
+        ))
+    }
+
+    fn address(&self) -> Address {
+        self.fuel_address
+    }
+}
+
+fn find_key(session: &Session, key_label: &str, class: ObjectClass) -> Result<ObjectHandle> {
+    let template = [
+        Attribute::Class(class),
+        Attribute::KeyType(KeyType::EC),
+        Attribute::Label(key_label.as_bytes().to_vec()),
+    ];
+
+    let mut handles = session
+        .find_objects(&template)
+        .map_err(format_pkcs11_error)?;
+
+    match handles.len() {
+        1 => Ok(handles.remove(0)),
+        0 => Err(Error::Other(format!(
+            "{PKCS11_ERROR_PREFIX}: No {class} found with label `{key_label}`"
+        ))),
+        n => Err(Error::Other(format!(
+            "{PKCS11_ERROR_PREFIX}: {n} objects of class {class} found with label `{key_label}`"
+        ))),
+    }
+}
+
+/// `CKA_EC_POINT` is specified as a DER `OCTET STRING` holding the point, but some modules
+/// return the bare point instead.
+///
+/// Both start with `0x04`, so they are told apart by their exact length.
+fn parse_ec_point(ec_point: &[u8]) -> Result<K256PublicKey> {
+    let point = match ec_point {
+        // Tag and length of an `OCTET STRING` holding an uncompressed point
+        [0x04, 0x41, point @ ..] if point.len() == UNCOMPRESSED_POINT_LEN => point,
+        point if point.len() == UNCOMPRESSED_POINT_LEN => point,
+        _ => {
+            return Err(Error::Other(format!(
+                "{PKCS11_ERROR_PREFIX}: Expected an uncompressed public key point"
+            )));
+        }
+    };
+
+    K256PublicKey::from_sec1_bytes(point)
+        .map_err(|_| Error::Other(format!("{PKCS11_ERROR_PREFIX}: Invalid public key point")))
+}
+
+fn format_pkcs11_error(err: impl std::fmt::Display) -> Error {
+    Error::Other(format!("{PKCS11_ERROR_PREFIX}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptoki::{
+        context::{CInitializeArgs, CInitializeFlags, Pkcs11},
+        session::UserType,
+        types::AuthPin,
+    };
+    use fuel_crypto::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn parses_wrapped_and_bare_ec_points() -> Result<()> {
+        let secret_key = SecretKey::try_from([7; 32].as_slice()).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+
+        let bare = [[0x04].as_slice(), public_key.as_ref()].concat();
+        let wrapped = [[0x04, 0x41].as_slice(), &bare].concat();
+
+        for ec_point in [bare, wrapped] {
+            assert_eq!(PublicKey::from(parse_ec_point(&ec_point)?), public_key);
+        }
+
+        let compressed = [[0x02].as_slice(), &public_key.as_ref()[..32]].concat();
+        assert!(parse_ec_point(&compressed).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn bare_ec_points_resembling_a_der_header_are_not_unwrapped() -> Result<()> {
+        // given a key whose X coordinate starts with `0x3f`, so that the bare point starts like
+        // an `OCTET STRING` of 63 bytes
+        let public_key = (1..u32::MAX)
+            .map(|idx| {
+                let mut secret_key = [0; 32];
+                secret_key[28..].copy_from_slice(&idx.to_be_bytes());
+                PublicKey::from(&SecretKey::try_from(secret_key.as_slice()).unwrap())
+            })
+            .find(|public_key| public_key.as_ref()[0] == 0x3f)
+            .expect("one of the keys should start with `0x3f`");
+        let bare = [[0x04].as_slice(), public_key.as_ref()].concat();
+
+        // when
+        let parsed = parse_ec_point(&bare)?;
+
+        // then
+        assert_eq!(PublicKey::from(parsed), public_key);
+
+        Ok(())
+    }
+
+    /// Requires SoftHSM, e.g. `TEST_PKCS11_MODULE=/usr/lib/softhsm/libsofthsm2.so`.
+    #[tokio::test]
+    #[ignore = "requires a PKCS#11 module"]
+    async fn signs_with_a_softhsm_key() -> Result<()> {
+        let pkcs11 = Pkcs11::new(std::env::var("TEST_PKCS11_MODULE").unwrap()).unwrap();
+        pkcs11
+            .initialize(CInitializeArgs::new(CInitializeFlags::OS_LOCKING_OK))
+            .unwrap();
+
+        let slot = pkcs11.get_slots_with_token().unwrap().remove(0);
+        let so_pin = AuthPin::new("abcdef654321".into());
+        let user_pin = AuthPin::new("fedcba123456".into());
+        pkcs11.init_token(slot, &so_pin, "fuels").unwrap();
+        {
+            let session = pkcs11.open_rw_session(slot).unwrap();
+            session.login(UserType::So, Some(&so_pin)).unwrap();
+            session.init_pin(&user_pin).unwrap();
+        }
+
+        let session = pkcs11.open_rw_session(slot).unwrap();
+        session.login(UserType::User, Some(&user_pin)).unwrap();
+        let label = b"fuel-key".to_vec();
+        session
+            .generate_key_pair(
+                &Mechanism::EccKeyPairGen,
+                &[
+                    Attribute::Token(true),
+                    Attribute::Verify(true),
+                    Attribute::EcParams(SECP256K1_EC_PARAMS.to_vec()),
+                    Attribute::Label(label.clone()),
+                ],
+                &[
+                    Attribute::Token(true),
+                    Attribute::Sign(true),
+                    Attribute::Sensitive(true),
+                    Attribute::Label(label),
+                ],
+            )
+            .unwrap();
+
+        let signer = Pkcs11Signer::new("fuel-key", session)?;
+        let expected_public_key = PublicKey::from(*signer.public_key());
+        assert_eq!(signer.address(), Address::from(*expected_public_key.hash()));
+
+        let message = Message::new("pkcs11");
+        let signature = signer.sign(message).await?;
+        assert_eq!(signature.recover(&message)?, expected_public_key);
+
+        Ok(())
+    }
+}
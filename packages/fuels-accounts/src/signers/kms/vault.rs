@@ -0,0 +1,447 @@
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use fuel_crypto::{Message, PublicKey, Signature};
+use fuels_core::{
+    traits::Signer,
+    types::{
+        Address,
+        errors::{Error, Result},
+    },
+};
+use k256::{PublicKey as K256PublicKey, pkcs8::DecodePublicKey};
+pub use reqwest;
+use reqwest::{RequestBuilder, Response};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::json;
+use std::collections::HashMap;
+
+use super::signature_utils;
+
+const VAULT_ERROR_PREFIX: &str = "Vault Error";
+
+const DEFAULT_MOUNT: &str = "transit";
+
+/// Connection to a HashiCorp Vault server, used to create [`VaultSigner`]s.
+#[derive(Clone)]
+pub struct VaultClient {
+    http: reqwest::Client,
+    address: String,
+    token: String,
+    namespace: Option<String>,
+    mount: String,
+}
+
+impl std::fmt::Debug for VaultClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VaultClient")
+            .field("address", &self.address)
+            .field("token", &"REDACTED")
+            .field("namespace", &self.namespace)
+            .field("mount", &self.mount)
+            .finish()
+    }
+}
+
+impl VaultClient {
+    /// `address` is the base URL of the server, e.g. `https://vault.internal:8200`.
+    pub fn new(address: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            address: address.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            namespace: None,
+            mount: DEFAULT_MOUNT.to_string(),
+        }
+    }
+
+    /// Path the Transit secrets engine is mounted at, `transit` by default.
+    pub fn with_mount(mut self, mount: impl Into<String>) -> Self {
+        self.mount = mount.into().trim_matches('/').to_string();
+        self
+    }
+
+    /// Vault Enterprise namespace sent with every request.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Use a preconfigured HTTP client, e.g. one trusting a private certificate authority.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Builds a request to the Transit endpoint at `path`, percent-encoding each of its segments.
+    fn request(&self, method: reqwest::Method, path: &[&str]) -> Result<RequestBuilder> {
+        let mut url = reqwest::Url::parse(&format!("{}/v1/{}/", self.address, self.mount))
+            .map_err(|e| format_vault_error(format!("Invalid address - {e}")))?;
+        url.path_segments_mut()
+            .map_err(|_| format_vault_error("Invalid address - cannot be a base URL"))?
+            .pop_if_empty()
+            .extend(path);
+
+        let request = self
+            .http
+            .request(method, url)
+            .header("X-Vault-Token", &self.token);
+
+        Ok(match &self.namespace {
+            Some(namespace) => request.header("X-Vault-Namespace", namespace),
+            None => request,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct VaultResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct KeyData {
+    #[serde(rename = "type")]
+    key_type: String,
+    latest_version: u32,
+    keys: HashMap<String, KeyVersion>,
+}
+
+#[derive(Deserialize)]
+struct KeyVersion {
+    public_key: String,
+}
+
+#[derive(Deserialize)]
+struct SignData {
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct VaultErrors {
+    errors: Vec<String>,
+}
+
+/// Signs with a secp256k1 key held by the Transit secrets engine of a HashiCorp Vault server.
+///
+/// The Transit engine shipped with Vault has no secp256k1 key type. The signer needs a Transit
+/// compatible secrets engine, such as a plugin or a fork of Transit, that serves secp256k1 keys
+/// with the same `keys/:name` and `sign/:name` endpoints and returns their public key PEM
+/// encoded. It has to be mounted at the path given by [`VaultClient::with_mount`].
+///
+/// The signer is pinned to the latest version of the key at creation, so rotating the key in
+/// Vault does not change its address.
+#[derive(Clone, Debug)]
+pub struct VaultSigner {
+    key_name: String,
+    key_version: u32,
+    client: VaultClient,
+    public_key_pem: String,
+    fuel_address: Address,
+}
+
+impl VaultSigner {
+    pub async fn new(key_name: impl Into<String>, client: &VaultClient) -> Result<Self> {
+        let key_name: String = key_name.into();
+        let (key_version, public_key_pem) = Self::retrieve_public_key(client, &key_name).await?;
+        let fuel_address = Self::derive_fuel_address(&public_key_pem)?;
+
+        Ok(Self {
+            key_name,
+            key_version,
+            client: client.clone(),
+            public_key_pem,
+            fuel_address,
+        })
+    }
+
+    async fn retrieve_public_key(client: &VaultClient, key_name: &str) -> Result<(u32, String)> {
+        let request = client.request(reqwest::Method::GET, &["keys", key_name])?;
+        let key: KeyData = send(request).await?;
+
+        let public_key = key
+            .keys
+            .get(&key.latest_version.to_string())
+            .map(|version| version.public_key.clone())
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "{VAULT_ERROR_PREFIX}: No public key for version {} of key `{key_name}`",
+                    key.latest_version
+                ))
+            })?;
+
+        if K256PublicKey::from_public_key_pem(&public_key).is_err() {
+            return Err(Error::Other(format!(
+                "{VAULT_ERROR_PREFIX}: Invalid key type {}, expected a secp256k1 key",
+                key.key_type
+            )));
+        }
+
+        Ok((key.latest_version, public_key))
+    }
+
+    fn derive_fuel_address(pem: &str) -> Result<Address> {
+        let k256_key = K256PublicKey::from_public_key_pem(pem)
+            .map_err(|_| Error::Other(format!("{VAULT_ERROR_PREFIX}: Invalid PEM encoding")))?;
+
+        let fuel_public_key = PublicKey::from(k256_key);
+
+        Ok(Address::from(*fuel_public_key.hash()))
+    }
+
+    async fn request_vault_signature(&self, message: Message) -> Result<Vec<u8>> {
+        let request = self
+            .client
+            .request(reqwest::Method::POST, &["sign", &self.key_name])?
+            .json(&json!({
+                "input": BASE64.encode(*message),
+                "key_version": self.key_version,
+                "prehashed": true,
+                "hash_algorithm": "sha2-256",
+                "marshaling_algorithm": "asn1",
+            }));
+        let response: SignData = send(request).await?;
+
+        // Signatures are formatted as `vault:v{key_version}:{base64 signature}`
+        let signature = response
+            .signature
+            .rsplit_once(':')
+            .map(|(_, signature)| signature)
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "{VAULT_ERROR_PREFIX}: Malformed signature response"
+                ))
+            })?;
+
+        BASE64.decode(signature).map_err(|_| {
+            Error::Other(format!(
+                "{VAULT_ERROR_PREFIX}: Invalid base64 encoding of signature"
+            ))
+        })
+    }
+
+    pub fn key_name(&self) -> &String {
+        &self.key_name
+    }
+
+    pub fn key_version(&self) -> u32 {
+        self.key_version
+    }
+
+    pub fn public_key(&self) -> &String {
+        &self.public_key_pem
+    }
+}
+
+#[async_trait]
+impl Signer for VaultSigner {
+    async fn sign(&self, message: Message) -> Result<Signature> {
+        let signature_der = self.request_vault_signature(message).await?;
+
+        let k256_key = K256PublicKey::from_public_key_pem(&self.public_key_pem).map_err(|_| {
+            Error::Other(format!("{VAULT_ERROR_PREFIX}: Invalid cached public key"))
+        })?;
+
+        let (normalized_sig, recovery_id) = signature_utils::normalize_signature(
+            &signature_der,
+            message,
+            &k256_key,
+            VAULT_ERROR_PREFIX,
+        )?;
+
+        Ok(signature_utils::convert_to_fuel_signature(
+            normalized_sig,
+            recovery_id,
+        ))
+    }
+
+    fn address(&self) -> Address {
+        self.fuel_address
+    }
+}
+
+async fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<T> {
+    let response = request.send().await.map_err(format_vault_error)?;
+
+    Ok(parse_response::<T>(response).await?.data)
+}
+
+async fn parse_response<T: DeserializeOwned>(response: Response) -> Result<VaultResponse<T>> {
+    let status = response.status();
+    let body = response.bytes().await.map_err(format_vault_error)?;
+
+    if !status.is_success() {
+        let reason = serde_json::from_slice::<VaultErrors>(&body)
+            .map(|errors| errors.errors.join(", "))
+            .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+
+        return Err(format_vault_error(format!("{status} - {reason}")));
+    }
+
+    serde_json::from_slice(&body)
+        .map_err(|e| format_vault_error(format!("Unexpected response - {e}")))
+}
+
+fn format_vault_error(err: impl std::fmt::Display) -> Error {
+    Error::Other(format!("{VAULT_ERROR_PREFIX}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_crypto::SecretKey;
+    use k256::{
+        ecdsa::{Signature as K256Signature, SigningKey, signature::hazmat::PrehashSigner},
+        pkcs8::{EncodePublicKey, LineEnding},
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    const TOKEN: &str = "s.test-token";
+
+    /// Serves the Transit `keys` and `sign` endpoints for a single secp256k1 key, whose name is
+    /// `key_path` once percent-encoded.
+    async fn mock_vault(signing_key: SigningKey, key_path: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let request = read_request(&mut stream).await;
+                let (status, body) = respond(&signing_key, key_path, &request);
+
+                let response = format!(
+                    "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        address
+    }
+
+    async fn read_request(stream: &mut tokio::net::TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+
+        loop {
+            let read = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..read]);
+
+            let text = String::from_utf8_lossy(&request);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let content_length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+
+                if body.len() >= content_length {
+                    return text.into_owned();
+                }
+            }
+        }
+    }
+
+    fn respond(signing_key: &SigningKey, key_path: &str, request: &str) -> (&'static str, String) {
+        if !request.contains(&format!("x-vault-token: {TOKEN}")) {
+            return (
+                "403 Forbidden",
+                json!({ "errors": ["permission denied"] }).to_string(),
+            );
+        }
+
+        let request_line = request.lines().next().unwrap();
+        if request_line.starts_with(&format!("GET /v1/transit/keys/{key_path} ")) {
+            let pem = signing_key
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap();
+            let body = json!({
+                "data": {
+                    "type": "ecdsa-secp256k1",
+                    "latest_version": 1,
+                    "keys": { "1": { "public_key": pem } },
+                }
+            });
+
+            ("200 OK", body.to_string())
+        } else if request_line.starts_with(&format!("POST /v1/transit/sign/{key_path} ")) {
+            let (_, body) = request.split_once("\r\n\r\n").unwrap();
+            let body: serde_json::Value = serde_json::from_str(body).unwrap();
+            assert_eq!(body["key_version"], 1);
+            assert_eq!(body["prehashed"], true);
+
+            let digest = BASE64.decode(body["input"].as_str().unwrap()).unwrap();
+            let signature: K256Signature = signing_key.sign_prehash(&digest).unwrap();
+            let body = json!({
+                "data": {
+                    "signature": format!("vault:v1:{}", BASE64.encode(signature.to_der())),
+                    "key_version": 1,
+                }
+            });
+
+            ("200 OK", body.to_string())
+        } else {
+            ("404 Not Found", json!({ "errors": [] }).to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn signs_with_a_transit_key() -> Result<()> {
+        let secret_key = SecretKey::try_from([7; 32].as_slice()).unwrap();
+        let signing_key = SigningKey::from_slice(secret_key.as_ref()).unwrap();
+        let address = mock_vault(signing_key, "fuel").await;
+
+        let client = VaultClient::new(address, TOKEN);
+        let signer = VaultSigner::new("fuel", &client).await?;
+
+        let expected_public_key = PublicKey::from(&secret_key);
+        assert_eq!(signer.address(), Address::from(*expected_public_key.hash()));
+        assert_eq!(signer.key_version(), 1);
+
+        let message = Message::new("vault");
+        let signature = signer.sign(message).await?;
+
+        signature.verify(&expected_public_key, &message)?;
+        assert_eq!(signature.recover(&message)?, expected_public_key);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn key_names_are_percent_encoded() -> Result<()> {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let address = mock_vault(signing_key, "team%2Ffuel%3Fkey").await;
+
+        let client = VaultClient::new(address, TOKEN);
+        let signer = VaultSigner::new("team/fuel?key", &client).await?;
+
+        signer.sign(Message::new("vault")).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn surfaces_vault_errors() {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let address = mock_vault(signing_key, "fuel").await;
+
+        let client = VaultClient::new(address, "wrong-token");
+        let err = VaultSigner::new("fuel", &client)
+            .await
+            .expect_err("should fail to fetch the key");
+
+        assert_eq!(
+            err.to_string(),
+            "Vault Error: 403 Forbidden - permission denied"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Signing of off-chain data, domain separated from transaction signatures.
+//!
+//! Personal messages are hashed as `sha256("\x19Fuel Signed Message:\n" ++ len ++ message)`, `len`
+//! being the decimal length of the message in bytes, like the Fuel wallets do. Typed data is
+//! hashed as `sha256("\x19\x01" ++ domain_separator ++ sha256(encode(data)))`, where `encode` is
+//! the ABI encoding of the value and `domain_separator` is `sha256(chain_id ++ contract_id)`, the
+//! chain id being a big-endian `u64`. Both hashes can be recomputed in Sway with `sha256` and the
+//! signer recovered with `Secp256k1::address`, as the `signed_messages` contract of the e2e tests
+//! does.
+
+use fuel_crypto::{Hasher, Message, Signature};
+use fuels_core::{
+    codec::ABIEncoder,
+    traits::{Signer, Tokenizable},
+    types::{
+        Address, Bytes32, ChainId, ContractId,
+        errors::{Result, error},
+    },
+};
+
+pub const PERSONAL_MESSAGE_PREFIX: &[u8] = b"\x19Fuel Signed Message:\n";
+pub const TYPED_DATA_PREFIX: &[u8] = b"\x19\x01";
+
+pub fn hash_personal_message(message: impl AsRef<[u8]>) -> Message {
+    let message = message.as_ref();
+
+    let hash = Hasher::default()
+        .chain(PERSONAL_MESSAGE_PREFIX)
+        .chain(message.len().to_string())
+        .chain(message)
+        .finalize();
+
+    Message::from_bytes(*hash)
+}
+
+pub async fn sign_personal_message(
+    signer: &impl Signer,
+    message: impl AsRef<[u8]>,
+) -> Result<Signature> {
+    signer.sign(hash_personal_message(message)).await
+}
+
+/// Checks that `signature` was made by `address` over the personal `message`.
+pub fn verify_personal_message(
+    address: &Address,
+    message: impl AsRef<[u8]>,
+    signature: &Signature,
+) -> Result<()> {
+    verify(address, &hash_personal_message(message), signature)
+}
+
+/// Binds typed data signatures to a chain and a contract, so that they cannot be replayed
+/// elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypedDataDomain {
+    pub chain_id: ChainId,
+    pub verifying_contract: ContractId,
+}
+
+impl TypedDataDomain {
+    pub fn new(chain_id: ChainId, verifying_contract: ContractId) -> Self {
+        Self {
+            chain_id,
+            verifying_contract,
+        }
+    }
+
+    pub fn separator(&self) -> Bytes32 {
+        Hasher::default()
+            .chain(u64::from(self.chain_id).to_be_bytes())
+            .chain(self.verifying_contract)
+            .finalize()
+    }
+}
+
+pub fn hash_typed_data(domain: &TypedDataDomain, data: impl Tokenizable) -> Result<Message> {
+    let encoded = ABIEncoder::default().encode(&[data.into_token()])?;
+
+    let hash = Hasher::default()
+        .chain(TYPED_DATA_PREFIX)
+        .chain(domain.separator())
+        .chain(Hasher::hash(encoded))
+        .finalize();
+
+    Ok(Message::from_bytes(*hash))
+}
+
+pub async fn sign_typed_data(
+    signer: &impl Signer,
+    domain: &TypedDataDomain,
+    data: impl Tokenizable,
+) -> Result<Signature> {
+    signer.sign(hash_typed_data(domain, data)?).await
+}
+
+/// Checks that `signature` was made by `address` over `data` in the given `domain`.
+pub fn verify_typed_data(
+    address: &Address,
+    domain: &TypedDataDomain,
+    data: impl Tokenizable,
+    signature: &Signature,
+) -> Result<()> {
+    verify(address, &hash_typed_data(domain, data)?, signature)
+}
+
+fn verify(address: &Address, message: &Message, signature: &Signature) -> Result<()> {
+    let public_key = signature.recover(message)?;
+    let signer = Address::from(*public_key.hash());
+
+    if &signer != address {
+        return Err(error!(
+            Other,
+            "message was signed by `{signer}` instead of `{address}`"
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use fuel_crypto::SecretKey;
+
+    use super::*;
+    use crate::signers::private_key::PrivateKeySigner;
+
+    fn signer(key: u8) -> PrivateKeySigner {
+        PrivateKeySigner::new(SecretKey::try_from([key; 32].as_slice()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn personal_messages_are_prefixed_and_verified() -> Result<()> {
+        let expected =
+            Bytes32::from_str("35e1bd14741cc97dc603593009e40496c48c73c125b7b269c753419c78f9e45f")?;
+        assert_eq!(*hash_personal_message("hello"), *expected);
+
+        // ANCHOR: personal_message
+        let signature = sign_personal_message(&signer(1), "hello").await?;
+
+        verify_personal_message(&signer(1).address(), "hello", &signature)?;
+        // ANCHOR_END: personal_message
+        assert!(verify_personal_message(&signer(1).address(), "hello!", &signature).is_err());
+        assert!(verify_personal_message(&signer(2).address(), "hello", &signature).is_err());
+
+        // a personal message never collides with the raw hash a transaction signature is made on
+        assert_ne!(hash_personal_message("hello"), Message::new("hello"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn typed_data_is_bound_to_its_domain() -> Result<()> {
+        // ANCHOR: typed_data
+        let domain = TypedDataDomain::new(ChainId::new(0), ContractId::from([1; 32]));
+        let order = (Address::from([2; 32]), 100u64, "buy".to_string());
+
+        let signature = sign_typed_data(&signer(1), &domain, order.clone()).await?;
+        verify_typed_data(&signer(1).address(), &domain, order.clone(), &signature)?;
+        // ANCHOR_END: typed_data
+
+        let other_chain = TypedDataDomain::new(ChainId::new(1), domain.verifying_contract);
+        let other_contract = TypedDataDomain::new(domain.chain_id, ContractId::from([3; 32]));
+        for domain in [other_chain, other_contract] {
+            assert!(
+                verify_typed_data(&signer(1).address(), &domain, order.clone(), &signature)
+                    .is_err()
+            );
+        }
+
+        let tampered = (order.0, 101u64, order.2);
+        assert!(verify_typed_data(&signer(1).address(), &domain, tampered, &signature).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use fuel_crypto::{Message, Signature};
+use fuels_core::types::{
+    Bytes64,
+    errors::{Result, error},
+};
+use p256::ecdsa::SigningKey;
+use rand::{CryptoRng, RngCore};
+
+/// Signs with a secp256r1 (P-256) key, such as the ones held by passkeys and secure enclaves.
+///
+/// Signatures use the same compact encoding as secp256k1 ones, with the recovery id stored in the
+/// highest bit of `s`, so they can be checked on-chain with `ECR1`. Unlike secp256k1 keys, these
+/// keys cannot own coins directly, so the signer is not a
+/// [`Signer`](fuels_core::traits::Signer): funds are held by the predicate of a
+/// [`SignatureAccount`](crate::signature_account::SignatureAccount), which signs through
+/// [`PredicateSigner`](crate::signature_account::PredicateSigner).
+#[derive(Clone)]
+pub struct Secp256r1Signer {
+    signing_key: SigningKey,
+    public_key: Bytes64,
+}
+
+impl std::fmt::Debug for Secp256r1Signer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Secp256r1Signer")
+            .field("private_key", &"REDACTED")
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
+impl Secp256r1Signer {
+    pub fn new(signing_key: SigningKey) -> Self {
+        let point = signing_key.verifying_key().to_encoded_point(false);
+        let public_key = Bytes64::try_from(&point.as_bytes()[1..])
+            .expect("uncompressed points are 65 bytes long");
+
+        Self {
+            signing_key,
+            public_key,
+        }
+    }
+
+    pub fn from_bytes(private_key: &[u8]) -> Result<Self> {
+        let signing_key = SigningKey::from_slice(private_key)
+            .map_err(|_| error!(Other, "invalid secp256r1 private key"))?;
+
+        Ok(Self::new(signing_key))
+    }
+
+    pub fn random(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        Self::new(SigningKey::random(rng))
+    }
+
+    /// The uncompressed public key, without the `0x04` prefix.
+    pub fn public_key(&self) -> Bytes64 {
+        self.public_key
+    }
+
+    pub(crate) fn sign_message(&self, message: Message) -> Result<Signature> {
+        let (signature, recovery_id) = self
+            .signing_key
+            .sign_prehash_recoverable(&*message)
+            .map_err(|e| error!(Other, "failed to sign with secp256r1 key: {e}"))?;
+
+        // negating `s` negates the nonce point as well, flipping the parity of its y coordinate
+        let (signature, is_y_odd) = match signature.normalize_s() {
+            Some(normalized) => (normalized, !recovery_id.is_y_odd()),
+            None => (signature, recovery_id.is_y_odd()),
+        };
+
+        let mut bytes: [u8; 64] = signature.to_bytes().into();
+        bytes[32] |= u8::from(is_y_odd) << 7;
+
+        Ok(Signature::from_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_recover_to_the_public_key() -> Result<()> {
+        for key in 1..=8 {
+            let signer = Secp256r1Signer::from_bytes(&[key; 32])?;
+            let message = Message::new([key]);
+
+            let signature = signer.sign_message(message)?;
+            let recovered = fuel_crypto::secp256r1::recover(&Bytes64::from(*signature), &message)?;
+
+            assert_eq!(recovered, signer.public_key());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+use std::time::Duration;
+
+use fuel_tx::TxId;
+use fuels_core::types::{
+    errors::{Context, Result, error},
+    transaction::{Transaction, TxPolicies},
+    transaction_builders::TransactionBuilder,
+    tx_response::TxResponse,
+    tx_status::TxStatus,
+};
+
+use crate::{
+    Account,
+    accounts_utils::{add_base_change_if_needed, available_base_assets_and_amount},
+    provider::Provider,
+    runtime::{self, Instant},
+};
+
+/// How the tip of a replacement transaction is raised.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TipStrategy {
+    /// Multiplies the previous tip by `factor`, raising it by at least `min_increase`.
+    Multiplier { factor: f64, min_increase: u64 },
+    /// Tips the gas price the node estimates for `block_horizon` blocks ahead for every unit of
+    /// the transaction's max gas, raising it by at least 1.
+    EstimatedGasPrice { block_horizon: u32 },
+}
+
+impl Default for TipStrategy {
+    fn default() -> Self {
+        Self::Multiplier {
+            factor: 1.5,
+            min_increase: 1,
+        }
+    }
+}
+
+impl TipStrategy {
+    /// The tip replacing `previous_tip` on a transaction consuming at most `max_gas`.
+    pub async fn next_tip(
+        &self,
+        previous_tip: u64,
+        max_gas: u64,
+        provider: &Provider,
+    ) -> Result<u64> {
+        let tip = match *self {
+            Self::Multiplier {
+                factor,
+                min_increase,
+            } => multiplied_tip(previous_tip, factor, min_increase),
+            Self::EstimatedGasPrice { block_horizon } => {
+                let gas_price = provider.estimate_gas_price(block_horizon).await?.gas_price;
+
+                gas_price
+                    .saturating_mul(max_gas)
+                    .max(previous_tip.saturating_add(1))
+            }
+        };
+
+        Ok(tip)
+    }
+}
+
+fn multiplied_tip(previous_tip: u64, factor: f64, min_increase: u64) -> u64 {
+    // float to int casts saturate
+    let multiplied = (previous_tip as f64 * factor).ceil() as u64;
+
+    multiplied.max(previous_tip.saturating_add(min_increase))
+}
+
+/// Configuration of [`Account::send_with_tip_bumping`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use fuels_accounts::tip_bump::{TipBumpConfig, TipStrategy};
+///
+/// let strategy = TipStrategy::EstimatedGasPrice { block_horizon: 2 };
+/// let config = TipBumpConfig::new(strategy, Duration::from_secs(10), 5)
+///     .unwrap()
+///     .with_max_tip(1_000_000);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TipBumpConfig {
+    strategy: TipStrategy,
+    resubmit_after: Duration,
+    max_bumps: usize,
+    max_tip: u64,
+    poll_interval: Duration,
+}
+
+impl TipBumpConfig {
+    /// A transaction is replaced when it is squeezed out or still pending after
+    /// `resubmit_after`, at most `max_bumps` times.
+    pub fn new(strategy: TipStrategy, resubmit_after: Duration, max_bumps: usize) -> Result<Self> {
+        if let TipStrategy::Multiplier { factor, .. } = strategy
+            && (factor.is_nan() || factor < 1.0)
+        {
+            return Err(error!(
+                Other,
+                "tip multiplier must be at least 1, got {factor}"
+            ));
+        }
+
+        Ok(Self {
+            strategy,
+            resubmit_after,
+            max_bumps,
+            max_tip: u64::MAX,
+            poll_interval: Duration::from_millis(500).min(resubmit_after),
+        })
+    }
+
+    /// The tip is never raised above `max_tip`.
+    pub fn with_max_tip(mut self, max_tip: u64) -> Self {
+        self.max_tip = max_tip;
+        self
+    }
+
+    /// How often the status of pending transactions is checked.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn strategy(&self) -> TipStrategy {
+        self.strategy
+    }
+
+    pub fn resubmit_after(&self) -> Duration {
+        self.resubmit_after
+    }
+
+    pub fn max_bumps(&self) -> usize {
+        self.max_bumps
+    }
+
+    pub fn max_tip(&self) -> u64 {
+        self.max_tip
+    }
+}
+
+impl Default for TipBumpConfig {
+    fn default() -> Self {
+        Self::new(TipStrategy::default(), Duration::from_secs(10), 5)
+            .expect("default multiplier is valid")
+    }
+}
+
+pub(crate) async fn send_with_tip_bumping<A, Tb>(
+    account: &A,
+    mut tb: Tb,
+    config: TipBumpConfig,
+) -> Result<TxResponse>
+where
+    A: Account + ?Sized,
+    Tb: TransactionBuilder + Clone + Sync,
+{
+    let provider = account.try_provider()?;
+    let consensus_parameters = provider.consensus_parameters().await?;
+    let chain_id = consensus_parameters.chain_id();
+
+    let mut tx = tb.clone().build(provider).await?;
+    let mut tip = tx.tip().unwrap_or_default();
+    let mut submitted = vec![tx.id(chain_id)];
+    provider.send_transaction(tx.clone()).await?;
+
+    let mut bumps = 0;
+    loop {
+        // the replaced transactions may still be included until the latest one is
+        if let Some(response) = await_any_final(provider, &submitted, &config).await? {
+            return Ok(response);
+        }
+
+        let latest = *submitted
+            .last()
+            .expect("at least one transaction was submitted");
+        if bumps == config.max_bumps {
+            return Err(error!(
+                Other,
+                "transaction `{latest}` was not included after raising its tip {bumps} times"
+            ));
+        }
+        bumps += 1;
+
+        let next_tip = config
+            .strategy
+            .next_tip(tip, tx.max_gas(&consensus_parameters), provider)
+            .await?
+            .min(config.max_tip);
+        if next_tip <= tip {
+            // the tip can't be raised any further, keep waiting for the pending transactions
+            continue;
+        }
+
+        tb = with_tip(account, tb, next_tip, tx.max_fee().unwrap_or_default()).await?;
+        tip = next_tip;
+        tx = tb.clone().build(provider).await?;
+        let tx_id = tx.id(chain_id);
+
+        if let Err(err) = provider.send_replacement_transaction(tx.clone()).await {
+            // a replaced transaction may have been included in the meantime
+            return match await_any_final(provider, &submitted, &config).await? {
+                Some(response) => Ok(response),
+                None => Err(err).with_context(|| {
+                    format!("failed to replace transaction `{latest}` with `{tx_id}`")
+                }),
+            };
+        }
+        submitted.push(tx_id);
+    }
+}
+
+/// Raises the tip of `tb`, adding inputs to cover the increase of its max fee.
+async fn with_tip<A, Tb>(account: &A, tb: Tb, tip: u64, previous_max_fee: u64) -> Result<Tb>
+where
+    A: Account + ?Sized,
+    Tb: TransactionBuilder + Sync,
+{
+    let provider = account.try_provider()?;
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+
+    let policies = tb.tx_policies();
+    // the max fee is estimated again to account for the new tip
+    let policies = TxPolicies::new(
+        Some(tip),
+        policies.witness_limit(),
+        policies.maturity(),
+        policies.expiration(),
+        None,
+        policies.script_gas_limit(),
+        policies.owner(),
+    );
+    let mut tb = tb.with_tx_policies(policies);
+
+    let missing_amount = tb
+        .estimate_max_fee(provider)
+        .await?
+        .saturating_sub(previous_max_fee);
+    if missing_amount > 0 {
+        let (used_base_assets, _) = available_base_assets_and_amount(&tb, &base_asset_id);
+        let inputs = account
+            .get_asset_inputs_for_amount(
+                base_asset_id,
+                missing_amount.into(),
+                Some(used_base_assets),
+            )
+            .await
+            .context("failed to get base asset inputs covering the raised tip")?;
+
+        tb.inputs_mut().extend(inputs);
+        add_base_change_if_needed(&mut tb, account.address(), base_asset_id);
+    }
+
+    Ok(tb)
+}
+
+/// Waits up to `resubmit_after` for one of the `submitted` transactions to reach a final status.
+///
+/// Returns `None` if none did or if the latest one was squeezed out, so that it can be replaced.
+async fn await_any_final(
+    provider: &Provider,
+    submitted: &[TxId],
+    config: &TipBumpConfig,
+) -> Result<Option<TxResponse>> {
+    let deadline = Instant::now() + config.resubmit_after;
+
+    loop {
+        for (i, tx_id) in submitted.iter().enumerate() {
+            match provider.tx_status(tx_id).await? {
+                status @ (TxStatus::Success(_) | TxStatus::Failure(_)) => {
+                    return Ok(Some(TxResponse {
+                        tx_status: status.take_success_checked(None)?,
+                        tx_id: *tx_id,
+                    }));
+                }
+                TxStatus::SqueezedOut(_) if i + 1 == submitted.len() => return Ok(None),
+                _ => {}
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        runtime::sleep(config.poll_interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplier_raises_the_tip_by_at_least_the_minimum() {
+        assert_eq!(multiplied_tip(0, 1.5, 10), 10);
+        assert_eq!(multiplied_tip(100, 1.5, 10), 150);
+        assert_eq!(multiplied_tip(101, 1.5, 10), 152);
+        assert_eq!(multiplied_tip(100, 1.0, 10), 110);
+        assert_eq!(multiplied_tip(u64::MAX, 1.5, 10), u64::MAX);
+    }
+
+    #[test]
+    fn multipliers_below_one_are_rejected() {
+        let strategy = TipStrategy::Multiplier {
+            factor: 0.5,
+            min_increase: 1,
+        };
+
+        assert!(TipBumpConfig::new(strategy, Duration::from_secs(1), 1).is_err());
+        assert!(TipBumpConfig::new(TipStrategy::default(), Duration::from_secs(1), 1).is_ok());
+    }
+}
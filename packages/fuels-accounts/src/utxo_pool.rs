@@ -0,0 +1,1012 @@
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use fuel_tx::{ConsensusParameters, Input as FuelInput, Output, TxId, UtxoId};
+use fuels_core::types::{
+    Address, AssetId,
+    coin::Coin,
+    coin_type::CoinType,
+    errors::{Context, Result, error},
+    input::Input,
+    transaction::{ScriptTransaction, Transaction, TxPolicies},
+    transaction_builders::{BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder},
+    tx_response::TxResponse,
+    tx_status::{Failure, Success, TxStatus},
+};
+
+use crate::{
+    Account,
+    accounts_utils::{
+        add_base_change_if_needed, available_base_assets_and_amount, calculate_missing_base_amount,
+    },
+    provider::Provider,
+};
+
+#[derive(Debug, Clone)]
+struct PoolCoin {
+    coin: Coin,
+    confirmed: bool,
+}
+
+#[derive(Debug, Default)]
+struct InFlightTx {
+    spent: Vec<PoolCoin>,
+    produced: Vec<UtxoId>,
+    change: Vec<Change>,
+    /// Whether the transaction can move coins in ways only known after its execution, e.g.
+    /// through contract calls, so that its change can't be computed client-side.
+    opaque: bool,
+}
+
+/// A change output owned by the pool's owner.
+#[derive(Debug)]
+struct Change {
+    utxo_id: UtxoId,
+    asset_id: AssetId,
+    is_base: bool,
+    /// The amount of the asset brought by the inputs.
+    inputs_amount: u128,
+    /// The amount of the asset sent to `Output::Coin`s.
+    outputs_amount: u128,
+}
+
+impl Change {
+    /// The amount of the change if the transaction succeeded and paid `fee`.
+    fn on_success(&self, fee: u64) -> u128 {
+        self.inputs_amount
+            .saturating_sub(self.outputs_amount)
+            .saturating_sub(self.fee(fee))
+    }
+
+    /// The amount of the change if the transaction reverted and paid `fee`. No coin outputs are
+    /// created and no coins are moved by the script, so the inputs come back as change.
+    fn on_failure(&self, fee: u64) -> u128 {
+        self.inputs_amount.saturating_sub(self.fee(fee))
+    }
+
+    fn fee(&self, fee: u64) -> u128 {
+        if self.is_base { u128::from(fee) } else { 0 }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    available: HashMap<UtxoId, PoolCoin>,
+    reserved: HashMap<UtxoId, PoolCoin>,
+    in_flight: HashMap<TxId, InFlightTx>,
+}
+
+impl PoolState {
+    fn add_coin(&mut self, coin: Coin, confirmed: bool) {
+        if coin.amount != 0 {
+            self.available
+                .insert(coin.utxo_id, PoolCoin { coin, confirmed });
+        }
+    }
+
+    /// The coin `utxo_id`, whether available, reserved or spent by an in-flight transaction.
+    fn coin_mut(&mut self, utxo_id: &UtxoId) -> Option<&mut PoolCoin> {
+        let Self {
+            available,
+            reserved,
+            in_flight,
+        } = self;
+
+        available
+            .get_mut(utxo_id)
+            .or_else(|| reserved.get_mut(utxo_id))
+            .or_else(|| {
+                in_flight
+                    .values_mut()
+                    .flat_map(|tx| tx.spent.iter_mut())
+                    .find(|pool_coin| pool_coin.coin.utxo_id == *utxo_id)
+            })
+    }
+
+    /// Forgets the coin `utxo_id`, which will never be created, so that it is not released back
+    /// to the pool by the reservation or the in-flight transaction holding it.
+    fn drop_coin(&mut self, utxo_id: &UtxoId) {
+        self.available.remove(utxo_id);
+        self.reserved.remove(utxo_id);
+        for tx in self.in_flight.values_mut() {
+            tx.spent
+                .retain(|pool_coin| pool_coin.coin.utxo_id != *utxo_id);
+        }
+    }
+
+    fn release(&mut self, coins: &[Coin]) {
+        for coin in coins {
+            if let Some(pool_coin) = self.reserved.remove(&coin.utxo_id) {
+                self.available.insert(coin.utxo_id, pool_coin);
+            }
+        }
+    }
+}
+
+/// A snapshot of the coins tracked by a [`UtxoPool`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UtxoPoolState {
+    /// Committed coins that can be reserved.
+    pub available: Vec<Coin>,
+    /// Coins created by in-flight transactions that can already be reserved.
+    pub unconfirmed: Vec<Coin>,
+    /// Coins held by a [`Reservation`].
+    pub reserved: Vec<Coin>,
+    /// Transactions that were submitted but not yet resolved.
+    pub in_flight: Vec<TxId>,
+}
+
+/// Coins of a [`UtxoPool`] set aside for a single transaction.
+///
+/// Dropping a reservation that was not handed to [`UtxoPool::submitted`] returns its coins to
+/// the pool.
+#[derive(Debug)]
+pub struct Reservation {
+    coins: Vec<Coin>,
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl Reservation {
+    pub fn coins(&self) -> &[Coin] {
+        &self.coins
+    }
+
+    /// Total amount of the reserved coins of asset `asset_id`.
+    pub fn amount(&self, asset_id: &AssetId) -> u128 {
+        self.coins
+            .iter()
+            .filter(|coin| coin.asset_id == *asset_id)
+            .map(|coin| u128::from(coin.amount))
+            .sum()
+    }
+
+    /// Returns the reserved coins as signed inputs.
+    pub fn inputs(&self) -> Vec<Input> {
+        self.coins
+            .iter()
+            .cloned()
+            .map(|coin| Input::resource_signed(CoinType::Coin(coin)))
+            .collect()
+    }
+
+    /// Moves the coins of `other` into this reservation, e.g. to combine the coins of the
+    /// transferred asset with the ones covering the fee.
+    pub fn merge(&mut self, mut other: Reservation) {
+        self.coins.append(&mut other.coins);
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if !self.coins.is_empty() {
+            lock(&self.state).release(&self.coins);
+        }
+    }
+}
+
+/// Keeps track of the coins of a single owner so that concurrently built transactions never
+/// spend the same coin.
+///
+/// Coins are [reserved](UtxoPool::reserve) for a transaction before it is built and marked as
+/// spent once the transaction is [submitted](UtxoPool::submitted). The coins created by the
+/// submitted transaction for the owner are added to the pool straight away and can be spent by
+/// later transactions before the first one is committed:
+/// * `Output::Coin` outputs,
+/// * `Output::Change` outputs of assets other than the base asset, whose amount is computed from
+///   the inputs and coin outputs.
+///
+/// The base asset change depends on the fee, so it becomes available once the transaction is
+/// [resolved](UtxoPool::resolve) as successful. The change of transactions with contract inputs
+/// or variable outputs can't be computed client-side and is only picked up by the next
+/// [`UtxoPool::sync`].
+///
+/// When a transaction is resolved as squeezed out its coins are returned to the pool. On failure
+/// they are considered spent, as the node still charges the fee, and its change outputs, holding
+/// what is left of the inputs, become available. In both cases the coins it would have created
+/// are dropped, also from the reservations and transactions that chained them. Chained change of
+/// a failed transaction stays with its holder, with the amount left after the revert.
+///
+/// [`UtxoPool::transfer`] and [`UtxoPool::adjust_for_fee`] build transactions of an account from
+/// the pool. The pool is cheap to clone and all clones share the same state.
+#[derive(Debug, Clone)]
+pub struct UtxoPool {
+    owner: Address,
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl UtxoPool {
+    pub fn new(owner: Address) -> Self {
+        Self {
+            owner,
+            state: Default::default(),
+        }
+    }
+
+    pub fn owner(&self) -> Address {
+        self.owner
+    }
+
+    /// Fetches the owner's coins of asset `asset_id` from the node, adding the ones not known to
+    /// the pool and removing the committed ones that were spent elsewhere.
+    pub async fn sync(&self, provider: &Provider, asset_id: AssetId) -> Result<()> {
+        let coins = provider.get_coins(&self.owner, asset_id).await?;
+        self.add_committed(asset_id, coins);
+
+        Ok(())
+    }
+
+    fn add_committed(&self, asset_id: AssetId, coins: Vec<Coin>) {
+        let mut state = lock(&self.state);
+
+        let in_flight_spent = state
+            .in_flight
+            .values()
+            .flat_map(|tx| tx.spent.iter().map(|pool_coin| pool_coin.coin.utxo_id))
+            .collect::<HashSet<_>>();
+        let committed = coins
+            .iter()
+            .map(|coin| coin.utxo_id)
+            .collect::<HashSet<_>>();
+
+        state.available.retain(|utxo_id, pool_coin| {
+            pool_coin.coin.asset_id != asset_id
+                || !pool_coin.confirmed
+                || committed.contains(utxo_id)
+        });
+
+        for coin in coins {
+            let utxo_id = coin.utxo_id;
+            if state.reserved.contains_key(&utxo_id) || in_flight_spent.contains(&utxo_id) {
+                continue;
+            }
+
+            state.add_coin(coin, true);
+        }
+    }
+
+    /// Reserves available coins of asset `asset_id` adding up to at least `amount`, largest
+    /// coins first.
+    pub fn reserve(&self, asset_id: AssetId, amount: u128) -> Result<Reservation> {
+        let mut state = lock(&self.state);
+
+        let mut candidates = state
+            .available
+            .values()
+            .filter(|pool_coin| pool_coin.coin.asset_id == asset_id)
+            .map(|pool_coin| &pool_coin.coin)
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|coin| std::cmp::Reverse(coin.amount));
+
+        let mut selected = vec![];
+        let mut total = 0u128;
+        for coin in candidates {
+            if total >= amount {
+                break;
+            }
+            total += u128::from(coin.amount);
+            selected.push(coin.utxo_id);
+        }
+
+        if total < amount {
+            return Err(error!(
+                Other,
+                "not enough coins of asset `{asset_id}` in the pool: requested `{amount}`, available `{total}`"
+            ));
+        }
+
+        let coins = selected
+            .into_iter()
+            .map(|utxo_id| {
+                let pool_coin = state.available.remove(&utxo_id).expect("was just selected");
+                let coin = pool_coin.coin.clone();
+                state.reserved.insert(utxo_id, pool_coin);
+
+                coin
+            })
+            .collect();
+
+        Ok(Reservation {
+            coins,
+            state: self.state.clone(),
+        })
+    }
+
+    /// Marks the coins of `reservation` as spent by the submitted `tx` and adds the coins it
+    /// creates for the pool's owner as unconfirmed coins. Returns the id of `tx`.
+    pub fn submitted<T: Transaction>(
+        &self,
+        mut reservation: Reservation,
+        tx: &T,
+        consensus_parameters: &ConsensusParameters,
+    ) -> TxId {
+        let tx_id = tx.id(consensus_parameters.chain_id());
+        let base_asset_id = consensus_parameters.base_asset_id();
+
+        let coins = mem::take(&mut reservation.coins);
+        let mut state = lock(&self.state);
+
+        let spent = coins
+            .iter()
+            .filter_map(|coin| state.reserved.remove(&coin.utxo_id))
+            .collect();
+
+        let opaque = tx.inputs().iter().any(FuelInput::is_contract)
+            || tx.outputs().iter().any(Output::is_variable);
+        let amount_of = |asset_id: &AssetId| {
+            let inputs_amount = tx
+                .inputs()
+                .iter()
+                .filter(|input| input.asset_id(base_asset_id) == Some(asset_id))
+                .filter_map(FuelInput::amount)
+                .map(u128::from)
+                .sum();
+            let outputs_amount = tx
+                .outputs()
+                .iter()
+                .filter_map(|output| match output {
+                    Output::Coin {
+                        amount,
+                        asset_id: output_asset_id,
+                        ..
+                    } if output_asset_id == asset_id => Some(u128::from(*amount)),
+                    _ => None,
+                })
+                .sum();
+
+            (inputs_amount, outputs_amount)
+        };
+
+        let mut produced = vec![];
+        let mut change = vec![];
+        for (idx, output) in tx.outputs().iter().enumerate() {
+            let utxo_id = UtxoId::new(tx_id, idx as u16);
+            match output {
+                Output::Coin {
+                    to,
+                    amount,
+                    asset_id,
+                } if *to == self.owner => {
+                    let coin = Coin {
+                        amount: *amount,
+                        asset_id: *asset_id,
+                        utxo_id,
+                        owner: self.owner,
+                    };
+                    state.add_coin(coin, false);
+                    produced.push(utxo_id);
+                }
+                Output::Change { to, asset_id, .. } if *to == self.owner => {
+                    let (inputs_amount, outputs_amount) = amount_of(asset_id);
+                    let output_change = Change {
+                        utxo_id,
+                        asset_id: *asset_id,
+                        is_base: asset_id == base_asset_id,
+                        inputs_amount,
+                        outputs_amount,
+                    };
+
+                    let chained_amount = output_change.on_success(0);
+                    if !opaque && !output_change.is_base && chained_amount != 0 {
+                        let coin = self.change_coin(&output_change, chained_amount);
+                        state.add_coin(coin, false);
+                        produced.push(utxo_id);
+                    }
+                    change.push(output_change);
+                }
+                _ => {}
+            }
+        }
+
+        state.in_flight.insert(
+            tx_id,
+            InFlightTx {
+                spent,
+                produced,
+                change,
+                opaque,
+            },
+        );
+
+        tx_id
+    }
+
+    /// Updates the pool according to the `status` of the in-flight transaction `tx_id`.
+    /// Non-final statuses are ignored.
+    pub fn resolve(&self, tx_id: &TxId, status: &TxStatus) {
+        let mut state = lock(&self.state);
+
+        match status {
+            TxStatus::Success(Success { total_fee, .. })
+            | TxStatus::PreconfirmationSuccess(Success { total_fee, .. }) => {
+                let Some(tx) = state.in_flight.remove(tx_id) else {
+                    return;
+                };
+
+                // chained coins may already be reserved by a later transaction
+                let state = &mut *state;
+                for utxo_id in &tx.produced {
+                    if let Some(pool_coin) = state
+                        .available
+                        .get_mut(utxo_id)
+                        .or_else(|| state.reserved.get_mut(utxo_id))
+                    {
+                        pool_coin.confirmed = true;
+                    }
+                }
+
+                if !tx.opaque {
+                    for change in tx.change.iter().filter(|change| change.is_base) {
+                        let coin = self.change_coin(change, change.on_success(*total_fee));
+                        state.add_coin(coin, true);
+                    }
+                }
+            }
+            TxStatus::SqueezedOut(_) => {
+                let Some(tx) = state.in_flight.remove(tx_id) else {
+                    return;
+                };
+
+                for utxo_id in &tx.produced {
+                    state.drop_coin(utxo_id);
+                }
+                for pool_coin in tx.spent {
+                    state.available.insert(pool_coin.coin.utxo_id, pool_coin);
+                }
+            }
+            TxStatus::Failure(Failure { total_fee, .. })
+            | TxStatus::PreconfirmationFailure(Failure { total_fee, .. }) => {
+                let Some(tx) = state.in_flight.remove(tx_id) else {
+                    return;
+                };
+
+                // chained coins may already be reserved or spent by a later transaction
+                for utxo_id in &tx.produced {
+                    let Some(change) = tx.change.iter().find(|change| change.utxo_id == *utxo_id)
+                    else {
+                        state.drop_coin(utxo_id);
+                        continue;
+                    };
+
+                    let amount = self
+                        .change_coin(change, change.on_failure(*total_fee))
+                        .amount;
+                    if let Some(pool_coin) = state.coin_mut(utxo_id) {
+                        pool_coin.coin.amount = amount;
+                        pool_coin.confirmed = true;
+                    }
+                }
+                for change in tx
+                    .change
+                    .iter()
+                    .filter(|change| !tx.produced.contains(&change.utxo_id))
+                {
+                    let coin = self.change_coin(change, change.on_failure(*total_fee));
+                    state.add_coin(coin, true);
+                }
+            }
+            TxStatus::Submitted => {}
+        }
+    }
+
+    fn change_coin(&self, change: &Change, amount: u128) -> Coin {
+        Coin {
+            amount: u64::try_from(amount).unwrap_or(u64::MAX),
+            asset_id: change.asset_id,
+            utxo_id: change.utxo_id,
+            owner: self.owner,
+        }
+    }
+
+    /// Submits `tx`, which must spend the coins of `reservation`, and marks it as submitted.
+    /// Its status is tracked by [`UtxoPool::await_commit`].
+    ///
+    /// If the node rejects the transaction the reserved coins are returned to the pool.
+    pub async fn submit<T: Transaction>(
+        &self,
+        provider: &Provider,
+        reservation: Reservation,
+        tx: T,
+    ) -> Result<TxId> {
+        let consensus_parameters = provider.consensus_parameters().await?;
+
+        // on error the dropped reservation returns its coins to the pool
+        provider.send_transaction(tx.clone()).await?;
+
+        Ok(self.submitted(reservation, &tx, &consensus_parameters))
+    }
+
+    /// Waits for the submitted transaction `tx_id` to be committed and resolves it.
+    pub async fn await_commit(&self, provider: &Provider, tx_id: TxId) -> Result<TxStatus> {
+        let status = provider
+            .await_transaction_commit::<ScriptTransaction>(tx_id)
+            .await?;
+        self.resolve(&tx_id, &status);
+
+        Ok(status)
+    }
+
+    /// Submits `tx`, which must spend the coins of `reservation`, and waits for it to be
+    /// committed, keeping the pool up to date along the way.
+    ///
+    /// If the node rejects the transaction the reserved coins are returned to the pool.
+    pub async fn send_transaction<T: Transaction>(
+        &self,
+        provider: &Provider,
+        reservation: Reservation,
+        tx: T,
+    ) -> Result<TxStatus> {
+        let tx_id = self.submit(provider, reservation, tx).await?;
+
+        self.await_commit(provider, tx_id).await
+    }
+
+    /// Like [`ViewOnlyAccount::adjust_for_fee`](crate::ViewOnlyAccount::adjust_for_fee), but covers the fee with base asset coins
+    /// reserved from the pool. The returned reservation must be submitted along with the ones
+    /// of the other inputs of `tb`, e.g. after [merging](Reservation::merge) them.
+    pub async fn adjust_for_fee<Tb: TransactionBuilder + Sync>(
+        &self,
+        provider: &Provider,
+        tb: &mut Tb,
+        used_base_amount: u128,
+    ) -> Result<Reservation> {
+        let consensus_parameters = provider.consensus_parameters().await?;
+        let base_asset_id = *consensus_parameters.base_asset_id();
+        let (_, base_amount) = available_base_assets_and_amount(tb, &base_asset_id);
+        let missing_base_amount =
+            calculate_missing_base_amount(tb, base_amount, used_base_amount, provider).await?;
+
+        let reservation = if missing_base_amount > 0 {
+            let reservation = self.reserve(base_asset_id, missing_base_amount)?;
+            tb.inputs_mut().extend(reservation.inputs());
+
+            reservation
+        } else {
+            Reservation {
+                coins: vec![],
+                state: self.state.clone(),
+            }
+        };
+
+        add_base_change_if_needed(tb, self.owner, base_asset_id);
+
+        Ok(reservation)
+    }
+
+    /// Like [`Account::transfer`], but spends coins reserved from the pool and returns once the
+    /// transaction is submitted, so that further transfers can be sent right away. The status
+    /// of the transfer is tracked by [`UtxoPool::await_commit`].
+    pub async fn submit_transfer(
+        &self,
+        account: &impl Account,
+        to: Address,
+        amount: u64,
+        asset_id: AssetId,
+        tx_policies: TxPolicies,
+    ) -> Result<TxId> {
+        if account.address() != self.owner {
+            return Err(error!(
+                Other,
+                "the account `{}` does not own the pool of `{}`",
+                account.address(),
+                self.owner
+            ));
+        }
+
+        let provider = account.try_provider()?;
+        let consensus_parameters = provider.consensus_parameters().await?;
+
+        let mut reservation = self.reserve(asset_id, amount.into())?;
+        let outputs = account.get_asset_outputs_for_amount(to, asset_id, amount);
+        let mut tb =
+            ScriptTransactionBuilder::prepare_transfer(reservation.inputs(), outputs, tx_policies);
+        account.add_witnesses(&mut tb)?;
+
+        let used_base_amount = if asset_id == *consensus_parameters.base_asset_id() {
+            amount.into()
+        } else {
+            0
+        };
+        reservation.merge(
+            self.adjust_for_fee(provider, &mut tb, used_base_amount)
+                .await
+                .context("failed to reserve coins covering the fee")?,
+        );
+
+        let tx = tb.build(provider).await?;
+
+        self.submit(provider, reservation, tx).await
+    }
+
+    /// Like [`Account::transfer`], but spends coins reserved from the pool, so that concurrent
+    /// transfers of the same account never spend the same coin.
+    pub async fn transfer(
+        &self,
+        account: &impl Account,
+        to: Address,
+        amount: u64,
+        asset_id: AssetId,
+        tx_policies: TxPolicies,
+    ) -> Result<TxResponse> {
+        let provider = account.try_provider()?;
+        let tx_id = self
+            .submit_transfer(account, to, amount, asset_id, tx_policies)
+            .await?;
+        let tx_status = self.await_commit(provider, tx_id).await?;
+
+        Ok(TxResponse {
+            tx_status: tx_status.take_success_checked(None)?,
+            tx_id,
+        })
+    }
+
+    /// Total amount of the available coins of asset `asset_id`, including unconfirmed ones.
+    pub fn available_amount(&self, asset_id: &AssetId) -> u128 {
+        lock(&self.state)
+            .available
+            .values()
+            .filter(|pool_coin| pool_coin.coin.asset_id == *asset_id)
+            .map(|pool_coin| u128::from(pool_coin.coin.amount))
+            .sum()
+    }
+
+    pub fn state(&self) -> UtxoPoolState {
+        let state = lock(&self.state);
+
+        let (confirmed, unconfirmed): (Vec<_>, Vec<_>) = state
+            .available
+            .values()
+            .cloned()
+            .partition(|pool_coin| pool_coin.confirmed);
+
+        UtxoPoolState {
+            available: confirmed
+                .into_iter()
+                .map(|pool_coin| pool_coin.coin)
+                .collect(),
+            unconfirmed: unconfirmed
+                .into_iter()
+                .map(|pool_coin| pool_coin.coin)
+                .collect(),
+            reserved: state
+                .reserved
+                .values()
+                .map(|pool_coin| pool_coin.coin.clone())
+                .collect(),
+            in_flight: state.in_flight.keys().copied().collect(),
+        }
+    }
+}
+
+/// Every update of the state completes before a panic could occur, so a poisoned lock still
+/// guards a consistent state. Not panicking also keeps reservations dropped while unwinding from
+/// aborting the process.
+fn lock(state: &Mutex<PoolState>) -> MutexGuard<'_, PoolState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_tx::{Script, Transaction as FuelTransaction, field::Inputs};
+    use fuels_core::types::tx_status::SqueezedOut;
+
+    use super::*;
+
+    const ASSET: AssetId = AssetId::new([1; 32]);
+
+    fn owner() -> Address {
+        Address::from([9; 32])
+    }
+
+    fn coin(idx: u8, amount: u64) -> Coin {
+        base_coin(idx, amount, ASSET)
+    }
+
+    fn base_coin(idx: u8, amount: u64, asset_id: AssetId) -> Coin {
+        Coin {
+            amount,
+            asset_id,
+            utxo_id: UtxoId::new(TxId::from([idx; 32]), 0),
+            owner: owner(),
+        }
+    }
+
+    fn pool_with_coins(coins: Vec<Coin>) -> UtxoPool {
+        let pool = UtxoPool::new(owner());
+
+        let mut coins_per_asset = HashMap::<_, Vec<_>>::new();
+        for coin in coins {
+            coins_per_asset.entry(coin.asset_id).or_default().push(coin);
+        }
+        for (asset_id, coins) in coins_per_asset {
+            pool.add_committed(asset_id, coins);
+        }
+
+        pool
+    }
+
+    fn script_tx(reservation: &Reservation, outputs: Vec<Output>) -> Script {
+        let inputs = reservation
+            .coins()
+            .iter()
+            .map(|coin| {
+                FuelInput::coin_signed(
+                    coin.utxo_id,
+                    coin.owner,
+                    coin.amount,
+                    coin.asset_id,
+                    Default::default(),
+                    0,
+                )
+            })
+            .collect();
+
+        FuelTransaction::script(
+            0,
+            vec![],
+            vec![],
+            Default::default(),
+            inputs,
+            outputs,
+            vec![],
+        )
+    }
+
+    fn submit(pool: &UtxoPool, reservation: Reservation, outputs: Vec<Output>) -> TxId {
+        let tx: ScriptTransaction = script_tx(&reservation, outputs).into();
+
+        pool.submitted(reservation, &tx, &ConsensusParameters::default())
+    }
+
+    fn success(total_fee: u64) -> TxStatus {
+        TxStatus::Success(Success {
+            receipts: Default::default(),
+            total_fee,
+            total_gas: 0,
+        })
+    }
+
+    fn failure(total_fee: u64) -> TxStatus {
+        TxStatus::Failure(Failure {
+            reason: "".to_string(),
+            receipts: Default::default(),
+            revert_id: None,
+            total_fee,
+            total_gas: 0,
+        })
+    }
+
+    #[test]
+    fn concurrent_reservations_do_not_overlap() -> Result<()> {
+        let pool = pool_with_coins(vec![coin(1, 10), coin(2, 20), coin(3, 30)]);
+
+        let first = pool.reserve(ASSET, 25)?;
+        let second = pool.reserve(ASSET, 25)?;
+
+        assert_eq!(first.coins(), &[coin(3, 30)]);
+        assert_eq!(second.amount(&ASSET), 30);
+        assert!(pool.reserve(ASSET, 1).is_err());
+
+        drop(second);
+        assert_eq!(pool.available_amount(&ASSET), 30);
+        assert_eq!(pool.state().reserved, vec![coin(3, 30)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn coin_outputs_can_be_chained_before_commit() -> Result<()> {
+        let pool = pool_with_coins(vec![coin(1, 100)]);
+
+        let reservation = pool.reserve(ASSET, 60)?;
+        let tx_id = submit(
+            &pool,
+            reservation,
+            vec![
+                Output::coin(Address::from([5; 32]), 60, ASSET),
+                Output::coin(owner(), 40, ASSET),
+                Output::change(owner(), 0, ASSET),
+            ],
+        );
+
+        let chained = UtxoId::new(tx_id, 1);
+        let state = pool.state();
+        assert_eq!(state.in_flight, vec![tx_id]);
+        assert_eq!(state.unconfirmed.len(), 1);
+        assert_eq!(state.unconfirmed[0].utxo_id, chained);
+
+        let next = pool.reserve(ASSET, 40)?;
+        assert_eq!(next.coins()[0].utxo_id, chained);
+
+        pool.resolve(&tx_id, &success(0));
+        drop(next);
+
+        let state = pool.state();
+        assert!(state.in_flight.is_empty());
+        assert_eq!(state.available.len(), 1);
+        assert_eq!(state.available[0].utxo_id, chained);
+
+        Ok(())
+    }
+
+    #[test]
+    fn change_is_chained_before_commit_unless_it_pays_the_fee() -> Result<()> {
+        let base_asset_id = AssetId::zeroed();
+        let pool = pool_with_coins(vec![coin(1, 100), base_coin(2, 50, base_asset_id)]);
+
+        let mut reservation = pool.reserve(ASSET, 30)?;
+        reservation.merge(pool.reserve(base_asset_id, 1)?);
+        let tx_id = submit(
+            &pool,
+            reservation,
+            vec![
+                Output::coin(Address::from([5; 32]), 30, ASSET),
+                Output::change(owner(), 0, ASSET),
+                Output::change(owner(), 0, base_asset_id),
+            ],
+        );
+
+        let state = pool.state();
+        assert_eq!(state.unconfirmed.len(), 1);
+        assert_eq!(state.unconfirmed[0].utxo_id, UtxoId::new(tx_id, 1));
+        assert_eq!(pool.available_amount(&ASSET), 70);
+        assert_eq!(pool.available_amount(&base_asset_id), 0);
+
+        pool.resolve(&tx_id, &success(8));
+
+        let state = pool.state();
+        assert!(state.unconfirmed.is_empty());
+        assert_eq!(pool.available_amount(&ASSET), 70);
+        assert_eq!(pool.available_amount(&base_asset_id), 42);
+
+        Ok(())
+    }
+
+    #[test]
+    fn change_of_contract_calls_is_left_to_sync() -> Result<()> {
+        let pool = pool_with_coins(vec![coin(1, 100)]);
+
+        let reservation = pool.reserve(ASSET, 30)?;
+        let mut tx = script_tx(&reservation, vec![Output::change(owner(), 0, ASSET)]);
+        tx.inputs_mut().push(FuelInput::contract(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        ));
+        let tx: ScriptTransaction = tx.into();
+        let tx_id = pool.submitted(reservation, &tx, &ConsensusParameters::default());
+
+        assert_eq!(pool.available_amount(&ASSET), 0);
+
+        pool.resolve(&tx_id, &success(0));
+        assert_eq!(pool.available_amount(&ASSET), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn squeezed_out_coins_are_released_and_failed_ones_are_spent() -> Result<()> {
+        let pool = pool_with_coins(vec![coin(1, 10), coin(2, 20)]);
+
+        let squeezed_tx = submit(&pool, pool.reserve(ASSET, 20)?, vec![]);
+        let failed_tx = submit(
+            &pool,
+            pool.reserve(ASSET, 10)?,
+            vec![Output::coin(owner(), 5, ASSET)],
+        );
+        assert_eq!(pool.available_amount(&ASSET), 5);
+
+        pool.resolve(
+            &squeezed_tx,
+            &TxStatus::SqueezedOut(SqueezedOut {
+                reason: "".to_string(),
+            }),
+        );
+        pool.resolve(&failed_tx, &failure(0));
+
+        let state = pool.state();
+        assert_eq!(state.available, vec![coin(2, 20)]);
+        assert!(state.unconfirmed.is_empty());
+        assert!(state.in_flight.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_transactions_return_their_inputs_as_change() -> Result<()> {
+        let base_asset_id = AssetId::zeroed();
+        let pool = pool_with_coins(vec![coin(1, 100), base_coin(2, 50, base_asset_id)]);
+
+        let mut reservation = pool.reserve(ASSET, 30)?;
+        reservation.merge(pool.reserve(base_asset_id, 1)?);
+        let tx_id = submit(
+            &pool,
+            reservation,
+            vec![
+                Output::coin(Address::from([5; 32]), 30, ASSET),
+                Output::change(owner(), 0, ASSET),
+                Output::change(owner(), 0, base_asset_id),
+            ],
+        );
+
+        pool.resolve(&tx_id, &failure(8));
+
+        let state = pool.state();
+        assert!(state.unconfirmed.is_empty());
+        assert_eq!(pool.available_amount(&ASSET), 100);
+        assert_eq!(pool.available_amount(&base_asset_id), 42);
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_transactions_update_coins_chained_by_later_ones() -> Result<()> {
+        let pool = pool_with_coins(vec![coin(1, 100)]);
+
+        let failed_tx = submit(
+            &pool,
+            pool.reserve(ASSET, 100)?,
+            vec![
+                Output::coin(Address::from([5; 32]), 30, ASSET),
+                Output::coin(owner(), 10, ASSET),
+                Output::change(owner(), 0, ASSET),
+            ],
+        );
+        let chained = pool.reserve(ASSET, 70)?;
+
+        pool.resolve(&failed_tx, &failure(0));
+
+        let change = Coin {
+            amount: 100,
+            utxo_id: UtxoId::new(failed_tx, 2),
+            ..coin(1, 100)
+        };
+        let state = pool.state();
+        assert_eq!(state.reserved, vec![change.clone()]);
+        assert!(state.available.is_empty());
+        assert!(pool.reserve(ASSET, 1).is_err());
+
+        drop(chained);
+        assert_eq!(pool.state().available, vec![change]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sync_skips_coins_in_use_and_drops_spent_ones() -> Result<()> {
+        let pool = pool_with_coins(vec![coin(1, 10), coin(2, 20)]);
+
+        let reservation = pool.reserve(ASSET, 20)?;
+        pool.add_committed(ASSET, vec![coin(2, 20), coin(3, 30)]);
+
+        let mut available = pool.state().available;
+        available.sort_by_key(|coin| coin.amount);
+        assert_eq!(available, vec![coin(3, 30)]);
+        assert_eq!(reservation.coins(), &[coin(2, 20)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reservations_are_released_after_the_pool_lock_is_poisoned() -> Result<()> {
+        let pool = pool_with_coins(vec![coin(1, 10)]);
+        let reservation = pool.reserve(ASSET, 10)?;
+
+        let state = Arc::clone(&pool.state);
+        std::thread::spawn(move || {
+            let _guard = state.lock().unwrap();
+            panic!("poisoning the pool lock");
+        })
+        .join()
+        .unwrap_err();
+
+        drop(reservation);
+        assert_eq!(pool.available_amount(&ASSET), 10);
+
+        Ok(())
+    }
+}
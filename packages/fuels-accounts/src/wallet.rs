@@ -1,9 +1,14 @@
-use crate::{provider::Provider, signers::private_key::PrivateKeySigner};
+use std::sync::Arc;
+
+use crate::{
+    coin_selection::CoinSelector, provider::Provider, signers::private_key::PrivateKeySigner,
+};
 
 #[derive(Debug, Clone)]
 pub struct Wallet<S = Unlocked<PrivateKeySigner>> {
     state: S,
     provider: Provider,
+    coin_selector: Option<Arc<dyn CoinSelector>>,
 }
 
 impl<S> Wallet<S> {
@@ -14,9 +19,21 @@ impl<S> Wallet<S> {
     pub fn provider(&self) -> &Provider {
         &self.provider
     }
+
+    /// Picks the resources spent by the wallet with `coin_selector` instead of leaving it to the
+    /// node.
+    pub fn set_coin_selector(&mut self, coin_selector: impl CoinSelector + 'static) {
+        self.coin_selector = Some(Arc::new(coin_selector));
+    }
+
+    pub fn with_coin_selector(mut self, coin_selector: impl CoinSelector + 'static) -> Self {
+        self.set_coin_selector(coin_selector);
+        self
+    }
 }
 
 mod unlocked {
+    #[cfg(not(target_arch = "wasm32"))]
     use async_trait::async_trait;
     use fuels_core::{
         traits::Signer,
@@ -29,7 +46,8 @@ mod unlocked {
 
     use super::{Locked, Wallet};
     use crate::{
-        Account, ViewOnlyAccount, provider::Provider, signers::private_key::PrivateKeySigner,
+        Account, ViewOnlyAccount, coin_selection::CoinSelector, provider::Provider,
+        signers::private_key::PrivateKeySigner,
     };
 
     #[derive(Debug, Clone)]
@@ -48,6 +66,7 @@ mod unlocked {
             Wallet {
                 state: Unlocked::new(signer),
                 provider,
+                coin_selector: None,
             }
         }
 
@@ -67,11 +86,14 @@ mod unlocked {
         S: Signer,
     {
         pub fn lock(&self) -> Wallet<Locked> {
-            Wallet::new_locked(self.state.signer.address(), self.provider.clone())
+            Wallet {
+                coin_selector: self.coin_selector.clone(),
+                ..Wallet::new_locked(self.state.signer.address(), self.provider.clone())
+            }
         }
     }
 
-    #[async_trait]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
     impl<S> ViewOnlyAccount for Wallet<Unlocked<S>>
     where
         S: Signer + Clone + Send + Sync + std::fmt::Debug + 'static,
@@ -84,6 +106,10 @@ mod unlocked {
             Ok(&self.provider)
         }
 
+        fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+            self.coin_selector.as_deref()
+        }
+
         async fn get_asset_inputs_for_amount(
             &self,
             asset_id: AssetId,
@@ -99,7 +125,7 @@ mod unlocked {
         }
     }
 
-    #[async_trait]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
     impl<S> Account for Wallet<Unlocked<S>>
     where
         S: Signer + Clone + Send + Sync + std::fmt::Debug + 'static,
@@ -114,13 +140,14 @@ mod unlocked {
 pub use unlocked::*;
 
 mod locked {
+    #[cfg(not(target_arch = "wasm32"))]
     use async_trait::async_trait;
     use fuels_core::types::{
         Address, AssetId, coin_type_id::CoinTypeId, errors::Result, input::Input,
     };
 
     use super::Wallet;
-    use crate::{ViewOnlyAccount, provider::Provider};
+    use crate::{ViewOnlyAccount, coin_selection::CoinSelector, provider::Provider};
 
     #[derive(Debug, Clone)]
     pub struct Locked {
@@ -138,11 +165,12 @@ mod locked {
             Self {
                 state: Locked::new(addr),
                 provider,
+                coin_selector: None,
             }
         }
     }
 
-    #[async_trait]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
     impl ViewOnlyAccount for Wallet<Locked> {
         fn address(&self) -> Address {
             self.state.address
@@ -152,6 +180,10 @@ mod locked {
             Ok(&self.provider)
         }
 
+        fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+            self.coin_selector.as_deref()
+        }
+
         async fn get_asset_inputs_for_amount(
             &self,
             asset_id: AssetId,
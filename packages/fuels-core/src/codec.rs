@@ -2,6 +2,7 @@ mod abi_decoder;
 mod abi_encoder;
 mod abi_formatter;
 mod function_selector;
+mod json;
 mod logs;
 mod utils;
 
@@ -11,6 +12,7 @@ pub use abi_decoder::*;
 pub use abi_encoder::*;
 pub use abi_formatter::*;
 pub use function_selector::*;
+pub use json::*;
 pub use logs::*;
 
 use crate::{
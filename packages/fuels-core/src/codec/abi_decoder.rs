@@ -1,12 +1,14 @@
 mod bounded_decoder;
 mod decode_as_debug_str;
 
+pub(crate) use decode_as_debug_str::decode_as_debug_str;
+
 use std::io::Read;
 
+use serde_json::Value;
+
 use crate::{
-    codec::abi_decoder::{
-        bounded_decoder::BoundedDecoder, decode_as_debug_str::decode_as_debug_str,
-    },
+    codec::{abi_decoder::bounded_decoder::BoundedDecoder, token_to_json, tokens_to_json},
     types::{Token, errors::Result, param_types::ParamType},
 };
 
@@ -127,6 +129,35 @@ impl ABIDecoder {
             .map(|(token, param_type)| decode_as_debug_str(param_type, &token))
             .collect()
     }
+
+    /// Decodes `bytes` following the schema described in `param_type` into its JSON
+    /// representation. See [`token_to_json`] for the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fuels_core::codec::ABIDecoder;
+    /// use fuels_core::types::param_types::ParamType;
+    ///
+    /// let decoder = ABIDecoder::default();
+    ///
+    /// let json = decoder.decode_as_json(&ParamType::U64, [0, 0, 0, 0, 0, 0, 0, 7].as_slice()).unwrap();
+    ///
+    /// assert_eq!(json, serde_json::json!("7"));
+    /// ```
+    pub fn decode_as_json(&self, param_type: &ParamType, mut bytes: impl Read) -> Result<Value> {
+        let token = BoundedDecoder::new(self.config).decode(param_type, &mut bytes)?;
+        token_to_json(param_type, &token)
+    }
+
+    pub fn decode_multiple_as_json(
+        &self,
+        param_types: &[ParamType],
+        mut bytes: impl Read,
+    ) -> Result<Vec<Value>> {
+        let tokens = BoundedDecoder::new(self.config).decode_multiple(param_types, &mut bytes)?;
+        tokens_to_json(param_types, &tokens)
+    }
 }
 
 #[cfg(test)]
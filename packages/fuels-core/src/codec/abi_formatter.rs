@@ -2,14 +2,19 @@ use std::{collections::HashMap, io::Read};
 
 use fuel_abi_types::abi::unified_program::UnifiedProgramABI;
 use itertools::Itertools;
+use serde_json::Value;
 
-use super::{ABIDecoder, DecoderConfig};
-use crate::{Result, error, types::param_types::ParamType};
+use super::{ABIDecoder, ABIEncoder, DecoderConfig, EncoderConfig, tokens_from_json};
+use crate::{
+    Result, error,
+    types::{Token, param_types::ParamType},
+};
 
 pub struct ABIFormatter {
     functions: HashMap<String, Vec<ParamType>>,
     configurables: Vec<(String, ParamType)>,
     decoder: ABIDecoder,
+    encoder: ABIEncoder,
 }
 
 impl ABIFormatter {
@@ -22,6 +27,11 @@ impl ABIFormatter {
         self
     }
 
+    pub fn with_encoder_config(mut self, config: EncoderConfig) -> Self {
+        self.encoder = ABIEncoder::new(config);
+        self
+    }
+
     pub fn from_abi(abi: UnifiedProgramABI) -> Result<Self> {
         let functions = abi
             .functions
@@ -65,6 +75,7 @@ impl ABIFormatter {
         Ok(Self {
             functions,
             decoder: ABIDecoder::default(),
+            encoder: ABIEncoder::default(),
             configurables,
         })
     }
@@ -75,14 +86,59 @@ impl ABIFormatter {
     }
 
     pub fn decode_fn_args<R: Read>(&self, fn_name: &str, data: R) -> Result<Vec<String>> {
-        let args = self
-            .functions
-            .get(fn_name)
-            .ok_or_else(|| error!(Codec, "Function '{}' not found in the ABI", fn_name))?;
+        let args = self.fn_inputs(fn_name)?;
 
         self.decoder.decode_multiple_as_debug_str(args, data)
     }
 
+    /// Same as `decode_fn_args` but decodes each argument into its JSON representation.
+    pub fn decode_fn_args_as_json<R: Read>(&self, fn_name: &str, data: R) -> Result<Vec<Value>> {
+        let args = self.fn_inputs(fn_name)?;
+
+        self.decoder.decode_multiple_as_json(args, data)
+    }
+
+    /// Converts the JSON `args` into the `Token`s expected by the inputs of `fn_name`.
+    /// See [`token_from_json`](super::token_from_json) for the accepted JSON format.
+    pub fn tokenize_fn_args(&self, fn_name: &str, args: &[Value]) -> Result<Vec<Token>> {
+        let inputs = self.fn_inputs(fn_name)?;
+
+        tokens_from_json(inputs, args)
+    }
+
+    /// Same as `tokenize_fn_args` but takes each argument as a string. Arguments of string
+    /// types are taken as is, so `123` is the string `"123"`. Other arguments are parsed as JSON,
+    /// falling back to a JSON string if they are not valid JSON. In both cases `"hello"` and
+    /// `hello` are equivalent.
+    pub fn tokenize_fn_str_args(
+        &self,
+        fn_name: &str,
+        args: &[impl AsRef<str>],
+    ) -> Result<Vec<Token>> {
+        let inputs = self.fn_inputs(fn_name)?;
+
+        let args = args
+            .iter()
+            .enumerate()
+            .map(|(idx, arg)| str_arg_to_json(inputs.get(idx), arg.as_ref()))
+            .collect::<Vec<_>>();
+
+        tokens_from_json(inputs, &args)
+    }
+
+    /// Encodes the JSON `args` of `fn_name` into the bytes expected by the contract.
+    pub fn encode_fn_args(&self, fn_name: &str, args: &[Value]) -> Result<Vec<u8>> {
+        let tokens = self.tokenize_fn_args(fn_name, args)?;
+
+        self.encoder.encode(&tokens)
+    }
+
+    fn fn_inputs(&self, fn_name: &str) -> Result<&Vec<ParamType>> {
+        self.functions
+            .get(fn_name)
+            .ok_or_else(|| error!(Codec, "Function '{}' not found in the ABI", fn_name))
+    }
+
     pub fn decode_configurables<R: Read>(
         &self,
         configurable_data: R,
@@ -104,12 +160,48 @@ impl ABIFormatter {
 
         Ok(decoded)
     }
+
+    /// Same as `decode_configurables` but decodes each value into its JSON representation.
+    pub fn decode_configurables_as_json<R: Read>(
+        &self,
+        configurable_data: R,
+    ) -> Result<Vec<(String, Value)>> {
+        let param_types = self
+            .configurables
+            .iter()
+            .map(|(_, param_type)| param_type)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let decoded = self
+            .decoder
+            .decode_multiple_as_json(&param_types, configurable_data)?
+            .into_iter()
+            .zip(&self.configurables)
+            .map(|(value, (name, _))| (name.clone(), value))
+            .collect();
+
+        Ok(decoded)
+    }
+}
+
+fn str_arg_to_json(param_type: Option<&ParamType>, arg: &str) -> Value {
+    let is_string = matches!(
+        param_type,
+        Some(ParamType::String | ParamType::StringSlice | ParamType::StringArray(_))
+    );
+
+    match serde_json::from_str(arg) {
+        Ok(Value::String(string)) => Value::String(string),
+        Ok(value) if !is_string => value,
+        _ => Value::String(arg.to_string()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::errors::Error;
+    use crate::types::{StaticStringToken, errors::Error};
 
     #[test]
     fn gracefully_handles_missing_fn() {
@@ -128,4 +220,176 @@ mod tests {
 
         assert_eq!(err, "Function 'non_existent_fn' not found in the ABI");
     }
+
+    #[test]
+    fn encodes_fn_args_from_json() -> Result<()> {
+        // given
+        let abi = r#"{
+            "programType": "contract",
+            "specVersion": "1",
+            "encodingVersion": "1",
+            "concreteTypes": [
+                {
+                "type": "()",
+                "concreteTypeId": "2e38e77b22c314a449e91fafed92a43826ac6aa403ae6a8acb6cf58239fbaf5d"
+                },
+                {
+                "type": "struct SomeStruct",
+                "concreteTypeId": "91804f0112892169cddf041007c9f16f95281d45c3f363e544c33dffc8179266",
+                "metadataTypeId": 0
+                },
+                {
+                "type": "u64",
+                "concreteTypeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0"
+                }
+            ],
+            "metadataTypes": [
+                {
+                "type": "struct SomeStruct",
+                "metadataTypeId": 0,
+                "components": [
+                    {
+                    "name": "field",
+                    "typeId": 1
+                    },
+                    {
+                    "name": "field_2",
+                    "typeId": 2
+                    }
+                ]
+                },
+                {
+                "type": "u32",
+                "metadataTypeId": 1
+                },
+                {
+                "type": "bool",
+                "metadataTypeId": 2
+                }
+            ],
+            "functions": [
+                {
+                "inputs": [
+                    {
+                    "name": "arg",
+                    "concreteTypeId": "91804f0112892169cddf041007c9f16f95281d45c3f363e544c33dffc8179266"
+                    },
+                    {
+                    "name": "amount",
+                    "concreteTypeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0"
+                    }
+                ],
+                "name": "set_struct",
+                "output": "2e38e77b22c314a449e91fafed92a43826ac6aa403ae6a8acb6cf58239fbaf5d",
+                "attributes": null
+                }
+            ],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        }"#;
+        let formatter = ABIFormatter::from_json_abi(abi)?;
+
+        // when
+        let encoded = formatter.encode_fn_args(
+            "set_struct",
+            &[
+                serde_json::json!({"field": 2, "field_2": true}),
+                serde_json::json!("10"),
+            ],
+        )?;
+        let str_tokens = formatter
+            .tokenize_fn_str_args("set_struct", &[r#"{"field": 2, "field_2": true}"#, "10"])?;
+
+        // then
+        let expected_tokens = vec![
+            Token::Struct(vec![Token::U32(2), Token::Bool(true)]),
+            Token::U64(10),
+        ];
+        assert_eq!(str_tokens, expected_tokens);
+        assert_eq!(encoded, ABIEncoder::default().encode(&expected_tokens)?);
+        assert_eq!(
+            formatter.decode_fn_args("set_struct", encoded.as_slice())?,
+            vec!["SomeStruct { field: 2, field_2: true }", "10"]
+        );
+        assert_eq!(
+            formatter.decode_fn_args_as_json("set_struct", encoded.as_slice())?,
+            vec![
+                serde_json::json!({"field": 2, "field_2": true}),
+                serde_json::json!("10")
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn str_args_of_string_types_are_not_parsed_as_json() -> Result<()> {
+        // given
+        let abi = r#"{
+            "programType": "contract",
+            "specVersion": "1",
+            "encodingVersion": "1",
+            "concreteTypes": [
+                {
+                "type": "()",
+                "concreteTypeId": "2e38e77b22c314a449e91fafed92a43826ac6aa403ae6a8acb6cf58239fbaf5d"
+                },
+                {
+                "type": "str",
+                "concreteTypeId": "8c25cb3686462e9a86d2883c5688a22fe738b0bbc85f458d2d2b5f3f667c6d5a"
+                },
+                {
+                "type": "str[4]",
+                "concreteTypeId": "94f0fa95c830be5e4f711963e83259fe7e8bc723278ab6ec34449e791a99b53a"
+                },
+                {
+                "type": "bool",
+                "concreteTypeId": "b760f44fa5965c2474a3b471467a22c43185152129295af588b022ae50b50903"
+                }
+            ],
+            "metadataTypes": [],
+            "functions": [
+                {
+                "inputs": [
+                    {
+                    "name": "slice",
+                    "concreteTypeId": "8c25cb3686462e9a86d2883c5688a22fe738b0bbc85f458d2d2b5f3f667c6d5a"
+                    },
+                    {
+                    "name": "array",
+                    "concreteTypeId": "94f0fa95c830be5e4f711963e83259fe7e8bc723278ab6ec34449e791a99b53a"
+                    },
+                    {
+                    "name": "flag",
+                    "concreteTypeId": "b760f44fa5965c2474a3b471467a22c43185152129295af588b022ae50b50903"
+                    }
+                ],
+                "name": "set_strings",
+                "output": "2e38e77b22c314a449e91fafed92a43826ac6aa403ae6a8acb6cf58239fbaf5d",
+                "attributes": null
+                }
+            ],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": []
+        }"#;
+        let formatter = ABIFormatter::from_json_abi(abi)?;
+
+        // when
+        let tokens = formatter.tokenize_fn_str_args("set_strings", &["123", "true", "true"])?;
+        let quoted_tokens =
+            formatter.tokenize_fn_str_args("set_strings", &[r#""123""#, "true", "true"])?;
+
+        // then
+        let expected_tokens = vec![
+            Token::StringSlice(StaticStringToken::new("123".to_string(), None)),
+            Token::StringArray(StaticStringToken::new("true".to_string(), Some(4))),
+            Token::Bool(true),
+        ];
+        assert_eq!(tokens, expected_tokens);
+        assert_eq!(quoted_tokens, expected_tokens);
+
+        Ok(())
+    }
 }
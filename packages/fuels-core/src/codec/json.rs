@@ -0,0 +1,556 @@
+use std::iter::zip;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeSeed};
+use serde_json::Value;
+
+use crate::types::{
+    StaticStringToken, Token, U256,
+    errors::{Error, Result, error},
+    param_types::ParamType,
+};
+
+/// Converts a JSON value into a [`Token`] following the schema given by `param_type`.
+///
+/// Integers are accepted as JSON numbers or as decimal or `0x`-prefixed hex strings. `b256`,
+/// `Bytes` and raw slices are given as hex strings. Structs are objects keyed by field name (or
+/// arrays of the field values in declaration order) and enums are `{"Variant": value}`, or just
+/// `"Variant"` for variants without data.
+pub fn token_from_json(param_type: &ParamType, value: &Value) -> Result<Token> {
+    let token = match param_type {
+        ParamType::Unit => match value {
+            Value::Null => Token::Unit,
+            Value::Array(elements) if elements.is_empty() => Token::Unit,
+            _ => return Err(mismatch(param_type, value)),
+        },
+        ParamType::Bool => Token::Bool(value.as_bool().ok_or_else(|| mismatch(param_type, value))?),
+        ParamType::U8 => Token::U8(narrow(param_type, value)?),
+        ParamType::U16 => Token::U16(narrow(param_type, value)?),
+        ParamType::U32 => Token::U32(narrow(param_type, value)?),
+        ParamType::U64 => Token::U64(narrow(param_type, value)?),
+        ParamType::U128 => Token::U128(parse_u128(param_type, value)?),
+        ParamType::U256 => Token::U256(parse_u256(param_type, value)?),
+        ParamType::B256 => {
+            let bytes = parse_hex(param_type, value)?;
+            let bits: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+                error!(
+                    Codec,
+                    "expected 32 bytes for `B256`, got {} in `{value}`",
+                    bytes.len()
+                )
+            })?;
+
+            Token::B256(bits)
+        }
+        ParamType::Bytes => Token::Bytes(parse_hex(param_type, value)?),
+        ParamType::RawSlice => Token::RawSlice(parse_hex(param_type, value)?),
+        ParamType::String => Token::String(as_str(param_type, value)?.to_string()),
+        ParamType::StringArray(len) => Token::StringArray(StaticStringToken::new(
+            as_str(param_type, value)?.to_string(),
+            Some(*len),
+        )),
+        ParamType::StringSlice => Token::StringSlice(StaticStringToken::new(
+            as_str(param_type, value)?.to_string(),
+            None,
+        )),
+        ParamType::Tuple(param_types) => {
+            let elements = as_array(param_type, value, Some(param_types.len()))?;
+
+            Token::Tuple(tokens_from_json(param_types, elements)?)
+        }
+        ParamType::Array(inner_type, len) => {
+            let elements = as_array(param_type, value, Some(*len))?;
+
+            Token::Array(
+                elements
+                    .iter()
+                    .map(|element| token_from_json(inner_type, element))
+                    .collect::<Result<_>>()?,
+            )
+        }
+        ParamType::Vector(inner_type) => {
+            let elements = as_array(param_type, value, None)?;
+
+            Token::Vector(
+                elements
+                    .iter()
+                    .map(|element| token_from_json(inner_type, element))
+                    .collect::<Result<_>>()?,
+            )
+        }
+        ParamType::Struct { name, fields, .. } => match value {
+            Value::Object(object) => {
+                if let Some(unknown) = object
+                    .keys()
+                    .find(|key| !fields.iter().any(|(field_name, _)| field_name == *key))
+                {
+                    return Err(error!(
+                        Codec,
+                        "struct `{name}` has no field named `{unknown}`"
+                    ));
+                }
+
+                let tokens = fields
+                    .iter()
+                    .map(|(field_name, field_type)| {
+                        let field_value = object.get(field_name).ok_or_else(|| {
+                            error!(Codec, "missing field `{field_name}` of struct `{name}`")
+                        })?;
+
+                        token_from_json(field_type, field_value)
+                    })
+                    .collect::<Result<_>>()?;
+
+                Token::Struct(tokens)
+            }
+            Value::Array(elements) => {
+                let field_types = fields
+                    .iter()
+                    .map(|(_, field_type)| field_type.clone())
+                    .collect::<Vec<_>>();
+
+                Token::Struct(tokens_from_json(&field_types, elements)?)
+            }
+            // Single field wrappers such as `Address` or `AssetId` can be given without the
+            // surrounding object
+            _ if fields.len() == 1 => Token::Struct(vec![token_from_json(&fields[0].1, value)?]),
+            _ => return Err(mismatch(param_type, value)),
+        },
+        ParamType::Enum {
+            name,
+            enum_variants,
+            ..
+        } => {
+            let (variant_name, variant_value) = match value {
+                Value::String(variant_name) => (variant_name, &Value::Null),
+                Value::Object(object) if object.len() == 1 => {
+                    object.iter().next().expect("has one entry")
+                }
+                _ => return Err(mismatch(param_type, value)),
+            };
+
+            let (discriminant, (_, variant_type)) = enum_variants
+                .variants()
+                .iter()
+                .enumerate()
+                .find(|(_, (name, _))| name == variant_name)
+                .ok_or_else(|| error!(Codec, "enum `{name}` has no variant `{variant_name}`"))?;
+
+            let token = token_from_json(variant_type, variant_value)?;
+
+            Token::Enum(Box::new((
+                discriminant as u64,
+                token,
+                enum_variants.clone(),
+            )))
+        }
+    };
+
+    Ok(token)
+}
+
+/// Converts the given JSON values into [`Token`]s, one for each of `param_types`.
+pub fn tokens_from_json(param_types: &[ParamType], values: &[Value]) -> Result<Vec<Token>> {
+    if param_types.len() != values.len() {
+        return Err(error!(
+            Codec,
+            "expected {} values, got {}",
+            param_types.len(),
+            values.len()
+        ));
+    }
+
+    zip(param_types, values)
+        .map(|(param_type, value)| token_from_json(param_type, value))
+        .collect()
+}
+
+/// Converts a [`Token`] into JSON following the schema given by `param_type`. The output uses
+/// the format accepted by [`token_from_json`], with `u64`, `u128` and `u256` rendered as decimal
+/// strings so that no precision is lost.
+pub fn token_to_json(param_type: &ParamType, token: &Token) -> Result<Value> {
+    let value = match (param_type, token) {
+        (ParamType::Unit, Token::Unit) => Value::Null,
+        (ParamType::Bool, Token::Bool(val)) => Value::Bool(*val),
+        (ParamType::U8, Token::U8(val)) => Value::from(*val),
+        (ParamType::U16, Token::U16(val)) => Value::from(*val),
+        (ParamType::U32, Token::U32(val)) => Value::from(*val),
+        (ParamType::U64, Token::U64(val)) => Value::String(val.to_string()),
+        (ParamType::U128, Token::U128(val)) => Value::String(val.to_string()),
+        (ParamType::U256, Token::U256(val)) => Value::String(val.to_string()),
+        (ParamType::B256, Token::B256(val)) => Value::String(format!("0x{}", hex::encode(val))),
+        (ParamType::Bytes, Token::Bytes(val)) | (ParamType::RawSlice, Token::RawSlice(val)) => {
+            Value::String(format!("0x{}", hex::encode(val)))
+        }
+        (ParamType::String, Token::String(val)) => Value::String(val.clone()),
+        (ParamType::StringArray(..), Token::StringArray(str_token))
+        | (ParamType::StringSlice, Token::StringSlice(str_token)) => {
+            Value::String(str_token.data.clone())
+        }
+        (ParamType::Tuple(param_types), Token::Tuple(tokens)) => {
+            Value::Array(tokens_to_json(param_types, tokens)?)
+        }
+        (ParamType::Array(inner_type, _), Token::Array(tokens))
+        | (ParamType::Vector(inner_type), Token::Vector(tokens)) => Value::Array(
+            tokens
+                .iter()
+                .map(|token| token_to_json(inner_type, token))
+                .collect::<Result<_>>()?,
+        ),
+        (ParamType::Struct { fields, .. }, Token::Struct(tokens))
+            if fields.len() == tokens.len() =>
+        {
+            Value::Object(
+                zip(fields, tokens)
+                    .map(|((field_name, field_type), token)| {
+                        Ok((field_name.clone(), token_to_json(field_type, token)?))
+                    })
+                    .collect::<Result<_>>()?,
+            )
+        }
+        (ParamType::Enum { .. }, Token::Enum(selector)) => {
+            let (discriminant, token, variants) = selector.as_ref();
+            let (variant_name, variant_type) = variants.select_variant(*discriminant)?;
+
+            let mut object = serde_json::Map::new();
+            object.insert(variant_name.clone(), token_to_json(variant_type, token)?);
+
+            Value::Object(object)
+        }
+        _ => {
+            return Err(error!(
+                Codec,
+                "cannot convert token `{token:?}` into JSON of type `{param_type:?}`"
+            ));
+        }
+    };
+
+    Ok(value)
+}
+
+/// Converts the given [`Token`]s into JSON values, one for each of `param_types`.
+pub fn tokens_to_json(param_types: &[ParamType], tokens: &[Token]) -> Result<Vec<Value>> {
+    if param_types.len() != tokens.len() {
+        return Err(error!(
+            Codec,
+            "expected {} tokens, got {}",
+            param_types.len(),
+            tokens.len()
+        ));
+    }
+
+    zip(param_types, tokens)
+        .map(|(param_type, token)| token_to_json(param_type, token))
+        .collect()
+}
+
+/// A [`Token`] together with its [`ParamType`]. Serializes into the JSON representation produced
+/// by [`token_to_json`], which can be read back through [`TokenSeed`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedToken {
+    pub param_type: ParamType,
+    pub token: Token,
+}
+
+impl TypedToken {
+    pub fn new(param_type: ParamType, token: Token) -> Self {
+        Self { param_type, token }
+    }
+}
+
+impl Serialize for TypedToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        token_to_json(&self.param_type, &self.token)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// Deserializes a [`Token`] of the given [`ParamType`] from its JSON representation.
+///
+/// # Examples
+/// ```
+/// use fuels_core::{codec::TokenSeed, types::{Token, param_types::ParamType}};
+/// use serde::de::DeserializeSeed;
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#""18446744073709551615""#);
+/// let token = TokenSeed(&ParamType::U64).deserialize(&mut deserializer).unwrap();
+///
+/// assert_eq!(token, Token::U64(u64::MAX));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TokenSeed<'a>(pub &'a ParamType);
+
+impl<'de> DeserializeSeed<'de> for TokenSeed<'_> {
+    type Value = Token;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        token_from_json(self.0, &value).map_err(serde::de::Error::custom)
+    }
+}
+
+fn mismatch(param_type: &ParamType, value: &Value) -> Error {
+    error!(
+        Codec,
+        "cannot convert JSON value `{value}` into a token of type `{param_type:?}`"
+    )
+}
+
+fn as_str<'a>(param_type: &ParamType, value: &'a Value) -> Result<&'a str> {
+    value.as_str().ok_or_else(|| mismatch(param_type, value))
+}
+
+fn as_array<'a>(
+    param_type: &ParamType,
+    value: &'a Value,
+    expected_len: Option<usize>,
+) -> Result<&'a [Value]> {
+    let elements = value
+        .as_array()
+        .ok_or_else(|| mismatch(param_type, value))?;
+
+    if let Some(expected_len) = expected_len
+        && elements.len() != expected_len
+    {
+        return Err(error!(
+            Codec,
+            "expected {expected_len} elements for `{param_type:?}`, got {}",
+            elements.len()
+        ));
+    }
+
+    Ok(elements)
+}
+
+fn parse_hex(param_type: &ParamType, value: &Value) -> Result<Vec<u8>> {
+    let hex_str = as_str(param_type, value)?;
+
+    hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str))
+        .map_err(|err| error!(Codec, "invalid hex string `{hex_str}`: {err}"))
+}
+
+fn parse_u128(param_type: &ParamType, value: &Value) -> Result<u128> {
+    match value {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(number) => match number.strip_prefix("0x") {
+            Some(hex_number) => u128::from_str_radix(hex_number, 16).ok(),
+            None => number.parse().ok(),
+        },
+        _ => None,
+    }
+    .ok_or_else(|| mismatch(param_type, value))
+}
+
+fn parse_u256(param_type: &ParamType, value: &Value) -> Result<U256> {
+    match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(number) => match number.strip_prefix("0x") {
+            Some(hex_number) => U256::from_str_radix(hex_number, 16).ok(),
+            None => U256::from_dec_str(number).ok(),
+        },
+        _ => None,
+    }
+    .ok_or_else(|| mismatch(param_type, value))
+}
+
+fn narrow<T: TryFrom<u128>>(param_type: &ParamType, value: &Value) -> Result<T> {
+    let number = parse_u128(param_type, value)?;
+
+    T::try_from(number)
+        .map_err(|_| error!(Codec, "`{number}` is out of range for `{param_type:?}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        codec::ABIEncoder,
+        traits::{Parameterize, Tokenizable},
+        types::{Address, AsciiString, Bits256, Bytes, RawSlice, SizedAsciiString, U256},
+    };
+
+    #[test]
+    fn converts_primitives() -> Result<()> {
+        assert_eq!(
+            token_from_json(&ParamType::Bool, &json!(true))?,
+            Token::Bool(true)
+        );
+        assert_eq!(
+            token_from_json(&ParamType::U8, &json!(255))?,
+            Token::U8(255)
+        );
+        assert_eq!(
+            token_from_json(&ParamType::U64, &json!("18446744073709551615"))?,
+            Token::U64(u64::MAX)
+        );
+        assert_eq!(
+            token_from_json(&ParamType::U128, &json!("0xff"))?,
+            Token::U128(255)
+        );
+        assert_eq!(
+            token_from_json(&ParamType::U256, &json!("1"))?,
+            Token::U256(U256::from(1))
+        );
+        assert_eq!(
+            token_from_json(&ParamType::Bytes, &json!("0x0102"))?,
+            Token::Bytes(vec![1, 2])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn out_of_range_integers_are_rejected() {
+        let err = token_from_json(&ParamType::U8, &json!(256)).expect_err("should fail");
+
+        assert!(err.to_string().contains("out of range"));
+    }
+
+    #[test]
+    fn hex_strings_take_a_single_prefix() {
+        let err = token_from_json(&ParamType::Bytes, &json!("0x0x12")).expect_err("should fail");
+
+        assert!(err.to_string().contains("invalid hex string"));
+    }
+
+    #[test]
+    fn converts_structs_and_enums() -> Result<()> {
+        let param_type = <(Address, Option<u32>, SizedAsciiString<3>)>::param_type();
+        let address = Address::from([1; 32]);
+
+        let token = token_from_json(
+            &param_type,
+            &json!([{"0": format!("{address:#x}")}, {"Some": 7}, "abc"]),
+        )?;
+
+        let expected = (
+            address,
+            Some(7u32),
+            SizedAsciiString::<3>::new("abc".into())?,
+        );
+        assert_eq!(token, expected.into_token());
+
+        // unit variants can be given by name and single field structs without the wrapper object
+        let token = token_from_json(
+            &param_type,
+            &json!([format!("{address:#x}"), "None", "abc"]),
+        )?;
+        let expected = (
+            address,
+            None::<u32>,
+            SizedAsciiString::<3>::new("abc".into())?,
+        );
+        assert_eq!(token, expected.into_token());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_struct_fields_are_rejected() {
+        let param_type = ParamType::Struct {
+            name: "SomeStruct".to_string(),
+            fields: vec![("field".to_string(), ParamType::U8)],
+            generics: vec![],
+        };
+
+        let err = token_from_json(&param_type, &json!({"field": 1, "other": 2}))
+            .expect_err("should fail");
+
+        assert!(err.to_string().contains("has no field named `other`"));
+    }
+
+    #[test]
+    fn converts_tokens_back_to_json() -> Result<()> {
+        let param_type = <(u64, Option<Bits256>, Vec<bool>)>::param_type();
+        let token = (u64::MAX, Some(Bits256([2; 32])), vec![true, false]).into_token();
+
+        let json = token_to_json(&param_type, &token)?;
+
+        assert_eq!(
+            json,
+            json!([
+                "18446744073709551615",
+                {"Some": format!("0x{}", "02".repeat(32))},
+                [true, false]
+            ])
+        );
+        assert_eq!(token_from_json(&param_type, &json)?, token);
+
+        Ok(())
+    }
+
+    #[test]
+    fn tokens_encode_like_typed_values() -> Result<()> {
+        let param_types = [Vec::<u64>::param_type(), AsciiString::param_type()];
+
+        let tokens = tokens_from_json(&param_types, &[json!([1, "2"]), json!("fuel")])?;
+
+        let encoder = ABIEncoder::default();
+        let expected = encoder.encode(&[
+            vec![1u64, 2].into_token(),
+            AsciiString::new("fuel".into())?.into_token(),
+        ])?;
+        assert_eq!(encoder.encode(&tokens)?, expected);
+
+        Ok(())
+    }
+
+    fn param_type_of<T: Parameterize>(_: &T) -> ParamType {
+        T::param_type()
+    }
+
+    #[test]
+    fn json_round_trip_is_lossless() -> Result<()> {
+        let value = (
+            (),
+            (true, 1u8, u16::MAX, u32::MAX, u64::MAX, u128::MAX),
+            U256::MAX,
+            Bits256([3; 32]),
+            Bytes(vec![1, 2, 3]),
+            RawSlice(vec![4, 5]),
+            (
+                String::from("fuel"),
+                SizedAsciiString::<2>::new("ab".into())?,
+                AsciiString::new("slice".into())?,
+            ),
+            [Some(1u8), None],
+            vec![Address::from([6; 32])],
+        );
+        let param_type = param_type_of(&value);
+        let token = value.into_token();
+
+        let json = serde_json::to_string(&TypedToken::new(param_type.clone(), token.clone()))
+            .expect("should serialize");
+        let decoded = TokenSeed(&param_type)
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .expect("should deserialize");
+
+        assert_eq!(decoded, token);
+        assert_eq!(
+            serde_json::from_str::<Value>(&json).expect("is valid json")[1],
+            json!([
+                true,
+                1,
+                65535,
+                4294967295u32,
+                "18446744073709551615",
+                u128::MAX.to_string()
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn mismatched_tokens_are_rejected() {
+        let err = token_to_json(&ParamType::U64, &Token::Bool(true)).expect_err("should fail");
+
+        assert!(err.to_string().contains("cannot convert token"));
+    }
+}
@@ -46,29 +46,71 @@ impl ErrorDetails {
 use fuel_tx::{ContractId, Receipt};
 
 use crate::{
-    codec::{ABIDecoder, DecoderConfig},
+    codec::{ABIDecoder, DecoderConfig, abi_decoder::decode_as_debug_str},
     traits::{Parameterize, Tokenizable},
-    types::errors::{Error, Result, error},
+    types::{
+        Token,
+        errors::{Error, Result, error},
+        param_types::ParamType,
+    },
 };
 
 #[derive(Clone)]
 pub struct LogFormatter {
-    formatter: fn(DecoderConfig, &[u8]) -> Result<String>,
-    type_id: TypeId,
+    kind: FormatterKind,
+}
+
+#[derive(Clone)]
+enum FormatterKind {
+    Typed {
+        formatter: fn(DecoderConfig, &[u8]) -> Result<String>,
+        type_id: TypeId,
+    },
+    Dynamic {
+        param_type: ParamType,
+        /// The error message of every variant, for error enums.
+        error_messages: Option<Vec<Option<String>>>,
+    },
 }
 
 impl LogFormatter {
     pub fn new_log<T: Tokenizable + Parameterize + Debug + 'static>() -> Self {
         Self {
-            formatter: Self::format_log::<T>,
-            type_id: TypeId::of::<T>(),
+            kind: FormatterKind::Typed {
+                formatter: Self::format_log::<T>,
+                type_id: TypeId::of::<T>(),
+            },
         }
     }
 
     pub fn new_error<T: Tokenizable + Parameterize + std::error::Error + 'static>() -> Self {
         Self {
-            formatter: Self::format_error::<T>,
-            type_id: TypeId::of::<T>(),
+            kind: FormatterKind::Typed {
+                formatter: Self::format_error::<T>,
+                type_id: TypeId::of::<T>(),
+            },
+        }
+    }
+
+    /// Formats logs of a type only known at runtime, e.g. read from a JSON ABI, the same way
+    /// `new_log` formats them.
+    pub fn new_dynamic_log(param_type: ParamType) -> Self {
+        Self {
+            kind: FormatterKind::Dynamic {
+                param_type,
+                error_messages: None,
+            },
+        }
+    }
+
+    /// Formats the error enum `param_type` with the `error_messages` of its variants, the same
+    /// way `new_error` formats errors.
+    pub fn new_dynamic_error(param_type: ParamType, error_messages: Vec<Option<String>>) -> Self {
+        Self {
+            kind: FormatterKind::Dynamic {
+                param_type,
+                error_messages: Some(error_messages),
+            },
         }
     }
 
@@ -90,20 +132,71 @@ impl LogFormatter {
         Ok(T::from_token(token)?.to_string())
     }
 
+    fn format_dynamic_error(
+        param_type: &ParamType,
+        error_messages: &[Option<String>],
+        token: Token,
+    ) -> Result<String> {
+        let Token::Enum(selector) = &token else {
+            return decode_as_debug_str(param_type, &token);
+        };
+        let (discriminant, value, variants) = selector.as_ref();
+        let (_, variant_type) = variants.select_variant(*discriminant)?;
+
+        let message = usize::try_from(*discriminant)
+            .ok()
+            .and_then(|idx| error_messages.get(idx))
+            .and_then(Option::as_ref);
+
+        match (message, variant_type) {
+            (Some(message), ParamType::Unit) => Ok(message.clone()),
+            (Some(message), _) => Ok(format!(
+                "{message}: {}",
+                decode_as_debug_str(variant_type, value)?
+            )),
+            (None, _) => decode_as_debug_str(param_type, &token),
+        }
+    }
+
     pub fn can_handle_type<T: Tokenizable + Parameterize + 'static>(&self) -> bool {
-        TypeId::of::<T>() == self.type_id
+        match &self.kind {
+            FormatterKind::Typed { type_id, .. } => TypeId::of::<T>() == *type_id,
+            FormatterKind::Dynamic { param_type, .. } => T::param_type() == *param_type,
+        }
     }
 
     pub fn format(&self, decoder_config: DecoderConfig, bytes: &[u8]) -> Result<String> {
-        (self.formatter)(decoder_config, bytes)
+        match &self.kind {
+            FormatterKind::Typed { formatter, .. } => formatter(decoder_config, bytes),
+            FormatterKind::Dynamic {
+                param_type,
+                error_messages,
+            } => {
+                let token = ABIDecoder::new(decoder_config).decode(param_type, bytes)?;
+
+                match error_messages {
+                    Some(error_messages) => {
+                        Self::format_dynamic_error(param_type, error_messages, token)
+                    }
+                    None => decode_as_debug_str(param_type, &token),
+                }
+            }
+        }
     }
 }
 
 impl Debug for LogFormatter {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("LogFormatter")
-            .field("type_id", &self.type_id)
-            .finish()
+        match &self.kind {
+            FormatterKind::Typed { type_id, .. } => f
+                .debug_struct("LogFormatter")
+                .field("type_id", type_id)
+                .finish(),
+            FormatterKind::Dynamic { param_type, .. } => f
+                .debug_struct("LogFormatter")
+                .field("param_type", param_type)
+                .finish(),
+        }
     }
 }
 
@@ -10,6 +10,7 @@ use crate::{error, types::errors::Result};
 mod core;
 mod dry_runner;
 pub mod errors;
+pub mod gas_price_strategy;
 pub mod param_types;
 mod token;
 pub mod transaction_builders;
@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use fuel_tx::{ConsensusParameters, Transaction as FuelTransaction};
 
-use crate::types::errors::Result;
+use crate::types::errors::{Result, error};
 
 #[derive(Debug, Clone, Copy)]
 pub struct DryRun {
@@ -31,6 +31,13 @@ pub trait DryRunner: Send + Sync {
         tx: &FuelTransaction,
         latest_chain_executor_version: Option<u32>,
     ) -> Result<FuelTransaction>;
+    /// The tips paid by the transactions of the latest `block_count` blocks.
+    async fn recent_tips(&self, _block_count: u32) -> Result<Vec<u64>> {
+        Err(error!(
+            Other,
+            "recent tips are not available from this dry runner"
+        ))
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -57,4 +64,8 @@ impl<T: DryRunner> DryRunner for &T {
             .estimate_predicates(tx, latest_chain_executor_version)
             .await
     }
+
+    async fn recent_tips(&self, block_count: u32) -> Result<Vec<u64>> {
+        (*self).recent_tips(block_count).await
+    }
 }
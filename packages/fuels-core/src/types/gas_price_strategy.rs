@@ -0,0 +1,263 @@
+//! Strategies deciding the tip of a transaction and the gas price its max fee is estimated for.
+//!
+//! Transaction builders and call handlers consult their [`GasPriceStrategy`] when building, unless
+//! the tip or the max fee are set explicitly through `TxPolicies`.
+
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+#[cfg(feature = "std")]
+use fuel_tx::{ConsensusParameters, Transaction as FuelTransaction};
+
+#[cfg(feature = "std")]
+use crate::types::DryRun;
+use crate::types::{
+    DryRunner,
+    errors::{Result, error},
+};
+
+/// The tip to pay and the gas price the max fee of a transaction is estimated for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasPriceQuote {
+    pub tip: u64,
+    pub gas_price: u64,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait GasPriceStrategy: Debug + Send + Sync {
+    /// `block_horizon` is the horizon the builder estimates the gas price for.
+    async fn quote(&self, provider: &dyn DryRunner, block_horizon: u32) -> Result<GasPriceQuote>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: GasPriceStrategy + ?Sized> GasPriceStrategy for Arc<T> {
+    async fn quote(&self, provider: &dyn DryRunner, block_horizon: u32) -> Result<GasPriceQuote> {
+        (**self).quote(provider, block_horizon).await
+    }
+}
+
+/// Always tips the same amount.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedTip(pub u64);
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasPriceStrategy for FixedTip {
+    async fn quote(&self, provider: &dyn DryRunner, block_horizon: u32) -> Result<GasPriceQuote> {
+        Ok(GasPriceQuote {
+            tip: self.0,
+            gas_price: provider.estimate_gas_price(block_horizon).await?,
+        })
+    }
+}
+
+/// Tips the given percentile of the tips paid in the latest blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecentTipsPercentile {
+    block_count: u32,
+    percentile: u8,
+}
+
+impl RecentTipsPercentile {
+    pub fn new(block_count: u32, percentile: u8) -> Result<Self> {
+        if block_count == 0 {
+            return Err(error!(Other, "`block_count` must be greater than `0`"));
+        }
+        if percentile > 100 {
+            return Err(error!(
+                Other,
+                "`percentile` must be at most `100`, got `{percentile}`"
+            ));
+        }
+
+        Ok(Self {
+            block_count,
+            percentile,
+        })
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasPriceStrategy for RecentTipsPercentile {
+    async fn quote(&self, provider: &dyn DryRunner, block_horizon: u32) -> Result<GasPriceQuote> {
+        let tips = provider.recent_tips(self.block_count).await?;
+
+        Ok(GasPriceQuote {
+            tip: percentile(tips, self.percentile),
+            gas_price: provider.estimate_gas_price(block_horizon).await?,
+        })
+    }
+}
+
+/// Outbids every transaction of the latest blocks, paying 20% more than the highest tip among
+/// them, and leaves room in the max fee for the gas price to rise over twice the usual horizon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Urgent {
+    block_count: u32,
+}
+
+impl Urgent {
+    pub fn new(block_count: u32) -> Result<Self> {
+        if block_count == 0 {
+            return Err(error!(Other, "`block_count` must be greater than `0`"));
+        }
+
+        Ok(Self { block_count })
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasPriceStrategy for Urgent {
+    async fn quote(&self, provider: &dyn DryRunner, block_horizon: u32) -> Result<GasPriceQuote> {
+        let highest_tip = provider
+            .recent_tips(self.block_count)
+            .await?
+            .into_iter()
+            .max()
+            .unwrap_or_default();
+
+        Ok(GasPriceQuote {
+            tip: urgent_tip(highest_tip),
+            gas_price: provider
+                .estimate_gas_price(block_horizon.saturating_mul(2))
+                .await?,
+        })
+    }
+}
+
+/// The nearest-rank percentile of `tips`, `0` if there are none.
+fn percentile(mut tips: Vec<u64>, percentile: u8) -> u64 {
+    if tips.is_empty() {
+        return 0;
+    }
+    tips.sort_unstable();
+
+    let rank = (tips.len() * usize::from(percentile)).div_ceil(100);
+
+    tips[rank.saturating_sub(1)]
+}
+
+fn urgent_tip(highest_tip: u64) -> u64 {
+    let premium = highest_tip.div_ceil(5);
+
+    highest_tip.saturating_add(premium).max(1)
+}
+
+/// The gas price strategy of a builder, quoted once for the builder and its clones.
+///
+/// The max fee estimation runs on a clone of the builder, so sharing the quote makes it fund the
+/// same tip and gas price the transaction is built with.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub(crate) struct QuotedStrategy {
+    strategy: Arc<dyn GasPriceStrategy>,
+    quote: Arc<Mutex<Option<(u32, GasPriceQuote)>>>,
+}
+
+#[cfg(feature = "std")]
+impl QuotedStrategy {
+    pub(crate) fn new(strategy: Arc<dyn GasPriceStrategy>) -> Self {
+        Self {
+            strategy,
+            quote: Default::default(),
+        }
+    }
+
+    /// Quotes the strategy unless it was already quoted for `block_horizon`.
+    pub(crate) async fn quote(
+        &self,
+        provider: &dyn DryRunner,
+        block_horizon: u32,
+    ) -> Result<GasPriceQuote> {
+        let cached = *self.quote.lock().expect("not poisoned");
+        if let Some((quoted_horizon, quote)) = cached
+            && quoted_horizon == block_horizon
+        {
+            return Ok(quote);
+        }
+
+        let quote = self.strategy.quote(provider, block_horizon).await?;
+        *self.quote.lock().expect("not poisoned") = Some((block_horizon, quote));
+
+        Ok(quote)
+    }
+}
+
+/// Reports the quoted gas price to the builder's max fee estimation.
+#[cfg(feature = "std")]
+pub(crate) struct QuotedGasPrice<D> {
+    pub(crate) dry_runner: D,
+    pub(crate) gas_price: Option<u64>,
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<D: DryRunner> DryRunner for QuotedGasPrice<D> {
+    async fn dry_run(&self, tx: FuelTransaction) -> Result<DryRun> {
+        self.dry_runner.dry_run(tx).await
+    }
+
+    async fn estimate_gas_price(&self, block_horizon: u32) -> Result<u64> {
+        match self.gas_price {
+            Some(gas_price) => Ok(gas_price),
+            None => self.dry_runner.estimate_gas_price(block_horizon).await,
+        }
+    }
+
+    async fn consensus_parameters(&self) -> Result<ConsensusParameters> {
+        self.dry_runner.consensus_parameters().await
+    }
+
+    async fn estimate_predicates(
+        &self,
+        tx: &FuelTransaction,
+        latest_chain_executor_version: Option<u32>,
+    ) -> Result<FuelTransaction> {
+        self.dry_runner
+            .estimate_predicates(tx, latest_chain_executor_version)
+            .await
+    }
+
+    async fn recent_tips(&self, block_count: u32) -> Result<Vec<u64>> {
+        self.dry_runner.recent_tips(block_count).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let tips = vec![40, 10, 30, 20, 50];
+
+        assert_eq!(percentile(tips.clone(), 0), 10);
+        assert_eq!(percentile(tips.clone(), 20), 10);
+        assert_eq!(percentile(tips.clone(), 50), 30);
+        assert_eq!(percentile(tips.clone(), 90), 50);
+        assert_eq!(percentile(tips, 100), 50);
+        assert_eq!(percentile(vec![], 50), 0);
+    }
+
+    #[test]
+    fn urgent_tips_outbid_the_highest_one() {
+        assert_eq!(urgent_tip(0), 1);
+        assert_eq!(urgent_tip(100), 120);
+        assert_eq!(urgent_tip(101), 122);
+        assert_eq!(urgent_tip(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        assert!(RecentTipsPercentile::new(0, 50).is_err());
+        assert!(RecentTipsPercentile::new(10, 101).is_err());
+        assert!(Urgent::new(0).is_err());
+    }
+}
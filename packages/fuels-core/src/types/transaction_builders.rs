@@ -30,6 +30,7 @@ use crate::{
         coin::Coin,
         coin_type::CoinType,
         errors::{Result, error, error_transaction},
+        gas_price_strategy::{GasPriceStrategy, QuotedGasPrice, QuotedStrategy},
         input::Input,
         message::Message,
         transaction::{
@@ -169,6 +170,7 @@ pub trait TransactionBuilder: BuildableTransaction + Send + sealed::Sealed {
     async fn estimate_max_fee(&self, provider: impl DryRunner) -> Result<u64>;
     fn enable_burn(self, enable: bool) -> Self;
     fn with_tx_policies(self, tx_policies: TxPolicies) -> Self;
+    fn tx_policies(&self) -> TxPolicies;
     fn with_inputs(self, inputs: Vec<Input>) -> Self;
     fn with_outputs(self, outputs: Vec<Output>) -> Self;
     fn with_witnesses(self, witnesses: Vec<Witness>) -> Self;
@@ -179,6 +181,9 @@ pub trait TransactionBuilder: BuildableTransaction + Send + sealed::Sealed {
     fn witnesses(&self) -> &Vec<Witness>;
     fn witnesses_mut(&mut self) -> &mut Vec<Witness>;
     fn with_estimation_horizon(self, block_horizon: u32) -> Self;
+    /// The strategy is consulted on every build for the tip, unless set in the `TxPolicies`, and
+    /// for the gas price the max fee is estimated for.
+    fn with_gas_price_strategy(self, strategy: impl GasPriceStrategy + 'static) -> Self;
 }
 
 macro_rules! impl_tx_builder_trait {
@@ -220,6 +225,12 @@ macro_rules! impl_tx_builder_trait {
                     .clone()
                     .with_build_strategy(Self::Strategy::NoSignatures);
 
+                let gas_price = fee_estimation_tb.resolve_gas_price_strategy(&provider).await?;
+                let provider = QuotedGasPrice {
+                    dry_runner: provider,
+                    gas_price,
+                };
+
                 // Add a temporary witness for every `Signer` to include them in the fee
                 // estimation.
                 let witness: Witness = Signature::default().as_ref().into();
@@ -265,6 +276,10 @@ macro_rules! impl_tx_builder_trait {
                 self
             }
 
+            fn tx_policies(&self) -> TxPolicies {
+                self.tx_policies
+            }
+
             fn with_inputs(mut self, inputs: Vec<Input>) -> Self {
                 self.inputs = inputs;
                 self
@@ -309,9 +324,36 @@ macro_rules! impl_tx_builder_trait {
 
                 self
             }
+
+            fn with_gas_price_strategy(mut self, strategy: impl GasPriceStrategy + 'static) -> Self {
+                self.gas_price_strategy = Some(QuotedStrategy::new(Arc::new(strategy)));
+
+                self
+            }
         }
 
         impl $ty {
+            /// Sets the tip quoted by the gas price strategy, unless already set, and returns the
+            /// quoted gas price. The quote is shared with the clones of the builder, so the max
+            /// fee is estimated for the tip and gas price the transaction is built with.
+            async fn resolve_gas_price_strategy(
+                &mut self,
+                provider: &impl DryRunner,
+            ) -> Result<Option<u64>> {
+                let Some(strategy) = self.gas_price_strategy.take() else {
+                    return Ok(None);
+                };
+
+                let quote = strategy
+                    .quote(provider, self.gas_price_estimation_block_horizon)
+                    .await?;
+                if self.tx_policies.tip().is_none() {
+                    self.tx_policies = self.tx_policies.with_tip(quote.tip);
+                }
+
+                Ok(Some(quote.gas_price))
+            }
+
             fn validate_no_signer_available(&self, address: &$crate::types::Address) -> Result<()> {
                 if self
                     .unresolved_witness_indexes
@@ -537,6 +579,7 @@ pub struct ScriptTransactionBuilder {
     pub build_strategy: ScriptBuildStrategy,
     unresolved_witness_indexes: UnresolvedWitnessIndexes,
     unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
+    gas_price_strategy: Option<QuotedStrategy>,
     enable_burn: bool,
 }
 
@@ -556,6 +599,7 @@ impl Default for ScriptTransactionBuilder {
             build_strategy: Default::default(),
             unresolved_witness_indexes: Default::default(),
             unresolved_signers: Default::default(),
+            gas_price_strategy: None,
             enable_burn: false,
         }
     }
@@ -576,6 +620,7 @@ pub struct CreateTransactionBuilder {
     pub build_strategy: Strategy,
     unresolved_witness_indexes: UnresolvedWitnessIndexes,
     unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
+    gas_price_strategy: Option<QuotedStrategy>,
     enable_burn: bool,
 }
 
@@ -595,6 +640,7 @@ impl Default for CreateTransactionBuilder {
             build_strategy: Default::default(),
             unresolved_witness_indexes: Default::default(),
             unresolved_signers: Default::default(),
+            gas_price_strategy: None,
             enable_burn: false,
         }
     }
@@ -621,6 +667,7 @@ pub struct UploadTransactionBuilder {
     pub build_strategy: Strategy,
     unresolved_witness_indexes: UnresolvedWitnessIndexes,
     unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
+    gas_price_strategy: Option<QuotedStrategy>,
     enable_burn: bool,
 }
 
@@ -641,6 +688,7 @@ impl Default for UploadTransactionBuilder {
             build_strategy: Default::default(),
             unresolved_witness_indexes: Default::default(),
             unresolved_signers: Default::default(),
+            gas_price_strategy: None,
             enable_burn: false,
         }
     }
@@ -659,6 +707,7 @@ pub struct UpgradeTransactionBuilder {
     pub build_strategy: Strategy,
     unresolved_witness_indexes: UnresolvedWitnessIndexes,
     unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
+    gas_price_strategy: Option<QuotedStrategy>,
     enable_burn: bool,
 }
 
@@ -675,6 +724,7 @@ impl Default for UpgradeTransactionBuilder {
             gas_price_estimation_block_horizon: GAS_ESTIMATION_BLOCK_HORIZON,
             unresolved_witness_indexes: Default::default(),
             unresolved_signers: Default::default(),
+            gas_price_strategy: None,
             max_fee_estimation_tolerance: Default::default(),
             build_strategy: Default::default(),
             enable_burn: false,
@@ -694,6 +744,15 @@ impl ScriptTransactionBuilder {
 
         let is_using_predicates = self.is_using_predicates();
 
+        let gas_price = match self.build_strategy {
+            ScriptBuildStrategy::StateReadOnly => None,
+            _ => self.resolve_gas_price_strategy(&provider).await?,
+        };
+        let provider = QuotedGasPrice {
+            dry_runner: provider,
+            gas_price,
+        };
+
         let tx = match self.build_strategy {
             ScriptBuildStrategy::Complete => self.resolve_fuel_tx(&provider).await?,
             ScriptBuildStrategy::NoSignatures => {
@@ -1007,6 +1066,12 @@ impl CreateTransactionBuilder {
 
         let is_using_predicates = self.is_using_predicates();
 
+        let gas_price = self.resolve_gas_price_strategy(&provider).await?;
+        let provider = QuotedGasPrice {
+            dry_runner: provider,
+            gas_price,
+        };
+
         let tx = match self.build_strategy {
             Strategy::Complete => self.resolve_fuel_tx(&provider).await?,
             Strategy::NoSignatures => {
@@ -1115,6 +1180,12 @@ impl UploadTransactionBuilder {
 
         let is_using_predicates = self.is_using_predicates();
 
+        let gas_price = self.resolve_gas_price_strategy(&provider).await?;
+        let provider = QuotedGasPrice {
+            dry_runner: provider,
+            gas_price,
+        };
+
         let tx = match self.build_strategy {
             Strategy::Complete => self.resolve_fuel_tx(&provider).await?,
             Strategy::NoSignatures => {
@@ -1233,6 +1304,12 @@ impl UpgradeTransactionBuilder {
         self.intercept_burn(consensus_parameters.base_asset_id())?;
 
         let is_using_predicates = self.is_using_predicates();
+
+        let gas_price = self.resolve_gas_price_strategy(&provider).await?;
+        let provider = QuotedGasPrice {
+            dry_runner: provider,
+            gas_price,
+        };
         let tx = match self.build_strategy {
             Strategy::Complete => self.resolve_fuel_tx(&provider).await?,
             Strategy::NoSignatures => {
@@ -1506,7 +1583,10 @@ async fn generate_missing_witnesses(
 
 #[cfg(test)]
 mod tests {
-    use std::iter::repeat_with;
+    use std::{
+        iter::repeat_with,
+        sync::atomic::{AtomicU64, Ordering},
+    };
 
     use fuel_crypto::Signature;
     use fuel_tx::{ConsensusParameters, UtxoId, input::coin::CoinSigned};
@@ -1600,12 +1680,14 @@ mod tests {
 
     struct MockDryRunner {
         c_param: ConsensusParameters,
+        recent_tips_calls: AtomicU64,
     }
 
     impl Default for MockDryRunner {
         fn default() -> Self {
             Self {
                 c_param: ConsensusParameters::standard(),
+                recent_tips_calls: AtomicU64::new(0),
             }
         }
     }
@@ -1636,6 +1718,13 @@ mod tests {
         ) -> Result<FuelTransaction> {
             Ok(tx.clone())
         }
+
+        async fn recent_tips(&self, _block_count: u32) -> Result<Vec<u64>> {
+            // The tips go up with every call, like on a busy chain.
+            let raise = 100 * self.recent_tips_calls.fetch_add(1, Ordering::Relaxed);
+
+            Ok([40, 10, 30, 20].map(|tip| tip + raise).to_vec())
+        }
     }
 
     #[tokio::test]
@@ -1712,6 +1801,48 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn gas_price_strategy_sets_the_tip_unless_given() -> Result<()> {
+        use crate::types::gas_price_strategy::RecentTipsPercentile;
+
+        let tb = ScriptTransactionBuilder::default()
+            .with_inputs(given_inputs(1))
+            .enable_burn(true)
+            .with_build_strategy(ScriptBuildStrategy::NoSignatures)
+            .with_gas_price_strategy(RecentTipsPercentile::new(10, 50)?);
+
+        let tx = tb.clone().build(&MockDryRunner::default()).await?;
+        assert_eq!(tx.tip(), Some(20));
+
+        let tx = tb
+            .with_tx_policies(TxPolicies::default().with_tip(5))
+            .build(&MockDryRunner::default())
+            .await?;
+        assert_eq!(tx.tip(), Some(5));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn gas_price_strategy_is_quoted_once() -> Result<()> {
+        use crate::types::gas_price_strategy::RecentTipsPercentile;
+
+        let provider = MockDryRunner::default();
+        let tb = ScriptTransactionBuilder::default()
+            .with_inputs(given_inputs(1))
+            .enable_burn(true)
+            .with_build_strategy(ScriptBuildStrategy::NoSignatures)
+            .with_gas_price_strategy(RecentTipsPercentile::new(10, 50)?);
+
+        tb.estimate_max_fee(&provider).await?;
+        let tx = tb.build(&provider).await?;
+
+        assert_eq!(tx.tip(), Some(20));
+        assert_eq!(provider.recent_tips_calls.load(Ordering::Relaxed), 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn build_w_enable_burn() -> Result<()> {
         let coin = CoinType::Coin(given_a_coin([1; 32], [2; 32], 1000));
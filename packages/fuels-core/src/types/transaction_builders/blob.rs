@@ -21,6 +21,7 @@ use crate::{
     types::{
         DryRunner,
         errors::{Result, error, error_transaction},
+        gas_price_strategy::{GasPriceStrategy, QuotedGasPrice, QuotedStrategy},
         input::Input,
         transaction::{BlobTransaction, EstimablePredicates, Transaction, TxPolicies},
     },
@@ -99,6 +100,7 @@ pub struct BlobTransactionBuilder {
     pub blob: Blob,
     unresolved_witness_indexes: UnresolvedWitnessIndexes,
     unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
+    gas_price_strategy: Option<QuotedStrategy>,
     enable_burn: bool,
 }
 
@@ -115,6 +117,7 @@ impl Default for BlobTransactionBuilder {
             blob: Default::default(),
             unresolved_witness_indexes: Default::default(),
             unresolved_signers: Default::default(),
+            gas_price_strategy: None,
             enable_burn: false,
         }
     }
@@ -152,6 +155,12 @@ impl BlobTransactionBuilder {
 
         let is_using_predicates = self.is_using_predicates();
 
+        let gas_price = self.resolve_gas_price_strategy(&provider).await?;
+        let provider = QuotedGasPrice {
+            dry_runner: provider,
+            gas_price,
+        };
+
         let tx = match self.build_strategy {
             Strategy::Complete => self.resolve_fuel_tx(&provider).await?,
             Strategy::NoSignatures => {
@@ -0,0 +1,424 @@
+use fuel_crypto::{Message, Signature};
+use fuel_tx::{
+    Bytes32, Input, Transaction as FuelTransaction, UniqueIdentifier, Witness,
+    field::{Inputs, Witnesses},
+};
+use fuel_types::{
+    ChainId,
+    canonical::{Deserialize as _, Serialize as _},
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    traits::Signer,
+    types::{
+        Address,
+        errors::{Error, Result, error},
+    },
+};
+
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PortableTransactionJson {
+    version: u32,
+    chain_id: u64,
+    #[serde(rename = "type")]
+    tx_type: String,
+    signing_message: String,
+    transaction: String,
+    signers: Vec<SignerJson>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignerJson {
+    address: String,
+    witness_index: u16,
+    signed: bool,
+}
+
+/// A built transaction together with everything needed to sign it on another machine.
+///
+/// The transaction is usually built online by a wallet using a
+/// `FakeSigner`, which leaves a zeroed signature in each witness slot so that the fee
+/// accounts for the final size. The exported JSON holds the chain id, the signing message, the
+/// hex encoded transaction and the witness slot of every required signer. An offline machine
+/// imports it, [signs](PortableTransaction::sign) it and exports it again, after which the
+/// transaction can be [extracted](PortableTransaction::into_transaction) and submitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortableTransaction {
+    chain_id: ChainId,
+    tx: FuelTransaction,
+}
+
+impl PortableTransaction {
+    pub fn new(tx: impl Into<FuelTransaction>, chain_id: ChainId) -> Result<Self> {
+        let tx = tx.into();
+        inputs(&tx)?;
+
+        Ok(Self { chain_id, tx })
+    }
+
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
+    pub fn id(&self) -> Bytes32 {
+        self.tx.id(&self.chain_id)
+    }
+
+    /// The message every required signer has to sign.
+    pub fn signing_message(&self) -> Message {
+        Message::from_bytes(*self.id())
+    }
+
+    /// Addresses whose signature the transaction requires, in witness order.
+    pub fn required_signers(&self) -> Vec<Address> {
+        self.signer_slots()
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect()
+    }
+
+    /// Required signers that didn't sign the transaction yet.
+    pub fn missing_signers(&self) -> Vec<Address> {
+        self.signer_slots()
+            .into_iter()
+            .filter(|(_, witness_index)| !self.is_signed(*witness_index))
+            .map(|(address, _)| address)
+            .collect()
+    }
+
+    /// The signers required by the inputs together with the index of their witness.
+    pub fn signer_slots(&self) -> Vec<(Address, u16)> {
+        self.inputs()
+            .iter()
+            .filter_map(|input| Some((*input.input_owner()?, input.witness_index()?)))
+            .unique()
+            .sorted_by_key(|(_, witness_index)| *witness_index)
+            .collect()
+    }
+
+    /// Signs the transaction with `signer`, filling the witness slot of its address.
+    pub async fn sign(&mut self, signer: &(impl Signer + Send + Sync)) -> Result<Signature> {
+        let address = signer.address();
+        let witness_indexes = self
+            .signer_slots()
+            .into_iter()
+            .filter(|(owner, _)| *owner == address)
+            .map(|(_, witness_index)| witness_index)
+            .collect::<Vec<_>>();
+
+        if witness_indexes.is_empty() {
+            return Err(error!(
+                Other,
+                "transaction does not require a signature from `{address}`"
+            ));
+        }
+
+        let signature = signer.sign(self.signing_message()).await?;
+        for witness_index in witness_indexes {
+            self.set_witness(witness_index, signature.as_ref().into())?;
+        }
+
+        Ok(signature)
+    }
+
+    /// Signs the transaction with `signer` and stores the signature at `witness_index`.
+    ///
+    /// Meant for signatures that are checked by predicates rather than by signed inputs. The
+    /// slots of signed inputs can only be filled by their owners.
+    pub async fn sign_witness(
+        &mut self,
+        witness_index: u16,
+        signer: &(impl Signer + Send + Sync),
+    ) -> Result<Signature> {
+        let address = signer.address();
+        if let Some((owner, _)) = self
+            .signer_slots()
+            .into_iter()
+            .find(|(owner, index)| *index == witness_index && *owner != address)
+        {
+            return Err(error!(
+                Other,
+                "witness `{witness_index}` is reserved for the signature of `{owner}`"
+            ));
+        }
+
+        let signature = signer.sign(self.signing_message()).await?;
+        self.set_witness(witness_index, signature.as_ref().into())?;
+
+        Ok(signature)
+    }
+
+    pub fn inputs(&self) -> &[Input] {
+        inputs(&self.tx).expect("checked on creation")
+    }
+
+    pub fn witnesses(&self) -> &[Witness] {
+        witnesses(&self.tx)
+    }
+
+    /// Returns the transaction, failing if any required signature is missing.
+    pub fn into_transaction<T>(self) -> Result<T>
+    where
+        T: TryFrom<FuelTransaction, Error = Error>,
+    {
+        let missing_signers = self.missing_signers();
+        if !missing_signers.is_empty() {
+            return Err(error!(
+                Other,
+                "transaction is missing signatures from: {}",
+                missing_signers.iter().join(", ")
+            ));
+        }
+
+        T::try_from(self.tx)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let signers = self
+            .signer_slots()
+            .into_iter()
+            .map(|(address, witness_index)| SignerJson {
+                address: format!("{address:#x}"),
+                witness_index,
+                signed: self.is_signed(witness_index),
+            })
+            .collect();
+
+        let json = PortableTransactionJson {
+            version: FORMAT_VERSION,
+            chain_id: *self.chain_id,
+            tx_type: tx_type(&self.tx).to_string(),
+            signing_message: format!("{:#x}", self.id()),
+            transaction: format!("0x{}", hex::encode(self.tx.to_bytes())),
+            signers,
+        };
+
+        serde_json::to_string_pretty(&json).map_err(|err| error!(Codec, "{err}"))
+    }
+
+    /// Imports a transaction exported with [`PortableTransaction::to_json`], checking that the
+    /// declared signing message and signers match the encoded transaction.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let json: PortableTransactionJson =
+            serde_json::from_str(json).map_err(|err| error!(Codec, "{err}"))?;
+
+        if json.version != FORMAT_VERSION {
+            return Err(error!(
+                Codec,
+                "unsupported portable transaction version `{}`", json.version
+            ));
+        }
+
+        let transaction = &json.transaction;
+        let bytes = hex::decode(transaction.strip_prefix("0x").unwrap_or(transaction))
+            .map_err(|err| error!(Codec, "invalid transaction hex: {err}"))?;
+        let tx = FuelTransaction::from_bytes(&bytes)
+            .map_err(|err| error!(Codec, "invalid transaction encoding: {err:?}"))?;
+
+        let portable = Self::new(tx, json.chain_id.into())?;
+
+        let signing_message = format!("{:#x}", portable.id());
+        if json.signing_message != signing_message {
+            return Err(error!(
+                Codec,
+                "declared signing message `{}` doesn't match the transaction's `{signing_message}`",
+                json.signing_message
+            ));
+        }
+
+        let declared_signers = json
+            .signers
+            .iter()
+            .map(|signer| (signer.address.to_lowercase(), signer.witness_index))
+            .collect::<Vec<_>>();
+        let signers = portable
+            .signer_slots()
+            .into_iter()
+            .map(|(address, witness_index)| (format!("{address:#x}"), witness_index))
+            .collect::<Vec<_>>();
+        if declared_signers != signers {
+            return Err(error!(
+                Codec,
+                "declared signers don't match the inputs of the transaction"
+            ));
+        }
+
+        Ok(portable)
+    }
+
+    fn set_witness(&mut self, witness_index: u16, witness: Witness) -> Result<()> {
+        let witness_index = usize::from(witness_index);
+        let witnesses = witnesses_mut(&mut self.tx)?;
+        if witnesses.len() <= witness_index {
+            witnesses.resize(witness_index + 1, Witness::default());
+        }
+        witnesses[witness_index] = witness;
+
+        Ok(())
+    }
+
+    fn is_signed(&self, witness_index: u16) -> bool {
+        witnesses(&self.tx)
+            .get(usize::from(witness_index))
+            .is_some_and(|witness| {
+                witness.as_vec().len() == Signature::LEN
+                    && witness.as_vec().as_slice() != Signature::default().as_ref()
+            })
+    }
+}
+
+fn tx_type(tx: &FuelTransaction) -> &'static str {
+    match tx {
+        FuelTransaction::Script(_) => "script",
+        FuelTransaction::Create(_) => "create",
+        FuelTransaction::Mint(_) => "mint",
+        FuelTransaction::Upgrade(_) => "upgrade",
+        FuelTransaction::Upload(_) => "upload",
+        FuelTransaction::Blob(_) => "blob",
+    }
+}
+
+fn inputs(tx: &FuelTransaction) -> Result<&Vec<Input>> {
+    match tx {
+        FuelTransaction::Script(tx) => Ok(tx.inputs()),
+        FuelTransaction::Create(tx) => Ok(tx.inputs()),
+        FuelTransaction::Upgrade(tx) => Ok(tx.inputs()),
+        FuelTransaction::Upload(tx) => Ok(tx.inputs()),
+        FuelTransaction::Blob(tx) => Ok(tx.inputs()),
+        FuelTransaction::Mint(_) => Err(error!(Other, "`Mint` transactions cannot be signed")),
+    }
+}
+
+fn witnesses(tx: &FuelTransaction) -> &[Witness] {
+    match tx {
+        FuelTransaction::Script(tx) => tx.witnesses(),
+        FuelTransaction::Create(tx) => tx.witnesses(),
+        FuelTransaction::Upgrade(tx) => tx.witnesses(),
+        FuelTransaction::Upload(tx) => tx.witnesses(),
+        FuelTransaction::Blob(tx) => tx.witnesses(),
+        FuelTransaction::Mint(_) => &[],
+    }
+}
+
+fn witnesses_mut(tx: &mut FuelTransaction) -> Result<&mut Vec<Witness>> {
+    match tx {
+        FuelTransaction::Script(tx) => Ok(tx.witnesses_mut()),
+        FuelTransaction::Create(tx) => Ok(tx.witnesses_mut()),
+        FuelTransaction::Upgrade(tx) => Ok(tx.witnesses_mut()),
+        FuelTransaction::Upload(tx) => Ok(tx.witnesses_mut()),
+        FuelTransaction::Blob(tx) => Ok(tx.witnesses_mut()),
+        FuelTransaction::Mint(_) => Err(error!(Other, "`Mint` transactions cannot be signed")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use fuel_crypto::SecretKey;
+    use fuel_tx::{AssetId, Output, TxPointer, UtxoId};
+
+    use super::*;
+    use crate::types::transaction::ScriptTransaction;
+
+    struct TestSigner(SecretKey);
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl Signer for TestSigner {
+        async fn sign(&self, message: Message) -> Result<Signature> {
+            Ok(Signature::sign(&self.0, &message))
+        }
+
+        fn address(&self) -> Address {
+            Address::from(*self.0.public_key().hash())
+        }
+    }
+
+    fn unsigned_tx(owners: &[Address]) -> FuelTransaction {
+        let inputs = owners
+            .iter()
+            .enumerate()
+            .map(|(idx, owner)| {
+                Input::coin_signed(
+                    UtxoId::new(Bytes32::from([idx as u8; 32]), 0),
+                    *owner,
+                    100,
+                    AssetId::zeroed(),
+                    TxPointer::default(),
+                    idx as u16,
+                )
+            })
+            .collect();
+        let witnesses = vec![Signature::default().as_ref().into(); owners.len()];
+
+        FuelTransaction::script(
+            0,
+            vec![],
+            vec![],
+            Default::default(),
+            inputs,
+            vec![Output::change(owners[0], 0, AssetId::zeroed())],
+            witnesses,
+        )
+        .into()
+    }
+
+    #[tokio::test]
+    async fn can_be_signed_after_a_json_round_trip() -> Result<()> {
+        let signers = [
+            TestSigner(SecretKey::try_from([1; 32].as_slice()).unwrap()),
+            TestSigner(SecretKey::try_from([7; 32].as_slice()).unwrap()),
+        ];
+        let owners = signers.iter().map(Signer::address).collect::<Vec<_>>();
+        let chain_id = ChainId::new(9);
+
+        let portable = PortableTransaction::new(unsigned_tx(&owners), chain_id)?;
+        assert_eq!(portable.missing_signers(), owners);
+        assert!(
+            portable
+                .clone()
+                .into_transaction::<ScriptTransaction>()
+                .is_err()
+        );
+
+        let mut imported = PortableTransaction::from_json(&portable.to_json()?)?;
+        assert_eq!(imported, portable);
+
+        for signer in &signers {
+            let signature = imported.sign(signer).await?;
+            let recovered = signature.recover(&imported.signing_message())?;
+            assert_eq!(Address::from(*recovered.hash()), signer.address());
+
+            imported = PortableTransaction::from_json(&imported.to_json()?)?;
+        }
+
+        assert!(imported.missing_signers().is_empty());
+        assert_eq!(imported.id(), portable.id());
+        imported.into_transaction::<ScriptTransaction>()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn tampered_files_are_rejected() -> Result<()> {
+        let owner = TestSigner(SecretKey::try_from([1; 32].as_slice()).unwrap()).address();
+        let portable = PortableTransaction::new(unsigned_tx(&[owner]), ChainId::new(0))?;
+
+        let json = portable.to_json()?;
+        let other_chain = json.replace("\"chainId\": 0", "\"chainId\": 1");
+
+        let err = PortableTransaction::from_json(&other_chain).expect_err("should fail");
+        assert!(err.to_string().contains("declared signing message"));
+
+        let double_prefix = json.replace("\"transaction\": \"0x", "\"transaction\": \"0x0x");
+        let err = PortableTransaction::from_json(&double_prefix).expect_err("should fail");
+        assert!(err.to_string().contains("invalid transaction hex"));
+
+        Ok(())
+    }
+}
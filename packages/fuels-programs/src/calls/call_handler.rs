@@ -16,8 +16,10 @@ use fuels_core::{
     types::{
         Address, AssetId, Bytes32, ContractId, Selector, Token,
         errors::{Error, Result, error, transaction::Reason},
+        gas_price_strategy::GasPriceStrategy,
         input::Input,
         output::Output,
+        param_types::ParamType,
         transaction::{ScriptTransaction, Transaction, TxPolicies},
         transaction_builders::{
             BuildableTransaction, ScriptBuildStrategy, ScriptTransactionBuilder,
@@ -49,6 +51,7 @@ pub struct CallHandler<A, C, T> {
     cached_tx_id: Option<Bytes32>,
     variable_output_policy: VariableOutputPolicy,
     unresolved_signers: Vec<Arc<dyn Signer + Send + Sync>>,
+    gas_price_strategy: Option<Arc<dyn GasPriceStrategy>>,
 }
 
 impl<A, C, T> CallHandler<A, C, T> {
@@ -85,13 +88,19 @@ impl<A, C, T> CallHandler<A, C, T> {
         self.unresolved_signers.push(Arc::new(signer));
         self
     }
+
+    /// Sets the strategy consulted for the tip, unless set in the `TxPolicies`, and for the gas
+    /// price the max fee is estimated for.
+    pub fn with_gas_price_strategy(mut self, strategy: impl GasPriceStrategy + 'static) -> Self {
+        self.gas_price_strategy = Some(Arc::new(strategy));
+        self
+    }
 }
 
 impl<A, C, T> CallHandler<A, C, T>
 where
     A: Account,
     C: TransactionTuner,
-    T: Tokenizable + Parameterize + Debug,
 {
     pub async fn transaction_builder(&self) -> Result<ScriptTransactionBuilder> {
         let consensus_parameters = self.account.try_provider()?.consensus_parameters().await?;
@@ -127,6 +136,10 @@ where
 
         tb.add_signers(&self.unresolved_signers)?;
 
+        if let Some(strategy) = &self.gas_price_strategy {
+            tb = tb.with_gas_price_strategy(Arc::clone(strategy));
+        }
+
         Ok(tb)
     }
 
@@ -152,13 +165,46 @@ where
 
         Ok(transaction_cost)
     }
+
+    async fn send_and_await_commit(&mut self) -> Result<TxStatus> {
+        let tx = self.build_tx().await?;
+        let provider = self.account.try_provider()?;
+
+        let consensus_parameters = provider.consensus_parameters().await?;
+        let chain_id = consensus_parameters.chain_id();
+        self.cached_tx_id = Some(tx.id(chain_id));
+
+        provider.send_transaction_and_await_commit(tx).await
+    }
+
+    async fn simulate_tx_status(
+        &self,
+        Execution {
+            execution_type,
+            at_height,
+        }: Execution,
+    ) -> Result<TxStatus> {
+        let provider = self.account.try_provider()?;
+
+        if let ExecutionType::StateReadOnly = execution_type {
+            let tx = self
+                .transaction_builder()
+                .await?
+                .with_build_strategy(ScriptBuildStrategy::StateReadOnly)
+                .build(provider)
+                .await?;
+
+            provider.dry_run_opt(tx, false, Some(0), at_height).await
+        } else {
+            let tx = self.build_tx().await?;
+            provider.dry_run_opt(tx, true, None, at_height).await
+        }
+    }
 }
 
 impl<A, C, T> CallHandler<A, C, T>
 where
-    A: Account,
-    C: ContractDependencyConfigurator + TransactionTuner + ResponseParser,
-    T: Tokenizable + Parameterize + Debug,
+    C: ContractDependencyConfigurator,
 {
     /// Sets external contracts as dependencies to this contract's call.
     /// Effectively, this will be used to create [`fuel_tx::Input::Contract`]/[`fuel_tx::Output::Contract`]
@@ -195,17 +241,17 @@ where
 
         self
     }
+}
 
+impl<A, C, T> CallHandler<A, C, T>
+where
+    A: Account,
+    C: ContractDependencyConfigurator + TransactionTuner + ResponseParser,
+    T: Tokenizable + Parameterize + Debug,
+{
     /// Call a contract's method on the node, in a state-modifying manner.
     pub async fn call(mut self) -> Result<CallResponse<T>> {
-        let tx = self.build_tx().await?;
-        let provider = self.account.try_provider()?;
-
-        let consensus_parameters = provider.consensus_parameters().await?;
-        let chain_id = consensus_parameters.chain_id();
-        self.cached_tx_id = Some(tx.id(chain_id));
-
-        let tx_status = provider.send_transaction_and_await_commit(tx).await?;
+        let tx_status = self.send_and_await_commit().await?;
 
         self.get_response(tx_status)
     }
@@ -222,28 +268,8 @@ where
 
     /// Call a contract's method on the node, in a simulated manner, meaning the state of the
     /// blockchain is *not* modified but simulated.
-    pub async fn simulate(
-        &mut self,
-        Execution {
-            execution_type,
-            at_height,
-        }: Execution,
-    ) -> Result<CallResponse<T>> {
-        let provider = self.account.try_provider()?;
-
-        let tx_status = if let ExecutionType::StateReadOnly = execution_type {
-            let tx = self
-                .transaction_builder()
-                .await?
-                .with_build_strategy(ScriptBuildStrategy::StateReadOnly)
-                .build(provider)
-                .await?;
-
-            provider.dry_run_opt(tx, false, Some(0), at_height).await?
-        } else {
-            let tx = self.build_tx().await?;
-            provider.dry_run_opt(tx, true, None, at_height).await?
-        };
+    pub async fn simulate(&mut self, execution: Execution) -> Result<CallResponse<T>> {
+        let tx_status = self.simulate_tx_status(execution).await?;
 
         self.get_response(tx_status)
     }
@@ -295,18 +321,20 @@ where
         is_payable: bool,
         encoder_config: EncoderConfig,
     ) -> Self {
-        let call = ContractCall {
+        let call = ContractCall::new(
             contract_id,
             encoded_selector,
-            encoded_args: ABIEncoder::new(encoder_config).encode(args),
-            call_parameters: CallParameters::default(),
-            external_contracts: vec![],
-            output_param: T::param_type(),
+            ABIEncoder::new(encoder_config).encode(args),
+            T::param_type(),
             is_payable,
-            custom_assets: Default::default(),
-            inputs: vec![],
-            outputs: vec![],
-        };
+        );
+
+        Self::from_contract_call(account, call, log_decoder)
+    }
+}
+
+impl<A, T> CallHandler<A, ContractCall, T> {
+    fn from_contract_call(account: A, call: ContractCall, log_decoder: LogDecoder) -> Self {
         CallHandler {
             account,
             call,
@@ -317,9 +345,83 @@ where
             cached_tx_id: None,
             variable_output_policy: VariableOutputPolicy::default(),
             unresolved_signers: vec![],
+            gas_price_strategy: None,
         }
     }
+}
+
+/// A contract method whose signature is only known at runtime, e.g. when the contract's ABI was
+/// loaded from JSON.
+#[derive(Debug, Clone)]
+pub struct DynamicMethod {
+    pub encoded_selector: Selector,
+    pub output_param: ParamType,
+    pub is_payable: bool,
+}
+
+impl<A> CallHandler<A, ContractCall, Token> {
+    /// Creates a call to `method`, whose output type is only known at runtime. The response
+    /// holds the decoded [`Token`].
+    pub fn new_dynamic_contract_call(
+        contract_id: ContractId,
+        account: A,
+        method: DynamicMethod,
+        args: &[Token],
+        log_decoder: LogDecoder,
+        encoder_config: EncoderConfig,
+    ) -> Self {
+        let call = ContractCall::new(
+            contract_id,
+            method.encoded_selector,
+            ABIEncoder::new(encoder_config).encode(args),
+            method.output_param,
+            method.is_payable,
+        );
+
+        Self::from_contract_call(account, call, log_decoder)
+    }
+}
+
+impl<A> CallHandler<A, ContractCall, Token>
+where
+    A: Account,
+{
+    /// Same as `call` but decodes the output using the `output_param` of the underlying
+    /// [`ContractCall`].
+    pub async fn call_dynamic(mut self) -> Result<CallResponse<Token>> {
+        let tx_status = self.send_and_await_commit().await?;
+
+        self.get_dynamic_response(tx_status)
+    }
+
+    /// Same as `simulate` but decodes the output using the `output_param` of the underlying
+    /// [`ContractCall`].
+    pub async fn simulate_dynamic(&mut self, execution: Execution) -> Result<CallResponse<Token>> {
+        let tx_status = self.simulate_tx_status(execution).await?;
+
+        self.get_dynamic_response(tx_status)
+    }
+
+    /// Create a [`CallResponse`] holding the output `Token` from `TxStatus`
+    pub fn get_dynamic_response(&self, tx_status: TxStatus) -> Result<CallResponse<Token>> {
+        let success = tx_status.take_success_checked(Some(&self.log_decoder))?;
+
+        let token = self.call.parse_call(
+            &success.receipts,
+            self.decoder_config,
+            &self.call.output_param,
+        )?;
+
+        Ok(CallResponse {
+            value: token,
+            log_decoder: self.log_decoder.clone(),
+            tx_id: self.cached_tx_id,
+            tx_status: success,
+        })
+    }
+}
 
+impl<A, T> CallHandler<A, ContractCall, T> {
     /// Adds a custom `asset_id` with its `amount` and an optional `address` to be used for
     /// generating outputs to this contract's call.
     ///
@@ -405,6 +507,7 @@ where
             cached_tx_id: None,
             variable_output_policy: VariableOutputPolicy::default(),
             unresolved_signers: vec![],
+            gas_price_strategy: None,
         }
     }
 
@@ -438,6 +541,7 @@ where
             cached_tx_id: None,
             variable_output_policy: VariableOutputPolicy::default(),
             unresolved_signers: vec![],
+            gas_price_strategy: None,
         }
     }
 
@@ -27,6 +27,27 @@ pub struct ContractCall {
 }
 
 impl ContractCall {
+    pub(crate) fn new(
+        contract_id: ContractId,
+        encoded_selector: Selector,
+        encoded_args: Result<Vec<u8>>,
+        output_param: ParamType,
+        is_payable: bool,
+    ) -> Self {
+        Self {
+            contract_id,
+            encoded_args,
+            encoded_selector,
+            call_parameters: CallParameters::default(),
+            external_contracts: vec![],
+            output_param,
+            is_payable,
+            custom_assets: Default::default(),
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
     pub(crate) fn data(&self, base_asset_id: AssetId) -> Result<ContractCallData> {
         let encoded_args = self
             .encoded_args
@@ -44,6 +44,9 @@ mod loader;
 // reexported to avoid doing a breaking change
 pub use loader::*;
 
+mod dynamic;
+pub use dynamic::*;
+
 pub use crate::assembly::contract_call::loader_contract_asm;
 
 fn compute_contract_id_and_state_root(
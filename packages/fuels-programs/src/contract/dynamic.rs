@@ -0,0 +1,427 @@
+use std::{collections::HashMap, sync::Arc};
+
+use fuel_abi_types::abi::unified_program::UnifiedProgramABI;
+use fuel_tx::ContractId;
+use fuels_core::{
+    codec::{
+        EncoderConfig, ErrorDetails, LogDecoder, LogFormatter, encode_fn_selector,
+        log_formatters_lookup, token_to_json, tokens_from_json,
+    },
+    types::{
+        Token,
+        errors::{Result, error},
+        param_types::ParamType,
+    },
+};
+use serde_json::Value;
+
+use crate::calls::{CallHandler, ContractCall, ContractDependency, DynamicMethod};
+
+#[derive(Debug, Clone)]
+struct DynamicFunction {
+    inputs: Vec<ParamType>,
+    output: ParamType,
+    is_payable: bool,
+}
+
+/// A handle to a deployed contract whose ABI is only known at runtime.
+///
+/// Unlike the contract instances generated by `abigen!`, methods are looked up by name and their
+/// arguments are given as JSON (see [`fuels_core::codec::token_from_json`]) or as [`Token`]s.
+/// The resulting [`CallHandler`]s return the output as a [`Token`] through `call_dynamic` and
+/// `simulate_dynamic`, which can be turned into JSON with [`DynamicContract::output_to_json`].
+#[derive(Debug, Clone)]
+pub struct DynamicContract<A> {
+    contract_id: ContractId,
+    account: A,
+    functions: Arc<HashMap<String, DynamicFunction>>,
+    log_decoder: LogDecoder,
+    encoder_config: EncoderConfig,
+}
+
+impl<A> DynamicContract<A> {
+    pub fn from_json_abi(
+        contract_id: ContractId,
+        account: A,
+        abi: impl AsRef<str>,
+    ) -> Result<Self> {
+        let abi = UnifiedProgramABI::from_json_abi(abi.as_ref())?;
+
+        Self::from_abi(contract_id, account, abi)
+    }
+
+    pub fn from_abi(contract_id: ContractId, account: A, abi: UnifiedProgramABI) -> Result<Self> {
+        let type_lookup = abi
+            .types
+            .iter()
+            .map(|decl| (decl.type_id, decl.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let functions = abi
+            .functions
+            .iter()
+            .map(|fun| {
+                let inputs = fun
+                    .inputs
+                    .iter()
+                    .map(|input| ParamType::try_from_type_application(input, &type_lookup))
+                    .collect::<Result<Vec<_>>>()?;
+                let output = ParamType::try_from_type_application(&fun.output, &type_lookup)?;
+                let is_payable = fun
+                    .attributes
+                    .iter()
+                    .flatten()
+                    .any(|attr| attr.name == "payable");
+
+                Ok((
+                    fun.name.clone(),
+                    DynamicFunction {
+                        inputs,
+                        output,
+                        is_payable,
+                    },
+                ))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        let error_codes = abi
+            .error_codes
+            .into_iter()
+            .flatten()
+            .map(|(id, details)| {
+                (
+                    id,
+                    ErrorDetails::new(
+                        details.pos.pkg,
+                        details.pos.file,
+                        details.pos.line,
+                        details.pos.column,
+                        details.log_id,
+                        details.msg,
+                    ),
+                )
+            })
+            .collect();
+
+        let log_formatters = abi
+            .logged_types
+            .iter()
+            .flatten()
+            .map(|logged_type| {
+                let param_type =
+                    ParamType::try_from_type_application(&logged_type.application, &type_lookup)?;
+                let error_messages = type_lookup
+                    .get(&logged_type.application.type_id)
+                    .and_then(|decl| decl.components.as_ref())
+                    .map(|components| {
+                        components
+                            .iter()
+                            .map(|component| component.error_message.clone())
+                            .collect::<Vec<_>>()
+                    })
+                    .filter(|messages| messages.iter().any(Option::is_some));
+
+                let log_formatter = match error_messages {
+                    Some(error_messages) => {
+                        LogFormatter::new_dynamic_error(param_type, error_messages)
+                    }
+                    None => LogFormatter::new_dynamic_log(param_type),
+                };
+
+                Ok((logged_type.log_id.clone(), log_formatter))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            contract_id,
+            account,
+            functions: Arc::new(functions),
+            log_decoder: LogDecoder::new(
+                log_formatters_lookup(log_formatters, contract_id),
+                error_codes,
+            ),
+            encoder_config: EncoderConfig::default(),
+        })
+    }
+
+    pub fn contract_id(&self) -> ContractId {
+        self.contract_id
+    }
+
+    pub fn account(&self) -> &A {
+        &self.account
+    }
+
+    pub fn with_account<U>(self, account: U) -> DynamicContract<U> {
+        DynamicContract {
+            contract_id: self.contract_id,
+            account,
+            functions: self.functions,
+            log_decoder: self.log_decoder,
+            encoder_config: self.encoder_config,
+        }
+    }
+
+    pub fn with_encoder_config(mut self, encoder_config: EncoderConfig) -> Self {
+        self.encoder_config = encoder_config;
+        self
+    }
+
+    pub fn has_method(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    /// Returns the names of all the methods found in the ABI.
+    pub fn method_names(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(String::as_str)
+    }
+
+    fn function(&self, name: &str) -> Result<&DynamicFunction> {
+        self.functions
+            .get(name)
+            .ok_or_else(|| error!(Other, "method `{name}` not found in the contract ABI"))
+    }
+
+    /// Converts the output `token` of method `name` into JSON.
+    pub fn output_to_json(&self, name: &str, token: &Token) -> Result<Value> {
+        token_to_json(&self.function(name)?.output, token)
+    }
+}
+
+impl<A: Clone> DynamicContract<A> {
+    /// Creates a call to method `name` with the arguments given as JSON values.
+    pub fn method(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<CallHandler<A, ContractCall, Token>> {
+        let tokens = tokens_from_json(&self.function(name)?.inputs, args)?;
+
+        self.method_with_tokens(name, &tokens)
+    }
+
+    /// Creates a call to method `name` with already tokenized arguments.
+    pub fn method_with_tokens(
+        &self,
+        name: &str,
+        args: &[Token],
+    ) -> Result<CallHandler<A, ContractCall, Token>> {
+        let function = self.function(name)?;
+
+        let method = DynamicMethod {
+            encoded_selector: encode_fn_selector(name),
+            output_param: function.output.clone(),
+            is_payable: function.is_payable,
+        };
+
+        Ok(CallHandler::new_dynamic_contract_call(
+            self.contract_id,
+            self.account.clone(),
+            method,
+            args,
+            self.log_decoder.clone(),
+            self.encoder_config,
+        ))
+    }
+}
+
+impl<A> ContractDependency for DynamicContract<A> {
+    fn id(&self) -> ContractId {
+        self.contract_id
+    }
+
+    fn log_decoder(&self) -> LogDecoder {
+        self.log_decoder.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_tx::Receipt;
+    use fuels_core::{codec::ABIEncoder, types::param_types::EnumVariants};
+    use serde_json::json;
+
+    use super::*;
+
+    const ABI: &str = r#"{
+        "programType": "contract",
+        "specVersion": "1",
+        "encodingVersion": "1",
+        "concreteTypes": [
+            {
+            "type": "bool",
+            "concreteTypeId": "b760f44fa5965c2474a3b471467a22c43185152129295af588b022ae50b50903"
+            },
+            {
+            "type": "u64",
+            "concreteTypeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0"
+            }
+        ],
+        "metadataTypes": [],
+        "functions": [
+            {
+            "inputs": [
+                {
+                "name": "amount",
+                "concreteTypeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0"
+                }
+            ],
+            "name": "deposit",
+            "output": "b760f44fa5965c2474a3b471467a22c43185152129295af588b022ae50b50903",
+            "attributes": [
+                {
+                "name": "payable",
+                "arguments": []
+                }
+            ]
+            },
+            {
+            "inputs": [],
+            "name": "total",
+            "output": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0",
+            "attributes": null
+            }
+        ],
+        "loggedTypes": [],
+        "messagesTypes": [],
+        "configurables": []
+    }"#;
+
+    #[test]
+    fn builds_calls_from_json_args() -> Result<()> {
+        // given
+        let contract_id = ContractId::from([1; 32]);
+        let contract = DynamicContract::from_json_abi(contract_id, (), ABI)?;
+
+        // when
+        let handler = contract.method("deposit", &[json!("42")])?;
+
+        // then
+        assert!(handler.is_payable());
+        assert_eq!(handler.call.contract_id, contract_id);
+        assert_eq!(handler.call.encoded_selector, encode_fn_selector("deposit"));
+        assert_eq!(handler.call.output_param, ParamType::Bool);
+        assert_eq!(
+            handler.call.encoded_args.as_ref().unwrap(),
+            &ABIEncoder::default().encode(&[Token::U64(42)])?
+        );
+
+        let total = contract.method("total", &[])?;
+        assert!(!total.is_payable());
+        assert_eq!(
+            contract.output_to_json("total", &Token::U64(7))?,
+            json!("7")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_methods_and_bad_args_are_rejected() -> Result<()> {
+        let contract = DynamicContract::from_json_abi(ContractId::zeroed(), (), ABI)?;
+
+        let err = contract.method("withdraw", &[]).expect_err("should fail");
+        assert!(err.to_string().contains("method `withdraw` not found"));
+
+        let err = contract.method("deposit", &[]).expect_err("should fail");
+        assert!(err.to_string().contains("expected 1 values, got 0"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn logs_of_dynamic_calls_are_decoded_from_the_abi() -> Result<()> {
+        // given
+        let abi = r#"{
+            "programType": "contract",
+            "specVersion": "1",
+            "encodingVersion": "1",
+            "concreteTypes": [
+                {
+                "type": "()",
+                "concreteTypeId": "2e38e77b22c314a449e91fafed92a43826ac6aa403ae6a8acb6cf58239fbaf5d"
+                },
+                {
+                "type": "u64",
+                "concreteTypeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0"
+                },
+                {
+                "type": "enum MyError",
+                "concreteTypeId": "44781f4b1eb667f225275b0a1c877dd4b9a8ab01f3cd01f8ed84f95c6cd2f363",
+                "metadataTypeId": 0
+                }
+            ],
+            "metadataTypes": [
+                {
+                "type": "enum MyError",
+                "metadataTypeId": 0,
+                "components": [
+                    {
+                    "name": "A",
+                    "typeId": "2e38e77b22c314a449e91fafed92a43826ac6aa403ae6a8acb6cf58239fbaf5d",
+                    "errorMessage": "some error A"
+                    },
+                    {
+                    "name": "B",
+                    "typeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0",
+                    "errorMessage": "some error B"
+                    }
+                ]
+                }
+            ],
+            "functions": [
+                {
+                "inputs": [],
+                "name": "produce_logs",
+                "output": "2e38e77b22c314a449e91fafed92a43826ac6aa403ae6a8acb6cf58239fbaf5d",
+                "attributes": null
+                }
+            ],
+            "loggedTypes": [
+                {
+                "logId": "1515152261580153489",
+                "concreteTypeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0"
+                },
+                {
+                "logId": "4933727799282657266",
+                "concreteTypeId": "44781f4b1eb667f225275b0a1c877dd4b9a8ab01f3cd01f8ed84f95c6cd2f363"
+                }
+            ],
+            "messagesTypes": [],
+            "configurables": []
+        }"#;
+        let contract_id = ContractId::from([1; 32]);
+        let contract = DynamicContract::from_json_abi(contract_id, (), abi)?;
+        let handler = contract.method("produce_logs", &[])?;
+
+        let log = |rb: u64, data: Vec<u8>| Receipt::log_data(contract_id, 0, rb, 0, 0, 0, data);
+        let receipts = [
+            log(1515152261580153489, 64u64.to_be_bytes().to_vec()),
+            log(
+                4933727799282657266,
+                ABIEncoder::default().encode(&[Token::Enum(Box::new((
+                    1,
+                    Token::U64(42),
+                    EnumVariants::new(vec![
+                        ("A".to_string(), ParamType::Unit),
+                        ("B".to_string(), ParamType::U64),
+                    ])?,
+                )))])?,
+            ),
+        ];
+
+        // when
+        let logs = handler.log_decoder.decode_logs(&receipts);
+
+        // then
+        assert_eq!(logs.filter_succeeded(), ["64", "some error B: 42"]);
+        assert_eq!(
+            handler
+                .log_decoder
+                .decode_logs_with_type::<u64>(&receipts)?,
+            [64]
+        );
+
+        Ok(())
+    }
+}
@@ -13,6 +13,8 @@ use fuels_core::types::{
     message::{Message, MessageStatus},
 };
 pub use node_types::*;
+#[cfg(feature = "fuels-accounts")]
+pub use offline::*;
 use rand::{Fill, Rng, SeedableRng, rngs::StdRng};
 use utils::{into_coin_configs, into_message_configs};
 pub use wallets_config::*;
@@ -24,6 +26,9 @@ pub(crate) mod fuel_bin_service;
 #[cfg(feature = "fuels-accounts")]
 mod accounts;
 
+#[cfg(feature = "fuels-accounts")]
+mod offline;
+
 pub use service::*;
 mod service;
 
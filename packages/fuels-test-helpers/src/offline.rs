@@ -0,0 +1,1503 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use fuel_core_client::client::{
+    pagination::{PageDirection, PaginatedResult, PaginationRequest},
+    schema::{
+        gas_price::EstimateGasPrice,
+        node_info::{IndexationFlags, TxPoolStats},
+    },
+    types::{
+        Balance, Block, ChainInfo, Coin, CoinType, Message, MessageCoin, NodeInfo,
+        TransactionResponse, TransactionStatus, TransactionType,
+        block::{Consensus, Header},
+        gas_price::LatestGasPrice,
+        primitives::{BlockId, TransactionId},
+    },
+};
+use fuel_core_types::{
+    blockchain::{
+        SealedBlock, block::Block as FuelBlock, header::BlockHeader, transaction::TransactionExt,
+    },
+    services::{
+        block_importer::ImportResult,
+        executor::{TransactionExecutionResult, TransactionExecutionStatus},
+    },
+    tai64::Tai64,
+};
+use fuel_crypto::Hasher;
+use fuel_tx::{
+    Chargeable, ConsensusParameters, Input, Output, Receipt, Transaction, TxId, UniqueIdentifier,
+    UtxoId,
+    field::{Inputs, Outputs},
+};
+use fuel_types::{Address, AssetId, BlockHeight, ContractId, Nonce};
+use fuel_vm::{
+    checked_transaction::{CheckPredicateParams, EstimatePredicates, IntoChecked},
+    interpreter::{InterpreterParams, MemoryInstance},
+    memory_client::MemoryClient,
+    state::ProgramState,
+    storage::{ContractsAssetsStorage, InterpreterStorage, MemoryStorage},
+};
+use fuels_accounts::provider::{NodeApi, Provider, SUPPORTED_FUEL_CORE_VERSION};
+use fuels_core::types::{coin::Coin as SdkCoin, errors::Result, message::Message as SdkMessage};
+use futures::{
+    StreamExt,
+    channel::mpsc::{self, UnboundedSender},
+    stream::BoxStream,
+};
+
+/// The url reported by providers connected to an [`OfflineNode`].
+const OFFLINE_NODE_URL: &str = "http://offline.invalid";
+
+/// A node running in-process, executing transactions in an embedded fuel-vm. It serves a
+/// [`Provider`] through [`Provider::connect_with_backend`], so wallets, contracts and scripts
+/// work against it without a `fuel-core` binary.
+///
+/// Coins, messages and contract state are kept in memory. Every accepted transaction is
+/// committed immediately in a block of its own, so results are deterministic. The executed
+/// blocks and transactions are kept as well, to be queried or subscribed to as on a node.
+///
+/// # Examples
+/// ```
+/// use fuels_accounts::Account;
+/// use fuels_accounts::signers::private_key::PrivateKeySigner;
+/// use fuels_accounts::wallet::Wallet;
+/// use fuels_core::types::{Address, AssetId};
+/// use fuels_test_helpers::{OfflineNode, setup_single_asset_coins};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let signer = PrivateKeySigner::random(&mut rand::thread_rng());
+///     let coins = setup_single_asset_coins(signer.address(), AssetId::zeroed(), 2, 1000);
+///     let provider = OfflineNode::new(coins, vec![], None).provider().await?;
+///     let wallet = Wallet::new(signer, provider.clone());
+///
+///     let recipient = Address::default();
+///     wallet
+///         .transfer(recipient, 300, AssetId::zeroed(), Default::default())
+///         .await?;
+///
+///     assert_eq!(provider.get_asset_balance(&recipient, &AssetId::zeroed()).await?, 300);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct OfflineNode {
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    client: MemoryClient<MemoryInstance>,
+    consensus_parameters: ConsensusParameters,
+    gas_price: u64,
+    utxos: Utxos,
+    /// Every block since genesis, indexed by height.
+    blocks: Vec<Block>,
+    transactions: HashMap<TxId, TransactionResponse>,
+    block_subscribers: Vec<UnboundedSender<ImportResult>>,
+}
+
+/// The coins and messages that can be spent.
+#[derive(Clone)]
+struct Utxos {
+    coins: BTreeMap<UtxoId, Coin>,
+    messages: BTreeMap<Nonce, Message>,
+}
+
+/// The effects of executing a transaction, before they are committed.
+struct Execution {
+    inputs: Vec<Input>,
+    outputs: Vec<Output>,
+    program_state: Option<ProgramState>,
+    failure: Option<String>,
+    receipts: Vec<Receipt>,
+    total_gas: u64,
+    total_fee: u64,
+}
+
+impl std::fmt::Debug for OfflineNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("OfflineNode")
+            .field("block_height", &state.block_height())
+            .field("gas_price", &state.gas_price)
+            .field("coins", &state.utxos.coins.len())
+            .field("messages", &state.utxos.messages.len())
+            .finish()
+    }
+}
+
+impl OfflineNode {
+    /// Creates a new offline node holding the given `coins` and `messages`. When no
+    /// `consensus_parameters` are given, the ones used by `setup_test_provider` are used.
+    pub fn new(
+        coins: Vec<SdkCoin>,
+        messages: Vec<SdkMessage>,
+        consensus_parameters: Option<ConsensusParameters>,
+    ) -> Self {
+        let consensus_parameters = consensus_parameters
+            .unwrap_or_else(|| crate::testnet_chain_config().consensus_parameters);
+        let genesis_height = BlockHeight::default();
+        let gas_price = 0;
+
+        let client = new_client(
+            MemoryStorage::new(genesis_height, ContractId::zeroed()),
+            gas_price,
+            &consensus_parameters,
+        );
+
+        let coins = coins
+            .into_iter()
+            .map(|coin| {
+                let coin = Coin {
+                    amount: coin.amount,
+                    block_created: 0,
+                    tx_created_idx: 0,
+                    asset_id: coin.asset_id,
+                    utxo_id: coin.utxo_id,
+                    owner: coin.owner,
+                };
+                (coin.utxo_id, coin)
+            })
+            .collect();
+        let messages = messages
+            .into_iter()
+            .map(|message| {
+                let message = Message {
+                    amount: message.amount,
+                    sender: message.sender,
+                    recipient: message.recipient,
+                    nonce: message.nonce,
+                    data: message.data,
+                    da_height: message.da_height,
+                };
+                (message.nonce, message)
+            })
+            .collect();
+
+        let state = State {
+            client,
+            consensus_parameters,
+            gas_price,
+            utxos: Utxos { coins, messages },
+            blocks: vec![new_block(genesis_height, Tai64::UNIX_EPOCH, vec![])],
+            transactions: HashMap::new(),
+            block_subscribers: vec![],
+        };
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Sets the gas price used for executing and estimating transactions.
+    pub fn with_gas_price(self, gas_price: u64) -> Self {
+        {
+            let mut state = self.lock();
+            state.gas_price = gas_price;
+            state.client.set_gas_price(gas_price);
+        }
+
+        self
+    }
+
+    /// A provider whose requests are served by this node.
+    pub async fn provider(&self) -> Result<Provider> {
+        Provider::connect_with_backend(OFFLINE_NODE_URL, self.clone()).await
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl State {
+    fn base_asset_id(&self) -> AssetId {
+        *self.consensus_parameters.base_asset_id()
+    }
+
+    fn latest_block(&self) -> &Block {
+        self.blocks
+            .last()
+            .expect("the genesis block is created with the node")
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.latest_block().header.height.into()
+    }
+
+    /// Appends a block holding `transactions` and notifies the block subscribers.
+    fn push_block(&mut self, time: Tai64, transactions: Vec<TxId>) -> io::Result<BlockHeight> {
+        let height = self
+            .block_height()
+            .succ()
+            .ok_or_else(|| io::Error::other("block height overflow"))?;
+
+        self.blocks.push(new_block(height, time, transactions));
+        self.client.as_mut().set_block_height(height);
+
+        let mut block = FuelBlock::default();
+        *block.header_mut() = BlockHeader::new_block(height, time);
+        let import_result = ImportResult::new_from_local(
+            SealedBlock {
+                entity: block,
+                consensus: Default::default(),
+            },
+            vec![],
+            vec![],
+        );
+        self.block_subscribers
+            .retain(|subscriber| subscriber.unbounded_send(import_result.clone()).is_ok());
+
+        Ok(height)
+    }
+
+    fn spendable_resources(&self, owner: &Address, asset_id: &AssetId) -> Vec<CoinType> {
+        let coins = self
+            .utxos
+            .coins
+            .values()
+            .filter(|coin| coin.owner == *owner && coin.asset_id == *asset_id)
+            .copied()
+            .map(CoinType::Coin);
+
+        let messages = self
+            .utxos
+            .messages
+            .values()
+            .filter(|message| {
+                *asset_id == self.base_asset_id()
+                    && message.recipient == *owner
+                    && message.data.is_empty()
+            })
+            .map(|message| {
+                CoinType::MessageCoin(MessageCoin {
+                    amount: message.amount,
+                    sender: message.sender,
+                    recipient: message.recipient,
+                    nonce: message.nonce,
+                    da_height: message.da_height,
+                })
+            });
+
+        coins.chain(messages).collect()
+    }
+
+    /// Executes `tx` and commits its effects in a new block.
+    fn execute(&mut self, tx: Transaction) -> io::Result<(TxId, TransactionStatus)> {
+        let tx_id = tx.id(&self.consensus_parameters.chain_id());
+        let height = self.block_height();
+
+        self.utxos
+            .check_inputs(tx.inputs().as_ref(), &self.base_asset_id())?;
+        let execution = transact(
+            &mut self.client,
+            tx.clone(),
+            height,
+            &self.consensus_parameters,
+            self.gas_price,
+            true,
+        )?;
+        self.client.persist();
+
+        let time = self.latest_block().header.time + 1;
+        let block_height = self.push_block(time, vec![tx_id])?;
+        self.utxos.apply(tx_id, &execution, block_height);
+
+        let status = match execution.failure {
+            Some(reason) => TransactionStatus::Failure {
+                block_height,
+                time,
+                total_gas: execution.total_gas,
+                total_fee: execution.total_fee,
+                reason,
+                program_state: execution.program_state,
+                receipts: execution.receipts,
+            },
+            None => TransactionStatus::Success {
+                block_height,
+                time,
+                total_gas: execution.total_gas,
+                total_fee: execution.total_fee,
+                program_state: execution.program_state,
+                receipts: execution.receipts,
+            },
+        };
+        self.transactions.insert(
+            tx_id,
+            TransactionResponse {
+                transaction: tx.into(),
+                status: status.clone(),
+            },
+        );
+
+        Ok((tx_id, status))
+    }
+
+    /// Pages through the executed transactions matching `filter`, in the order of their blocks.
+    fn executed_transactions(
+        &self,
+        request: PaginationRequest<String>,
+        filter: impl Fn(&Transaction) -> bool,
+    ) -> PaginatedResult<TransactionResponse, String> {
+        let transactions = self
+            .blocks
+            .iter()
+            .flat_map(|block| &block.transactions)
+            .filter_map(|id| Some((id, self.transactions.get(id)?)))
+            .filter(|(_, response)| {
+                matches!(&response.transaction, TransactionType::Known(tx) if filter(tx))
+            })
+            .map(|(id, response)| (*id, response.clone()))
+            .collect();
+
+        let page = paginate(transactions, request, |(id, _)| id.to_string());
+
+        PaginatedResult {
+            cursor: page.cursor,
+            results: page
+                .results
+                .into_iter()
+                .map(|(_, response)| response)
+                .collect(),
+            has_next_page: page.has_next_page,
+            has_previous_page: page.has_previous_page,
+        }
+    }
+}
+
+impl Utxos {
+    /// Checks that every coin and message input is unspent and matches the stored entry, as the
+    /// VM trusts the inputs' amounts and owners.
+    fn check_inputs(&self, inputs: &[Input], base_asset_id: &AssetId) -> io::Result<()> {
+        for input in inputs {
+            if let Some(utxo_id) = input.utxo_id().filter(|_| input.is_coin()) {
+                let coin = self.coins.get(utxo_id).ok_or_else(|| {
+                    invalid_input(format!(
+                        "coin `{utxo_id}` does not exist or was already spent"
+                    ))
+                })?;
+
+                if input.input_owner() != Some(&coin.owner)
+                    || input.amount() != Some(coin.amount)
+                    || input.asset_id(base_asset_id) != Some(&coin.asset_id)
+                {
+                    return Err(invalid_input(format!(
+                        "input of coin `{utxo_id}` doesn't match it, expected owner `{}`, amount `{}` and asset id `{}`",
+                        coin.owner, coin.amount, coin.asset_id
+                    )));
+                }
+            } else if let Some(nonce) = input.nonce() {
+                let message = self.messages.get(nonce).ok_or_else(|| {
+                    invalid_input(format!(
+                        "message `{nonce}` does not exist or was already spent"
+                    ))
+                })?;
+
+                if input.sender() != Some(&message.sender)
+                    || input.recipient() != Some(&message.recipient)
+                    || input.amount() != Some(message.amount)
+                    || input.input_data().unwrap_or_default() != message.data.as_slice()
+                {
+                    return Err(invalid_input(format!(
+                        "input of message `{nonce}` doesn't match it, expected sender `{}`, recipient `{}`, amount `{}` and {} bytes of data",
+                        message.sender,
+                        message.recipient,
+                        message.amount,
+                        message.data.len()
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Spends the inputs of an execution and adds its outputs, created in `block_height`.
+    fn apply(&mut self, tx_id: TxId, execution: &Execution, block_height: BlockHeight) {
+        let reverted = execution.failure.is_some();
+
+        for input in &execution.inputs {
+            if let Some(utxo_id) = input.utxo_id().filter(|_| input.is_coin()) {
+                self.coins.remove(utxo_id);
+            } else if let Some(nonce) = input.nonce() {
+                // Messages with data are retryable and stay spendable if the script reverts
+                if !(reverted && is_message_data(input)) {
+                    self.messages.remove(nonce);
+                }
+            }
+        }
+
+        for (idx, output) in execution.outputs.iter().enumerate() {
+            let (owner, amount, asset_id) = match output {
+                Output::Change {
+                    to,
+                    amount,
+                    asset_id,
+                } => (to, amount, asset_id),
+                Output::Coin {
+                    to,
+                    amount,
+                    asset_id,
+                }
+                | Output::Variable {
+                    to,
+                    amount,
+                    asset_id,
+                } if !reverted => (to, amount, asset_id),
+                _ => continue,
+            };
+
+            if *amount == 0 {
+                continue;
+            }
+
+            let utxo_id = UtxoId::new(tx_id, idx as u16);
+            self.coins.insert(
+                utxo_id,
+                Coin {
+                    amount: *amount,
+                    block_created: *block_height,
+                    tx_created_idx: 0,
+                    asset_id: *asset_id,
+                    utxo_id,
+                    owner: *owner,
+                },
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl NodeApi for OfflineNode {
+    async fn health(&self) -> io::Result<bool> {
+        Ok(true)
+    }
+
+    async fn node_info(&self) -> io::Result<NodeInfo> {
+        let state = self.lock();
+        let tx_params = state.consensus_parameters.tx_params();
+
+        Ok(NodeInfo {
+            utxo_validation: true,
+            vm_backtrace: false,
+            max_tx: u64::MAX,
+            max_gas: tx_params.max_gas_per_tx(),
+            max_size: tx_params.max_size(),
+            max_depth: u64::MAX,
+            node_version: SUPPORTED_FUEL_CORE_VERSION.to_string(),
+            indexation: IndexationFlags {
+                balances: true,
+                coins_to_spend: true,
+                asset_metadata: false,
+            },
+            tx_pool_stats: TxPoolStats {
+                tx_count: 0u64.into(),
+                total_gas: 0u64.into(),
+                total_size: 0u64.into(),
+            },
+        })
+    }
+
+    async fn chain_info(&self) -> io::Result<ChainInfo> {
+        let state = self.lock();
+
+        Ok(ChainInfo {
+            da_height: 0,
+            name: "offline".to_string(),
+            latest_block: state.latest_block().clone(),
+            consensus_parameters: state.consensus_parameters.clone(),
+        })
+    }
+
+    async fn latest_gas_price(&self) -> io::Result<LatestGasPrice> {
+        let state = self.lock();
+
+        Ok(LatestGasPrice {
+            gas_price: state.gas_price,
+            block_height: state.block_height(),
+        })
+    }
+
+    async fn estimate_gas_price(&self, _block_horizon: u32) -> io::Result<EstimateGasPrice> {
+        Ok(EstimateGasPrice {
+            gas_price: self.lock().gas_price.into(),
+        })
+    }
+
+    async fn estimate_predicates(&self, tx: &mut Transaction) -> io::Result<()> {
+        let state = self.lock();
+        let params = CheckPredicateParams::from(&state.consensus_parameters);
+
+        tx.estimate_predicates(&params, MemoryInstance::new(), state.client.as_ref())
+            .map_err(|err| invalid_input(format!("failed to estimate predicates: {err:?}")))
+    }
+
+    async fn dry_run_opt(
+        &self,
+        txs: &[Transaction],
+        utxo_validation: Option<bool>,
+        gas_price: Option<u64>,
+        at_height: Option<BlockHeight>,
+    ) -> io::Result<Vec<TransactionExecutionStatus>> {
+        let state = self.lock();
+        let utxo_validation = utxo_validation.unwrap_or(true);
+        let gas_price = gas_price.unwrap_or(state.gas_price);
+        let height = at_height.unwrap_or_else(|| state.block_height());
+        let params = &state.consensus_parameters;
+
+        // Every transaction sees the effects of the previous ones, none of them is committed
+        let mut client = new_client(state.client.as_ref().clone(), gas_price, params);
+        client.as_mut().set_block_height(height);
+        let mut utxos = state.utxos.clone();
+
+        txs.iter()
+            .map(|tx| {
+                if utxo_validation {
+                    utxos.check_inputs(tx.inputs().as_ref(), params.base_asset_id())?;
+                }
+
+                let id = tx.id(&params.chain_id());
+                let execution = transact(
+                    &mut client,
+                    tx.clone(),
+                    height,
+                    params,
+                    gas_price,
+                    utxo_validation,
+                )?;
+                utxos.apply(id, &execution, height);
+
+                let receipts = Arc::new(execution.receipts);
+                let result = match execution.failure {
+                    Some(_) => TransactionExecutionResult::Failed {
+                        result: execution.program_state,
+                        receipts,
+                        total_gas: execution.total_gas,
+                        total_fee: execution.total_fee,
+                    },
+                    None => TransactionExecutionResult::Success {
+                        result: execution.program_state,
+                        receipts,
+                        total_gas: execution.total_gas,
+                        total_fee: execution.total_fee,
+                    },
+                };
+
+                Ok(TransactionExecutionStatus { id, result })
+            })
+            .collect()
+    }
+
+    async fn submit(&self, tx: &Transaction) -> io::Result<TransactionId> {
+        let (tx_id, _) = self.lock().execute(tx.clone())?;
+
+        Ok(tx_id)
+    }
+
+    async fn submit_and_await_commit(&self, tx: &Transaction) -> io::Result<TransactionStatus> {
+        let (_, status) = self.lock().execute(tx.clone())?;
+
+        Ok(status)
+    }
+
+    async fn submit_and_await_status<'a>(
+        &'a self,
+        tx: &'a Transaction,
+        _include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        let status = self.submit_and_await_commit(tx).await?;
+
+        Ok(futures::stream::once(async { Ok(status) }).boxed())
+    }
+
+    async fn transaction(&self, id: &TxId) -> io::Result<Option<TransactionResponse>> {
+        Ok(self.lock().transactions.get(id).cloned())
+    }
+
+    async fn transaction_status(&self, id: &TxId) -> io::Result<TransactionStatus> {
+        self.lock()
+            .transactions
+            .get(id)
+            .map(|response| response.status.clone())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("transaction `{id}` not found"),
+                )
+            })
+    }
+
+    async fn subscribe_transaction_status<'a>(
+        &'a self,
+        id: &'a TxId,
+        _include_preconfirmation: bool,
+    ) -> io::Result<BoxStream<'a, io::Result<TransactionStatus>>> {
+        let status = self.transaction_status(id).await?;
+
+        Ok(futures::stream::once(async { Ok(status) }).boxed())
+    }
+
+    async fn await_transaction_commit(&self, id: &TxId) -> io::Result<TransactionStatus> {
+        // Transactions are committed as soon as they are submitted
+        self.transaction_status(id).await
+    }
+
+    async fn transactions(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        Ok(self.lock().executed_transactions(request, |_| true))
+    }
+
+    async fn transactions_by_owner(
+        &self,
+        owner: &Address,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        Ok(self.lock().executed_transactions(request, |tx| {
+            tx.inputs()
+                .iter()
+                .any(|input| input.input_owner() == Some(owner))
+                || tx.outputs().iter().any(|output| output.to() == Some(owner))
+        }))
+    }
+
+    async fn produce_blocks(
+        &self,
+        blocks_to_produce: u32,
+        start_timestamp: Option<u64>,
+    ) -> io::Result<BlockHeight> {
+        let mut state = self.lock();
+        state
+            .block_height()
+            .checked_add(blocks_to_produce)
+            .ok_or_else(|| io::Error::other("block height overflow"))?;
+
+        for idx in 0..blocks_to_produce {
+            let time = match start_timestamp {
+                Some(timestamp) if idx == 0 => Tai64(timestamp),
+                _ => state.latest_block().header.time + 1,
+            };
+            state.push_block(time, vec![])?;
+        }
+
+        Ok(state.block_height())
+    }
+
+    async fn block(&self, id: &BlockId) -> io::Result<Option<Block>> {
+        Ok(self
+            .lock()
+            .blocks
+            .iter()
+            .find(|block| block.id == *id)
+            .cloned())
+    }
+
+    async fn block_by_height(&self, height: BlockHeight) -> io::Result<Option<Block>> {
+        Ok(self.lock().blocks.get(*height as usize).cloned())
+    }
+
+    async fn blocks(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Block, String>> {
+        let blocks = self.lock().blocks.clone();
+
+        Ok(paginate(blocks, request, |block| {
+            block.header.height.to_string()
+        }))
+    }
+
+    async fn new_blocks_subscription(&self) -> io::Result<BoxStream<'_, io::Result<ImportResult>>> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.lock().block_subscribers.push(sender);
+
+        Ok(receiver.map(Ok).boxed())
+    }
+
+    async fn coins(
+        &self,
+        owner: &Address,
+        asset_id: Option<&AssetId>,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Coin, String>> {
+        let state = self.lock();
+        let asset_id = asset_id.copied().unwrap_or_else(|| state.base_asset_id());
+
+        let coins = state
+            .utxos
+            .coins
+            .values()
+            .filter(|coin| coin.owner == *owner && coin.asset_id == asset_id)
+            .copied()
+            .collect();
+
+        Ok(paginate(coins, request, |coin| coin.utxo_id.to_string()))
+    }
+
+    async fn coins_to_spend(
+        &self,
+        owner: &Address,
+        spend_query: Vec<(AssetId, u128, Option<u16>)>,
+        excluded_ids: Option<(Vec<UtxoId>, Vec<Nonce>)>,
+    ) -> io::Result<Vec<Vec<CoinType>>> {
+        let state = self.lock();
+        let (excluded_utxos, excluded_nonces) = excluded_ids.unwrap_or_default();
+
+        spend_query
+            .into_iter()
+            .map(|(asset_id, amount, max)| {
+                let mut candidates: Vec<_> = state
+                    .spendable_resources(owner, &asset_id)
+                    .into_iter()
+                    .filter(|resource| match resource {
+                        CoinType::Coin(coin) => !excluded_utxos.contains(&coin.utxo_id),
+                        CoinType::MessageCoin(message) => !excluded_nonces.contains(&message.nonce),
+                        CoinType::Unknown => false,
+                    })
+                    .collect();
+                candidates.sort_by_key(|resource| std::cmp::Reverse(resource.amount()));
+
+                let max = max.map_or(usize::MAX, usize::from);
+                let mut total = 0u128;
+                let resources: Vec<_> = candidates
+                    .into_iter()
+                    .take_while(|resource| {
+                        let needed = total < amount;
+                        total += u128::from(resource.amount());
+                        needed
+                    })
+                    .take(max)
+                    .collect();
+
+                let covered: u128 = resources
+                    .iter()
+                    .map(|resource| u128::from(resource.amount()))
+                    .sum();
+                if covered < amount {
+                    return Err(invalid_input(format!(
+                        "not enough coins to fit the target: requested `{amount}` of asset `{asset_id}`, available `{covered}`"
+                    )));
+                }
+
+                Ok(resources)
+            })
+            .collect()
+    }
+
+    async fn balance(&self, owner: &Address, asset_id: Option<&AssetId>) -> io::Result<u128> {
+        let state = self.lock();
+        let asset_id = asset_id.copied().unwrap_or_else(|| state.base_asset_id());
+
+        Ok(state
+            .spendable_resources(owner, &asset_id)
+            .iter()
+            .map(|resource| u128::from(resource.amount()))
+            .sum())
+    }
+
+    async fn balances(
+        &self,
+        owner: &Address,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Balance, String>> {
+        let state = self.lock();
+
+        let coins = state
+            .utxos
+            .coins
+            .values()
+            .filter(|coin| coin.owner == *owner)
+            .map(|coin| (coin.asset_id, coin.amount));
+        let messages = state
+            .utxos
+            .messages
+            .values()
+            .filter(|message| message.recipient == *owner && message.data.is_empty())
+            .map(|message| (state.base_asset_id(), message.amount));
+
+        let mut amounts = BTreeMap::<AssetId, u128>::new();
+        for (asset_id, amount) in coins.chain(messages) {
+            *amounts.entry(asset_id).or_default() += u128::from(amount);
+        }
+
+        let balances = amounts
+            .into_iter()
+            .map(|(asset_id, amount)| Balance {
+                owner: *owner,
+                amount,
+                asset_id,
+            })
+            .collect();
+
+        Ok(paginate(balances, request, |balance| {
+            balance.asset_id.to_string()
+        }))
+    }
+
+    async fn contract_balance(&self, id: &ContractId, asset: Option<&AssetId>) -> io::Result<u64> {
+        let state = self.lock();
+        let asset_id = asset.copied().unwrap_or_else(|| state.base_asset_id());
+
+        let balance = state
+            .client
+            .as_ref()
+            .contract_asset_id_balance(id, &asset_id)
+            .map_err(|err| io::Error::other(format!("{err:?}")))?;
+
+        Ok(balance.unwrap_or_default())
+    }
+
+    async fn contract_exists(&self, id: &ContractId) -> io::Result<bool> {
+        self.lock()
+            .client
+            .as_ref()
+            .storage_contract_exists(id)
+            .map_err(|err| io::Error::other(format!("{err:?}")))
+    }
+
+    async fn messages(
+        &self,
+        owner: Option<&Address>,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<Message, String>> {
+        let messages = self
+            .lock()
+            .utxos
+            .messages
+            .values()
+            .filter(|message| owner.is_none_or(|owner| message.recipient == *owner))
+            .cloned()
+            .collect();
+
+        Ok(paginate(messages, request, |message| {
+            message.nonce.to_string()
+        }))
+    }
+
+    async fn is_user_account(&self, address: [u8; 32]) -> io::Result<bool> {
+        Ok(!self.contract_exists(&ContractId::new(address)).await?)
+    }
+}
+
+/// Executes `tx` on the storage of `client` without committing it. Signatures and predicates
+/// are only verified with `utxo_validation`.
+fn transact(
+    client: &mut MemoryClient<MemoryInstance>,
+    tx: Transaction,
+    height: BlockHeight,
+    params: &ConsensusParameters,
+    gas_price: u64,
+    utxo_validation: bool,
+) -> io::Result<Execution> {
+    let gas_costs = params.gas_costs();
+    let fee_params = params.fee_params();
+    let to_fee = |fee: u128| u64::try_from(fee).unwrap_or(u64::MAX);
+
+    macro_rules! checked {
+        ($tx: expr) => {
+            if utxo_validation {
+                $tx.into_checked(height, params)
+            } else {
+                $tx.into_checked_basic(height, params)
+            }
+            .map_err(|err| invalid_input(format!("{err:?}")))?
+        };
+    }
+
+    let execution = match tx {
+        Transaction::Script(script) => {
+            let checked = checked!(script);
+            let min_gas = checked.metadata().min_gas;
+
+            client.transact(checked);
+            let transition = client
+                .state_transition()
+                .ok_or_else(|| io::Error::other("transaction could not be executed"))?;
+
+            let script = transition.tx();
+            let receipts = transition.receipts().to_vec();
+            let used_gas = script_gas_used(&receipts);
+            let refund = script
+                .refund_fee(gas_costs, fee_params, used_gas, gas_price)
+                .unwrap_or_default();
+            let total_fee = script
+                .max_fee(gas_costs, fee_params, gas_price)
+                .saturating_sub(refund.into());
+
+            let state = *transition.state();
+            let failure = match state {
+                ProgramState::Revert(revert_id) => Some(format!("Revert({revert_id})")),
+                state if transition.should_revert() => Some(format!("{state:?}")),
+                _ => None,
+            };
+
+            Execution {
+                inputs: script.inputs().clone(),
+                outputs: script.outputs().clone(),
+                program_state: Some(state),
+                failure,
+                receipts,
+                total_gas: min_gas.saturating_add(used_gas),
+                total_fee: to_fee(total_fee),
+            }
+        }
+        Transaction::Create(create) => {
+            let checked = checked!(create);
+            let total_gas = checked.metadata().min_gas;
+
+            let create = client
+                .deploy(checked)
+                .map_err(|err| io::Error::other(format!("failed to deploy contract: {err:?}")))?;
+            // Unlike scripts, deployments are left uncommitted by the client
+            client.as_mut().commit();
+
+            Execution {
+                inputs: create.inputs().clone(),
+                outputs: create.outputs().clone(),
+                program_state: None,
+                failure: None,
+                receipts: vec![],
+                total_gas,
+                total_fee: to_fee(create.min_fee(gas_costs, fee_params, gas_price)),
+            }
+        }
+        Transaction::Blob(blob) => {
+            let checked = checked!(blob);
+            let total_gas = checked.metadata().min_gas;
+
+            let blob = client
+                .blob(checked)
+                .ok_or_else(|| io::Error::other("failed to upload blob"))?;
+            client.as_mut().commit();
+
+            Execution {
+                inputs: blob.inputs().clone(),
+                outputs: blob.outputs().clone(),
+                program_state: None,
+                failure: None,
+                receipts: vec![],
+                total_gas,
+                total_fee: to_fee(blob.min_fee(gas_costs, fee_params, gas_price)),
+            }
+        }
+        _ => {
+            return Err(invalid_input(
+                "transaction type is not supported by the offline node",
+            ));
+        }
+    };
+
+    Ok(execution)
+}
+
+/// Pages through `items`, using the `cursor` of the last returned item to resume after it.
+fn paginate<T>(
+    mut items: Vec<T>,
+    request: PaginationRequest<String>,
+    cursor: impl Fn(&T) -> String,
+) -> PaginatedResult<T, String> {
+    if matches!(request.direction, PageDirection::Backward) {
+        items.reverse();
+    }
+
+    let len = items.len();
+    let start = request.cursor.map_or(0, |after| {
+        items
+            .iter()
+            .position(|item| cursor(item) == after)
+            .map_or(len, |idx| idx + 1)
+    });
+    let end = start
+        .saturating_add(usize::try_from(request.results).unwrap_or_default())
+        .min(len);
+
+    PaginatedResult {
+        cursor: items[start..end].last().map(&cursor),
+        results: items.drain(start..end).collect(),
+        has_next_page: end < len,
+        has_previous_page: start > 0,
+    }
+}
+
+/// A block whose id is derived from its height.
+fn new_block(height: BlockHeight, time: Tai64, transactions: Vec<TxId>) -> Block {
+    let id = Hasher::hash(height.to_be_bytes());
+
+    Block {
+        id,
+        header: Header {
+            id,
+            da_height: 0,
+            consensus_parameters_version: 0,
+            state_transition_bytecode_version: 0,
+            transactions_count: transactions.len() as u16,
+            message_receipt_count: 0,
+            transactions_root: Default::default(),
+            message_outbox_root: Default::default(),
+            event_inbox_root: Default::default(),
+            height: *height,
+            prev_root: Default::default(),
+            time,
+            application_hash: Default::default(),
+            #[cfg(feature = "fault-proving")]
+            tx_id_commitment: None,
+        },
+        consensus: Consensus::Unknown,
+        transactions,
+        block_producer: None,
+    }
+}
+
+fn new_client(
+    storage: MemoryStorage,
+    gas_price: u64,
+    consensus_parameters: &ConsensusParameters,
+) -> MemoryClient<MemoryInstance> {
+    MemoryClient::new(
+        MemoryInstance::new(),
+        storage,
+        InterpreterParams::new(gas_price, consensus_parameters),
+    )
+}
+
+fn invalid_input(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg.into())
+}
+
+fn is_message_data(input: &Input) -> bool {
+    matches!(
+        input,
+        Input::MessageDataSigned(_) | Input::MessageDataPredicate(_)
+    )
+}
+
+fn script_gas_used(receipts: &[Receipt]) -> u64 {
+    receipts
+        .iter()
+        .rev()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_crypto::SecretKey;
+    use fuel_tx::Salt;
+    use fuel_vm::fuel_asm::{GTFArgs, RegId, op};
+    use fuels_accounts::{
+        Account, ViewOnlyAccount, provider::ResourceFilter, signers::private_key::PrivateKeySigner,
+        wallet::Wallet,
+    };
+    use fuels_core::types::{
+        coin_type_id::CoinTypeId,
+        input::Input as FuelsInput,
+        transaction_builders::{
+            BuildableTransaction, CreateTransactionBuilder, ScriptTransactionBuilder,
+            TransactionBuilder,
+        },
+        tx_status::TxStatus,
+    };
+    use futures::TryStreamExt;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::{setup_single_asset_coins, setup_single_message};
+
+    #[tokio::test]
+    async fn wallet_transfer_moves_coins_between_owners() -> Result<()> {
+        // given
+        let mut rng = StdRng::seed_from_u64(1);
+        let signer = PrivateKeySigner::random(&mut rng);
+        let recipient = PrivateKeySigner::random(&mut rng).address();
+
+        let coins = setup_single_asset_coins(signer.address(), AssetId::zeroed(), 2, 1000);
+        let provider = OfflineNode::new(coins, vec![], None).provider().await?;
+        let wallet = Wallet::new(signer, provider.clone());
+
+        // when
+        let response = wallet
+            .transfer(recipient, 300, AssetId::zeroed(), Default::default())
+            .await?;
+
+        // then
+        let status = provider.tx_status(&response.tx_id).await?;
+        assert!(matches!(status, TxStatus::Success(_)));
+        assert_eq!(
+            provider
+                .get_asset_balance(&recipient, &AssetId::zeroed())
+                .await?,
+            300
+        );
+        assert_eq!(
+            wallet.get_asset_balance(&AssetId::zeroed()).await?,
+            2000 - 300 - u128::from(response.tx_status.total_fee)
+        );
+        assert_eq!(provider.latest_block_height().await?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn contract_call_runs_deployed_contract() -> Result<()> {
+        // given
+        let signer = PrivateKeySigner::random(&mut StdRng::seed_from_u64(3));
+        let coins = setup_single_asset_coins(signer.address(), AssetId::zeroed(), 2, 1000);
+        let provider = OfflineNode::new(coins, vec![], None).provider().await?;
+        let wallet = Wallet::new(signer, provider.clone());
+
+        let bytecode: Vec<u8> = [op::movi(0x10, 42), op::ret(0x10)].into_iter().collect();
+        let contract = fuel_tx::Contract::from(bytecode.clone());
+        let state_root = fuel_tx::Contract::default_state_root();
+        let contract_id = fuel_tx::Contract::id(&Salt::zeroed(), &contract.root(), &state_root);
+
+        let mut tb = CreateTransactionBuilder::prepare_contract_deployment(
+            bytecode,
+            contract_id,
+            state_root,
+            Salt::zeroed(),
+            vec![],
+            Default::default(),
+        );
+        wallet.adjust_for_fee(&mut tb, 0).await?;
+        wallet.add_witnesses(&mut tb)?;
+        let tx = tb.build(&provider).await?;
+        provider.send_transaction_and_await_commit(tx).await?;
+
+        // when
+        let script = [
+            op::gtf_args(0x10, 0x00, GTFArgs::ScriptData),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ];
+        let call_data = [contract_id.to_vec(), vec![0; 16]].concat();
+        let mut tb = ScriptTransactionBuilder::default()
+            .with_script(script.into_iter().collect())
+            .with_script_data(call_data)
+            .with_inputs(vec![FuelsInput::contract(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                contract_id,
+            )])
+            .with_outputs(vec![Output::contract(
+                0,
+                Default::default(),
+                Default::default(),
+            )]);
+        wallet.adjust_for_fee(&mut tb, 0).await?;
+        wallet.add_witnesses(&mut tb)?;
+        let tx = tb.build(&provider).await?;
+        let status = provider.send_transaction_and_await_commit(tx).await?;
+
+        // then
+        let receipts = status.take_receipts_checked(None)?;
+        assert!(receipts.iter().any(|receipt| matches!(
+            receipt,
+            Receipt::Return { id, val: 42, .. } if *id == contract_id
+        )));
+        assert!(provider.contract_exists(&contract_id).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spent_coins_are_rejected() -> Result<()> {
+        // given
+        let mut rng = StdRng::seed_from_u64(2);
+        let signer = PrivateKeySigner::random(&mut rng);
+        let recipient = PrivateKeySigner::random(&mut rng).address();
+
+        let coins = setup_single_asset_coins(signer.address(), AssetId::zeroed(), 1, 1000);
+        let node = OfflineNode::new(coins.clone(), vec![], None);
+        let wallet = Wallet::new(signer, node.provider().await?);
+        wallet
+            .transfer(recipient, 100, AssetId::zeroed(), Default::default())
+            .await?;
+
+        // when
+        let tx = Transaction::script(
+            0,
+            vec![],
+            vec![],
+            Default::default(),
+            vec![Input::coin_signed(
+                coins[0].utxo_id,
+                coins[0].owner,
+                coins[0].amount,
+                coins[0].asset_id,
+                Default::default(),
+                0,
+            )],
+            vec![],
+            vec![],
+        );
+        let err = node.submit(&tx.into()).await.expect_err("should fail");
+
+        // then
+        assert!(err.to_string().contains("already spent"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn inputs_must_match_the_stored_resources() -> Result<()> {
+        // given
+        let owner = Address::default();
+        let coins = setup_single_asset_coins(owner, AssetId::zeroed(), 1, 1000);
+        let message = setup_single_message(Address::default(), owner, 10, Nonce::zeroed(), vec![]);
+        let node = OfflineNode::new(coins.clone(), vec![message.clone()], None);
+
+        let script_with = |input| {
+            Transaction::script(
+                0,
+                vec![],
+                vec![],
+                Default::default(),
+                vec![input],
+                vec![],
+                vec![],
+            )
+        };
+
+        // when
+        let inflated_coin = script_with(Input::coin_signed(
+            coins[0].utxo_id,
+            coins[0].owner,
+            1_000_000,
+            coins[0].asset_id,
+            Default::default(),
+            0,
+        ));
+        let coin_err = node
+            .submit(&inflated_coin.into())
+            .await
+            .expect_err("should fail");
+
+        let inflated_message = script_with(Input::message_coin_signed(
+            message.sender,
+            message.recipient,
+            1_000_000,
+            message.nonce,
+            0,
+        ));
+        let message_err = node
+            .submit(&inflated_message.into())
+            .await
+            .expect_err("should fail");
+
+        // then
+        assert!(coin_err.to_string().contains("doesn't match"));
+        assert!(message_err.to_string().contains("doesn't match"));
+        assert_eq!(node.lock().utxos.coins.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn dry_run_spends_outputs_of_previous_transactions() -> Result<()> {
+        // given
+        let secret = SecretKey::random(&mut StdRng::seed_from_u64(4));
+        let owner = Input::owner(&secret.public_key());
+        let coins = setup_single_asset_coins(owner, AssetId::zeroed(), 1, 1000);
+        let node = OfflineNode::new(coins.clone(), vec![], None);
+        let params = node.lock().consensus_parameters.clone();
+
+        let transfer = |utxo_id, amount| {
+            fuel_tx::TransactionBuilder::script([op::ret(RegId::ONE)].into_iter().collect(), vec![])
+                .with_params(params.clone())
+                .script_gas_limit(10_000)
+                .add_unsigned_coin_input(
+                    secret,
+                    utxo_id,
+                    amount,
+                    AssetId::zeroed(),
+                    Default::default(),
+                )
+                .add_output(Output::coin(owner, 400, AssetId::zeroed()))
+                .add_output(Output::change(owner, 0, AssetId::zeroed()))
+                .finalize_as_transaction()
+        };
+        let first = transfer(coins[0].utxo_id, 1000);
+        let second = transfer(UtxoId::new(first.id(&params.chain_id()), 0), 400);
+
+        // when
+        let statuses = node
+            .dry_run_opt(&[first, second.clone()], Some(true), None, None)
+            .await?;
+        let err = node
+            .dry_run_opt(&[second], Some(true), None, None)
+            .await
+            .expect_err("should fail");
+
+        // then
+        assert_eq!(statuses.len(), 2);
+        assert!(
+            statuses
+                .iter()
+                .all(|status| matches!(status.result, TransactionExecutionResult::Success { .. }))
+        );
+        assert!(err.to_string().contains("does not exist"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn executed_transactions_are_served_with_their_blocks() -> Result<()> {
+        // given
+        let mut rng = StdRng::seed_from_u64(5);
+        let signer = PrivateKeySigner::random(&mut rng);
+        let recipient = PrivateKeySigner::random(&mut rng).address();
+
+        let coins = setup_single_asset_coins(signer.address(), AssetId::zeroed(), 1, 1000);
+        let provider = OfflineNode::new(coins, vec![], None).provider().await?;
+        let wallet = Wallet::new(signer, provider.clone());
+        let tx_id = wallet
+            .transfer(recipient, 100, AssetId::zeroed(), Default::default())
+            .await?
+            .tx_id;
+
+        // when
+        let block = provider.block_by_height(1.into()).await?;
+        let tx = provider.get_transaction_by_id(&tx_id).await?;
+        let owned = provider
+            .get_transactions_by_owner(
+                &recipient,
+                PaginationRequest {
+                    cursor: None,
+                    results: 10,
+                    direction: PageDirection::Forward,
+                },
+            )
+            .await?;
+        let blocks: Vec<_> = provider.subscribe_blocks(0).take(2).try_collect().await?;
+
+        // then
+        assert_eq!(block.expect("block exists").transactions, vec![tx_id]);
+        assert!(tx.is_some());
+        assert_eq!(owned.results.len(), 1);
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| block.header.height)
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn block_subscribers_are_notified_of_new_blocks() -> Result<()> {
+        // given
+        let provider = OfflineNode::new(vec![], vec![], None).provider().await?;
+        let mut blocks = std::pin::pin!(provider.subscribe_blocks(1));
+
+        // when
+        let (block, produced) = futures::join!(blocks.next(), provider.produce_blocks(1, None));
+
+        // then
+        assert_eq!(produced?, 1);
+        assert_eq!(block.expect("stream is not over")?.header.height, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spendable_resources_respect_filter() -> Result<()> {
+        // given
+        let owner = Address::default();
+        let coins = setup_single_asset_coins(owner, AssetId::zeroed(), 3, 100);
+        let excluded = coins[0].utxo_id;
+        let provider = OfflineNode::new(coins, vec![], None).provider().await?;
+
+        // when
+        let resources = provider
+            .get_spendable_resources(ResourceFilter {
+                from: owner,
+                amount: 150,
+                excluded_utxos: vec![excluded],
+                ..Default::default()
+            })
+            .await?;
+
+        // then
+        assert_eq!(resources.len(), 2);
+        assert!(
+            resources
+                .iter()
+                .all(|r| r.id() != Some(CoinTypeId::UtxoId(excluded)))
+        );
+
+        let err = provider
+            .get_spendable_resources(ResourceFilter {
+                from: owner,
+                amount: 301,
+                ..Default::default()
+            })
+            .await
+            .expect_err("should fail");
+        assert!(err.to_string().contains("not enough coins"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn messages_count_towards_base_asset_balance() -> Result<()> {
+        // given
+        let recipient = Address::default();
+        let message =
+            setup_single_message(Address::default(), recipient, 10, Nonce::zeroed(), vec![]);
+        let coins = setup_single_asset_coins(recipient, AssetId::zeroed(), 1, 5);
+
+        // when
+        let provider = OfflineNode::new(coins, vec![message], None)
+            .provider()
+            .await?;
+
+        // then
+        assert_eq!(provider.get_messages(&recipient).await?.len(), 1);
+        assert_eq!(
+            provider
+                .get_asset_balance(&recipient, &AssetId::zeroed())
+                .await?,
+            15
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn pagination_walks_through_all_items() {
+        // given
+        let request = |cursor| PaginationRequest {
+            cursor,
+            results: 2,
+            direction: PageDirection::Forward,
+        };
+
+        let cursor = |item: &u32| item.to_string();
+
+        // when
+        let first = paginate(vec![1, 2, 3], request(None), cursor);
+        let second = paginate(vec![1, 2, 3], request(first.cursor.clone()), cursor);
+        let third = paginate(vec![1, 2, 3], request(second.cursor.clone()), cursor);
+
+        // then
+        assert_eq!(first.results, [1, 2]);
+        assert!(first.has_next_page);
+        assert_eq!(second.results, [3]);
+        assert!(!second.has_next_page);
+        assert!(third.results.is_empty());
+    }
+}